@@ -54,7 +54,7 @@ import { PlayerUI } from './components/PlayerUI';
 type DbConnection = moduleBindings.DbConnection;
 type EventContext = moduleBindings.EventContext;
 type ErrorContext = moduleBindings.ErrorContext;
-type PlayerData = moduleBindings.PlayerData;
+type PlayerView = moduleBindings.PlayerView;
 type ProjectileData = moduleBindings.ProjectileData;
 type InputState = moduleBindings.InputState;
 // ... other types ...
@@ -65,9 +65,9 @@ function App() {
   const [connected, setConnected] = useState(false);
   const [identity, setIdentity] = useState<Identity | null>(null);
   const [statusMessage, setStatusMessage] = useState("Connecting...");
-  const [players, setPlayers] = useState<ReadonlyMap<string, PlayerData>>(new Map());
+  const [players, setPlayers] = useState<ReadonlyMap<string, PlayerView>>(new Map());
   const [projectiles, setProjectiles] = useState<ReadonlyMap<number, ProjectileData>>(new Map());
-  const [localPlayer, setLocalPlayer] = useState<PlayerData | null>(null);
+  const [localPlayer, setLocalPlayer] = useState<PlayerView | null>(null);
   const [showJoinDialog, setShowJoinDialog] = useState(false);
   const [isDebugPanelExpanded, setIsDebugPanelExpanded] = useState(false);
   const [isPointerLocked, setIsPointerLocked] = useState(false); // State for pointer lock status
@@ -76,6 +76,7 @@ function App() {
   const currentInputRef = useRef<InputState>({
     forward: false, backward: false, left: false, right: false,
     sprint: false, jump: false, attack: false, castSpell: false,
+    dash: false, block: false,
     sequence: 0,
   });
   const lastSentInputState = useRef<Partial<InputState>>({});
@@ -89,9 +90,9 @@ function App() {
     if (!conn) return;
     console.log("Registering table callbacks...");
 
-    conn.db.player.onInsert((_ctx: EventContext, player: PlayerData) => {
+    conn.db.playerView.onInsert((_ctx: EventContext, player: PlayerView) => {
         console.log("Player inserted (callback):", player.identity.toHexString());
-        setPlayers((prev: ReadonlyMap<string, PlayerData>) => new Map(prev).set(player.identity.toHexString(), player));
+        setPlayers((prev: ReadonlyMap<string, PlayerView>) => new Map(prev).set(player.identity.toHexString(), player));
         // Use conn.identity directly to avoid race condition with React state
         const currentIdentity = conn.identity;
         if (currentIdentity && player.identity.toHexString() === currentIdentity.toHexString()) {
@@ -101,8 +102,8 @@ function App() {
         }
     });
 
-    conn.db.player.onUpdate((_ctx: EventContext, _oldPlayer: PlayerData, newPlayer: PlayerData) => {
-        setPlayers((prev: ReadonlyMap<string, PlayerData>) => {
+    conn.db.playerView.onUpdate((_ctx: EventContext, _oldPlayer: PlayerView, newPlayer: PlayerView) => {
+        setPlayers((prev: ReadonlyMap<string, PlayerView>) => {
             const newMap = new Map(prev);
             newMap.set(newPlayer.identity.toHexString(), newPlayer);
             return newMap;
@@ -114,9 +115,9 @@ function App() {
         }
     });
 
-    conn.db.player.onDelete((_ctx: EventContext, player: PlayerData) => {
+    conn.db.playerView.onDelete((_ctx: EventContext, player: PlayerView) => {
         console.log("Player deleted (callback):", player.identity.toHexString());
-        setPlayers((prev: ReadonlyMap<string, PlayerData>) => {
+        setPlayers((prev: ReadonlyMap<string, PlayerView>) => {
             const newMap = new Map(prev);
             newMap.delete(player.identity.toHexString());
             return newMap;
@@ -169,13 +170,13 @@ function App() {
 
   const onSubscriptionApplied = useCallback(() => {
      console.log("Subscription applied successfully.");
-     setPlayers((prev: ReadonlyMap<string, PlayerData>) => {
+     setPlayers((prev: ReadonlyMap<string, PlayerView>) => {
          if (prev.size === 0 && conn) {
-             const currentPlayers = new Map<string, PlayerData>();
+             const currentPlayers = new Map<string, PlayerView>();
              // Use conn.identity directly to avoid race condition with React state
              const currentIdentity = conn.identity;
       
-             for (const player of conn.db.player.iter()) {
+             for (const player of conn.db.playerView.iter()) {
                  currentPlayers.set(player.identity.toHexString(), player);
                  if (currentIdentity && player.identity.toHexString() === currentIdentity.toHexString()) {
                      console.log("Setting localPlayer from subscription:", player.username);
@@ -198,7 +199,7 @@ function App() {
     if (!conn) return;
     console.log("Subscribing to tables...");
     const subscription = conn.subscriptionBuilder();
-    subscription.subscribe("SELECT * FROM player");
+    subscription.subscribe("SELECT * FROM player_view");
     subscription.subscribe("SELECT * FROM projectile");
     subscription.onApplied(onSubscriptionApplied);
     subscription.onError(onSubscriptionError);
@@ -217,7 +218,7 @@ function App() {
   // --- Input State Management ---
   const keyMap: { [key: string]: keyof Omit<InputState, 'sequence' | 'castSpell'> } = {
       KeyW: 'forward', KeyS: 'backward', KeyA: 'left', KeyD: 'right',
-      ShiftLeft: 'sprint', Space: 'jump',
+      ShiftLeft: 'sprint', Space: 'jump', KeyQ: 'dash',
   };
 
   const determineAnimation = useCallback((input: InputState): string => {
@@ -335,6 +336,8 @@ function App() {
            if (!currentInputRef.current.attack) {
                currentInputRef.current.attack = true;
            }
+      } else if (event.button === 2) {
+           currentInputRef.current.block = true;
       }
   }, []);
 
@@ -343,9 +346,16 @@ function App() {
            if (currentInputRef.current.attack) {
                currentInputRef.current.attack = false;
            }
+      } else if (event.button === 2) {
+           currentInputRef.current.block = false;
       }
   }, []);
 
+  // Right mouse button blocks, so keep the browser menu from opening over the game
+  const handleContextMenu = useCallback((event: MouseEvent) => {
+      event.preventDefault();
+  }, []);
+
   // Add mouse move handler with pointer lock for rotation
   const handleMouseMove = useCallback((event: MouseEvent) => {
     // Only rotate if we have pointer lock
@@ -373,20 +383,22 @@ function App() {
       window.addEventListener('keyup', handleKeyUp);
       window.addEventListener('mousedown', handleMouseDown);
       window.addEventListener('mouseup', handleMouseUp);
+      window.addEventListener('contextmenu', handleContextMenu);
       window.addEventListener('mousemove', handleMouseMove); // Add mouse move listener
       document.addEventListener('pointerlockchange', handlePointerLockChange); // Listen for lock changes
       console.log("Input listeners added.");
-  }, [handleKeyDown, handleKeyUp, handleMouseDown, handleMouseUp, handleMouseMove, handlePointerLockChange]);
+  }, [handleKeyDown, handleKeyUp, handleMouseDown, handleMouseUp, handleContextMenu, handleMouseMove, handlePointerLockChange]);
 
   const removeInputListeners = useCallback(() => {
       window.removeEventListener('keydown', handleKeyDown);
       window.removeEventListener('keyup', handleKeyUp);
       window.removeEventListener('mousedown', handleMouseDown);
       window.removeEventListener('mouseup', handleMouseUp);
+      window.removeEventListener('contextmenu', handleContextMenu);
       window.removeEventListener('mousemove', handleMouseMove); // Remove mouse move listener
       document.removeEventListener('pointerlockchange', handlePointerLockChange); // Remove listener
       console.log("Input listeners removed.");
-  }, [handleKeyDown, handleKeyUp, handleMouseDown, handleMouseUp, handleMouseMove, handlePointerLockChange]);
+  }, [handleKeyDown, handleKeyUp, handleMouseDown, handleMouseUp, handleContextMenu, handleMouseMove, handlePointerLockChange]);
 
   const setupDelegatedListeners = useCallback(() => {
       document.body.addEventListener('click', handleDelegatedClick, true);
@@ -35,13 +35,13 @@
 import React, { useState } from 'react';
 import { Identity } from '@clockworklabs/spacetimedb-sdk';
 // Import generated type, assuming path from components dir
-import { PlayerData } from '../generated'; 
+import { PlayerView } from '../generated'; 
 
 interface DebugPanelProps {
   statusMessage: string;
-  localPlayer: PlayerData | null;
+  localPlayer: PlayerView | null;
   identity: Identity | null;
-  playerMap: ReadonlyMap<string, PlayerData>; // Pass the whole map
+  playerMap: ReadonlyMap<string, PlayerView>; // Pass the whole map
   expanded: boolean; // Receive expansion state from parent
   onToggleExpanded: () => void; // Receive toggle function from parent
   isPointerLocked: boolean; // Receive pointer lock state from parent
@@ -109,7 +109,7 @@ export const DebugPanel: React.FC<DebugPanelProps> = ({
   };
 
   // Derive player list array inside the component
-  const playerList: PlayerData[] = Array.from(playerMap.values()).sort((a, b) => 
+  const playerList: PlayerView[] = Array.from(playerMap.values()).sort((a, b) => 
     a.identity.toHexString().localeCompare(b.identity.toHexString())
   );
 
@@ -36,13 +36,13 @@ import { Box, Plane, Grid, Sky } from '@react-three/drei';
 import * as THREE from 'three';
 import { DirectionalLightHelper, CameraHelper } from 'three'; // Import the helper
 // Import generated types
-import { PlayerData, ProjectileData, InputState } from '../generated';
+import { PlayerView, ProjectileData, InputState } from '../generated';
 import { Identity } from '@clockworklabs/spacetimedb-sdk';
 import { Player } from './Player';
 import { FireBall } from '../shaders/FireShader';
 
 interface GameSceneProps {
-  players: ReadonlyMap<string, PlayerData>; // Receive the map
+  players: ReadonlyMap<string, PlayerView>; // Receive the map
   projectiles: ReadonlyMap<number, ProjectileData>; // Receive projectiles map
   localPlayerIdentity: Identity | null;
   onPlayerRotation?: (rotation: THREE.Euler) => void; // Optional callback for player rotation
@@ -155,7 +155,7 @@ export const GameScene: React.FC<GameSceneProps> = ({
 };
 
 // Smooth projectile component with homing but dodgable behavior
-function SmoothProjectile({ projectile, players }: { projectile: ProjectileData, players: ReadonlyMap<string, PlayerData> }) {
+function SmoothProjectile({ projectile, players }: { projectile: ProjectileData, players: ReadonlyMap<string, PlayerView> }) {
   const meshRef = useRef<THREE.Mesh>(null);
   const currentPosition = useRef(new THREE.Vector3(projectile.position.x, projectile.position.y, projectile.position.z));
   const velocity = useRef(new THREE.Vector3(0, 0, 0));
@@ -36,7 +36,7 @@ import { useFrame, useThree } from '@react-three/fiber';
 import * as THREE from 'three';
 import { useAnimations, Html, Sphere } from '@react-three/drei';
 import { FBXLoader } from 'three/examples/jsm/loaders/FBXLoader.js';
-import { PlayerData, InputState } from '../generated';
+import { PlayerView, InputState } from '../generated';
 
 // Define animation names for reuse
 const ANIMATIONS = {
@@ -59,6 +59,8 @@ const ANIMATIONS = {
 // --- Client-side Constants ---
 const PLAYER_SPEED = 5.0; // Match server logic
 const SPRINT_MULTIPLIER = 1.8; // Match server logic
+const MOVEMENT_ACCELERATION = 6.0; // Match server MOVEMENT_ACCELERATION (speed gained per second)
+const MOVEMENT_DECELERATION = 15.0; // Match server MOVEMENT_DECELERATION (speed lost per second)
 
 // --- Client-side Prediction Constants ---
 const SERVER_TICK_RATE = 60; // Assuming server runs at 60Hz
@@ -74,7 +76,7 @@ const CAMERA_MODES = {
 };
 
 interface PlayerProps {
-  playerData: PlayerData;
+  playerData: PlayerView;
   isLocalPlayer: boolean;
   onRotationChange?: (rotation: THREE.Euler) => void;
   currentInput?: InputState; // Prop to receive current input for local player
@@ -92,7 +94,7 @@ export const Player: React.FC<PlayerProps> = ({
 }) => {
   const group = useRef<THREE.Group>(null!);
   const { camera } = useThree();
-  const dataRef = useRef<PlayerData>(playerData);
+  const dataRef = useRef<PlayerView>(playerData);
   const characterClass = playerData.characterClass || 'Wizard';
   
   // Model management
@@ -105,6 +107,7 @@ export const Player: React.FC<PlayerProps> = ({
   // --- Client Prediction State ---
   const localPositionRef = useRef<THREE.Vector3>(new THREE.Vector3(playerData.position.x, playerData.position.y, playerData.position.z));
   const localRotationRef = useRef<THREE.Euler>(new THREE.Euler(0, 0, 0, 'YXZ')); // Initialize with zero rotation
+  const predictedSpeedRef = useRef<number>(0); // Ramped like the server's current_speed
   const debugArrowRef = useRef<THREE.ArrowHelper | null>(null); // Declare the ref for the debug arrow
   
   // Camera control variables
@@ -139,14 +142,22 @@ export const Player: React.FC<PlayerProps> = ({
   // --- Client-Side Movement Calculation (Matches Server Logic *before* Sign Flip) ---
   const calculateClientMovement = useCallback((currentPos: THREE.Vector3, currentRot: THREE.Euler, inputState: InputState, delta: number): THREE.Vector3 => {
     // console.log(`[Move Calc] cameraMode: ${cameraMode}`); // Suppressed log
-    
+    const isMoving = inputState.forward || inputState.backward || inputState.left || inputState.right;
+
+    // Ramp toward the target speed before moving, in the same order as the server's update_input_state
+    const targetSpeed = !isMoving ? 0 : inputState.sprint ? PLAYER_SPEED * SPRINT_MULTIPLIER : PLAYER_SPEED;
+    const currentSpeed = predictedSpeedRef.current;
+    predictedSpeedRef.current = currentSpeed < targetSpeed
+      ? Math.min(currentSpeed + MOVEMENT_ACCELERATION * delta, targetSpeed)
+      : Math.max(currentSpeed - MOVEMENT_DECELERATION * delta, targetSpeed);
+
     // Skip if no movement input
-    if (!inputState.forward && !inputState.backward && !inputState.left && !inputState.right) {
+    if (!isMoving) {
       return currentPos;
     }
 
     let worldMoveVector = new THREE.Vector3();
-    const speed = inputState.sprint ? PLAYER_SPEED * SPRINT_MULTIPLIER : PLAYER_SPEED;
+    const speed = predictedSpeedRef.current;
     let rotationYaw = 0;
 
     // 1. Calculate local movement vector based on WASD
@@ -25,11 +25,11 @@
  */
 
 import React, { useState, useEffect, useCallback } from 'react';
-import { PlayerData } from '../generated';
+import { PlayerView } from '../generated';
 import * as moduleBindings from '../generated';
 
 interface PlayerUIProps {
-  playerData: PlayerData | null;
+  playerData: PlayerView | null;
   connection: moduleBindings.DbConnection | null;
 }
 
@@ -0,0 +1,145 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - account_link_logic.rs
+ *
+ * This file contains account links: a player-chosen key that carries progression
+ * over to a new identity (another browser, device or a cleared token).
+ *
+ * Key components:
+ *
+ * 1. Linking:
+ *    - link_account: Links the caller's identity to a key. A key nobody uses is simply
+ *      linked (replacing the caller's previous key, if any). A key linked to another
+ *      identity is a claim: the link moves to the caller and remembers the old
+ *      identity in previous_identity, so the next registration can pick up its
+ *      progression
+ *    - Conflicts: a claim is refused while the linked identity is playing, when that
+ *      identity is banned, or when the caller already has progression of their own
+ *      (active or logged out), since restoring would overwrite it
+ *
+ * 2. Restoring:
+ *    - restore_linked_progression: Called by registration before it looks up the
+ *      logged-out row. Moves the previous identity's LoggedOutPlayerData (and
+ *      autosave snapshot) to the new identity, so the normal rejoin path restores it
+ *
+ * When modifying:
+ *    - The key is the only proof of ownership, AccountLink must stay private
+ *    - Keys are trimmed but otherwise compared exactly (case-sensitive)
+ *
+ * Related files:
+ *    - lib.rs: AccountLink table, register_player_as and the link_account reducer
+ *    - autosave_logic.rs: PlayerSnapshot, restored when there is no logged-out row
+ */
+
+use spacetimedb::{ReducerContext, Identity, Table};
+use crate::common::{MIN_ACCOUNT_KEY_LENGTH, MAX_ACCOUNT_KEY_LENGTH};
+use crate::{ban_logic, AccountLink, LoggedOutPlayerData, PlayerSnapshot};
+// Import table traits
+use crate::{account_link, logged_out_player, player, player_snapshot};
+
+pub fn link_account(ctx: &ReducerContext, key: String) -> Result<(), String> {
+    let key = validate_account_key(&key)?;
+
+    let Some(existing) = ctx.db.account_link().key().find(&key) else {
+        // Unused key: link it, dropping the caller's previous key
+        ctx.db.account_link().identity().delete(ctx.sender);
+        ctx.db.account_link().insert(AccountLink {
+            key,
+            identity: ctx.sender,
+            previous_identity: None,
+            linked_at: ctx.timestamp,
+        });
+        spacetimedb::log::info!("Linked an account key to {}.", ctx.sender);
+        return Ok(());
+    };
+    if existing.identity == ctx.sender {
+        return Ok(());
+    }
+
+    // The key belongs to another identity: claim it for the caller
+    if ctx.db.player().identity().find(existing.identity).is_some() {
+        return Err("That account is in use by an active player.".to_string());
+    }
+    if ban_logic::is_banned(ctx, existing.identity) {
+        return Err("That account is banned.".to_string());
+    }
+    if ctx.db.player().identity().find(ctx.sender).is_some()
+        || ctx.db.logged_out_player().identity().find(ctx.sender).is_some()
+    {
+        return Err("This identity already has its own progression and cannot claim another account.".to_string());
+    }
+
+    ctx.db.account_link().identity().delete(ctx.sender);
+    spacetimedb::log::info!("Account key moved from {} to {}.", existing.identity, ctx.sender);
+    ctx.db.account_link().key().update(AccountLink {
+        identity: ctx.sender,
+        previous_identity: Some(existing.identity),
+        linked_at: ctx.timestamp,
+        ..existing
+    });
+    Ok(())
+}
+
+// Moves a claimed account's progression onto player_identity, if there is any to move
+pub fn restore_linked_progression(ctx: &ReducerContext, player_identity: Identity) {
+    let Some(mut link) = ctx.db.account_link().identity().find(player_identity) else {
+        return;
+    };
+    let Some(previous_identity) = link.previous_identity.take() else {
+        return;
+    };
+    ctx.db.account_link().key().update(link);
+
+    if ctx.db.logged_out_player().identity().find(player_identity).is_some() {
+        spacetimedb::log::warn!("Not restoring {} onto {}: it has progression of its own.", previous_identity, player_identity);
+        return;
+    }
+    if let Some(logged_out_player) = ctx.db.logged_out_player().identity().find(previous_identity) {
+        ctx.db.logged_out_player().identity().delete(previous_identity);
+        ctx.db.logged_out_player().insert(LoggedOutPlayerData { identity: player_identity, ..logged_out_player });
+    }
+    if let Some(snapshot) = ctx.db.player_snapshot().identity().find(previous_identity) {
+        ctx.db.player_snapshot().identity().delete(previous_identity);
+        ctx.db.player_snapshot().identity().delete(player_identity);
+        ctx.db.player_snapshot().insert(PlayerSnapshot { identity: player_identity, ..snapshot });
+    }
+    spacetimedb::log::info!("Restored linked progression of {} onto {}.", previous_identity, player_identity);
+}
+
+// Trim a key and check its length and characters
+fn validate_account_key(key: &str) -> Result<String, String> {
+    let key = key.trim().to_string();
+    let key_length = key.chars().count();
+    if !(MIN_ACCOUNT_KEY_LENGTH..=MAX_ACCOUNT_KEY_LENGTH).contains(&key_length) {
+        return Err(format!(
+            "Account key must be between {} and {} characters.",
+            MIN_ACCOUNT_KEY_LENGTH, MAX_ACCOUNT_KEY_LENGTH
+        ));
+    }
+    if key.chars().any(char::is_control) {
+        return Err("Account key cannot contain control characters.".to_string());
+    }
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_keys_are_trimmed() {
+        assert_eq!(validate_account_key("  correct-horse  "), Ok("correct-horse".to_string()));
+    }
+
+    #[test]
+    fn account_key_length_is_bounded() {
+        assert!(validate_account_key(&"k".repeat(MIN_ACCOUNT_KEY_LENGTH - 1)).is_err());
+        assert!(validate_account_key(&"k".repeat(MIN_ACCOUNT_KEY_LENGTH)).is_ok());
+        assert!(validate_account_key(&"k".repeat(MAX_ACCOUNT_KEY_LENGTH)).is_ok());
+        assert!(validate_account_key(&"k".repeat(MAX_ACCOUNT_KEY_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn account_keys_reject_control_characters() {
+        assert!(validate_account_key("secret\u{7}key").is_err());
+    }
+}
@@ -0,0 +1,88 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - arena_logic.rs
+ *
+ * This file contains the public description of the arena clients build the map from.
+ *
+ * Key components:
+ *
+ * 1. Arena Descriptor:
+ *    - arena_info: Builds the ArenaInfo row from the server constants: world bounds
+ *      (WORLD_HALF_EXTENT), GROUND_HEIGHT, the spawn points from
+ *      player_logic::spawn_position and the static geometry list
+ *    - static_geometry: The ground plane and the duel arena
+ *    - sync_arena_info: Writes (or rewrites) the row, called from init
+ *
+ * When modifying:
+ *    - Anything that changes the shape of the world (bounds, new static obstacles)
+ *      should be listed here, clients don't hard-code map data
+ *
+ * Related files:
+ *    - common.rs: Arena constants and the ArenaGeometry struct
+ *    - lib.rs: ArenaInfo table definition and init
+ */
+
+use spacetimedb::{ReducerContext, Table};
+use crate::common::{
+    Vector3, ArenaGeometry, WORLD_HALF_EXTENT, GROUND_HEIGHT, SPAWN_POINT_COUNT, DUEL_ARENA_CENTER,
+    DUEL_ARENA_HALF_DISTANCE,
+};
+use crate::player_logic;
+use crate::ArenaInfo;
+// Import table traits
+use crate::arena_info;
+
+// ArenaInfo is a single-row table keyed by this id
+pub const ARENA_INFO_ID: u32 = 0;
+
+pub const GEOMETRY_GROUND: &str = "ground";
+pub const GEOMETRY_DUEL_ARENA: &str = "duel_arena";
+
+pub fn static_geometry() -> Vec<ArenaGeometry> {
+    vec![
+        // Flat floor covering the playable area, its top is at y = 0
+        ArenaGeometry {
+            kind: GEOMETRY_GROUND.to_string(),
+            center: Vector3 { x: 0.0, y: 0.0, z: 0.0 },
+            size: Vector3 { x: WORLD_HALF_EXTENT * 2.0, y: 0.0, z: WORLD_HALF_EXTENT * 2.0 },
+        },
+        // Where accepted duels take place (duelists start on either side of the center)
+        ArenaGeometry {
+            kind: GEOMETRY_DUEL_ARENA.to_string(),
+            center: DUEL_ARENA_CENTER,
+            size: Vector3 { x: DUEL_ARENA_HALF_DISTANCE * 2.0, y: 0.0, z: DUEL_ARENA_HALF_DISTANCE * 2.0 },
+        },
+    ]
+}
+
+pub fn arena_info() -> ArenaInfo {
+    ArenaInfo {
+        id: ARENA_INFO_ID,
+        half_extent: WORLD_HALF_EXTENT,
+        ground_height: GROUND_HEIGHT,
+        spawn_points: (0..SPAWN_POINT_COUNT).map(player_logic::spawn_position).collect(),
+        geometry: static_geometry(),
+    }
+}
+
+pub fn sync_arena_info(ctx: &ReducerContext) {
+    let info = arena_info();
+    if ctx.db.arena_info().id().find(ARENA_INFO_ID).is_some() {
+        ctx.db.arena_info().id().update(info);
+    } else {
+        ctx.db.arena_info().insert(info);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arena_info_describes_spawns_inside_the_playable_area() {
+        let info = arena_info();
+        assert_eq!(info.spawn_points.len(), SPAWN_POINT_COUNT);
+        assert!(info.spawn_points.iter().all(|spawn| !player_logic::is_out_of_bounds(spawn)));
+        assert!(info.geometry.iter().any(|g| g.kind == GEOMETRY_GROUND));
+        assert!(info.geometry.iter().any(|g| g.kind == GEOMETRY_DUEL_ARENA));
+    }
+}
@@ -0,0 +1,110 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - autosave_logic.rs
+ *
+ * This file backs up active players' progression so it survives a module crash.
+ * LoggedOutPlayerData is only written on a clean disconnect, so without this an
+ * active player's kills/level/xp/score would be lost if identity_disconnected never ran.
+ *
+ * Key components:
+ *
+ * 1. Game Tick:
+ *    - autosave_players: Upserts a PlayerSnapshot for every active player (bots aside) whose
+ *      snapshot is missing or older than AUTOSAVE_INTERVAL_MICROS
+ *
+ * 2. Restore:
+ *    - restore_from_snapshot: Copies saved progression onto a player registering
+ *      without a LoggedOutPlayerData row
+ *
+ * When modifying:
+ *    - Keep PlayerSnapshot's fields in line with the progression fields copied to
+ *      LoggedOutPlayerData on logout
+ *
+ * Related files:
+ *    - lib.rs: PlayerSnapshot table, register_player and identity_disconnected
+ *      (which removes the snapshot once the logged-out row is written)
+ */
+
+use spacetimedb::{ReducerContext, Table, Timestamp};
+use crate::common::AUTOSAVE_INTERVAL_MICROS;
+use crate::{PlayerData, PlayerSnapshot};
+// Import table traits
+use crate::{player, player_snapshot};
+
+fn snapshot_for(player: &PlayerData, now: Timestamp) -> PlayerSnapshot {
+    PlayerSnapshot {
+        identity: player.identity,
+        kills: player.kills,
+        deaths: player.deaths,
+        level: player.level,
+        xp: player.xp,
+        score: player.score,
+        shots_fired: player.shots_fired,
+        shots_hit: player.shots_hit,
+        saved_at: now,
+    }
+}
+
+pub fn autosave_players(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let mut saved = 0;
+    for player in ctx.db.player().iter().filter(|p| !p.is_bot) {
+        match ctx.db.player_snapshot().identity().find(player.identity) {
+            Some(existing) => {
+                if now_micros - existing.saved_at.to_micros_since_unix_epoch() < AUTOSAVE_INTERVAL_MICROS {
+                    continue;
+                }
+                ctx.db.player_snapshot().identity().update(snapshot_for(&player, ctx.timestamp));
+            }
+            None => {
+                ctx.db.player_snapshot().insert(snapshot_for(&player, ctx.timestamp));
+            }
+        }
+        saved += 1;
+    }
+    if saved > 0 {
+        spacetimedb::log::debug!("Autosaved {} player(s)", saved);
+    }
+}
+
+// Returns true if a snapshot was found and applied
+pub fn restore_from_snapshot(ctx: &ReducerContext, player: &mut PlayerData) -> bool {
+    let Some(snapshot) = ctx.db.player_snapshot().identity().find(player.identity) else {
+        return false;
+    };
+    spacetimedb::log::info!("Restoring progression for {} from autosave", player.identity);
+    apply_snapshot(player, &snapshot);
+    true
+}
+
+fn apply_snapshot(player: &mut PlayerData, snapshot: &PlayerSnapshot) {
+    player.kills = snapshot.kills;
+    player.deaths = snapshot.deaths;
+    player.level = snapshot.level;
+    player.xp = snapshot.xp;
+    player.score = snapshot.score;
+    player.shots_fired = snapshot.shots_fired;
+    player.shots_hit = snapshot.shots_hit;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at_micros, player};
+
+    #[test]
+    fn snapshots_restore_saved_progression() {
+        let veteran = PlayerData {
+            kills: 7, deaths: 3, level: 4, xp: 250, score: 900, shots_fired: 40, shots_hit: 22,
+            ..player(1)
+        };
+        let snapshot = snapshot_for(&veteran, at_micros(5_000_000));
+        assert_eq!(snapshot.saved_at, at_micros(5_000_000));
+
+        let mut returning = player(1);
+        apply_snapshot(&mut returning, &snapshot);
+        assert_eq!(
+            (returning.kills, returning.deaths, returning.level, returning.xp, returning.score, returning.shots_fired, returning.shots_hit),
+            (7, 3, 4, 250, 900, 40, 22),
+        );
+    }
+}
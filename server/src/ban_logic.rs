@@ -0,0 +1,81 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - ban_logic.rs
+ *
+ * This file contains banning identities from the game.
+ *
+ * Key components:
+ *
+ * 1. Banning:
+ *    - ban_player: Adds the identity to the Ban table and, if they're playing, evicts
+ *      them right away through the same logout path as a disconnect. Their
+ *      logged-out row is then deleted, so nothing can restore them later
+ *      (auto-rejoin, join queue). Queue and spectator entries are removed too
+ *    - validate_ban: Refuses self-bans and banning an identity twice
+ *    - unban_player: Removes the ban; progression deleted by the ban is not restored
+ *
+ * 2. Checks:
+ *    - is_banned: Used by registration (which also covers auto-rejoin and queue
+ *      admission) and start_spectating
+ *
+ * Related files:
+ *    - lib.rs: Ban table, logout_player and the ban_player/unban_player reducers
+ */
+
+use spacetimedb::{ReducerContext, Identity, Table};
+use crate::{logout_player, Ban};
+// Import table traits
+use crate::{ban, join_queue, logged_out_player, look_target, player, spectator};
+
+pub fn is_banned(ctx: &ReducerContext, identity: Identity) -> bool {
+    ctx.db.ban().identity().find(identity).is_some()
+}
+
+pub fn ban_player(ctx: &ReducerContext, identity: Identity, reason: String) -> Result<(), String> {
+    validate_ban(ctx.sender, identity, is_banned(ctx, identity))?;
+    ctx.db.ban().insert(Ban { identity, reason, banned_by: ctx.sender, banned_at: ctx.timestamp });
+
+    if let Some(player) = ctx.db.player().identity().find(identity) {
+        spacetimedb::log::info!("Evicting banned player {}.", identity);
+        logout_player(ctx, player);
+    }
+    ctx.db.logged_out_player().identity().delete(identity);
+    ctx.db.join_queue().identity().delete(identity);
+    ctx.db.spectator().identity().delete(identity);
+    ctx.db.look_target().observer().delete(identity);
+    Ok(())
+}
+
+// Admins can't lock themselves out, and a ban is only recorded once
+fn validate_ban(admin: Identity, identity: Identity, already_banned: bool) -> Result<(), String> {
+    if identity == admin {
+        return Err("Admins cannot ban themselves.".to_string());
+    }
+    if already_banned {
+        return Err("Player is already banned.".to_string());
+    }
+    Ok(())
+}
+
+pub fn unban_player(ctx: &ReducerContext, identity: Identity) -> Result<(), String> {
+    if !ctx.db.ban().identity().delete(identity) {
+        return Err("Player is not banned.".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::identity;
+
+    #[test]
+    fn admins_can_ban_other_players() {
+        assert!(validate_ban(identity(1), identity(2), false).is_ok());
+    }
+
+    #[test]
+    fn admins_cannot_ban_themselves_or_ban_twice() {
+        assert!(validate_ban(identity(1), identity(1), false).is_err());
+        assert!(validate_ban(identity(1), identity(2), true).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - bot_logic.rs
+ *
+ * This file contains server-driven bots for load testing and PvE filler.
+ *
+ * Key components:
+ *
+ * 1. Spawning:
+ *    - spawn_bot: Registers a PlayerData row for a synthetic identity (bot_identity)
+ *      through the normal registration path, then flags it is_bot (with PvP on) and adds
+ *      its BotState
+ *    - remove_bot: Removes a bot through logout_player (which keeps nothing for bots)
+ *
+ * 2. Game Tick:
+ *    - update_bots: Drives every living bot. A bot with an enemy within BOT_AGGRO_RANGE
+ *      turns to face them, closes in to BOT_PREFERRED_RANGE and casts its first equipped
+ *      spell every BOT_CAST_INTERVAL_MICROS through cast_spell_as. Without one it wanders,
+ *      changing heading every BOT_WANDER_INTERVAL_MICROS. Bots walk at the configured
+ *      player_speed, stay in bounds and are separated from other players like movers.
+ *      Dead bots respawn through update_players_logic like anyone else
+ *
+ * When modifying:
+ *    - Bots are excluded from HighScore, autosave and persistence on logout, and can't
+ *      vote in (or block) vote kicks
+ *
+ * Related files:
+ *    - lib.rs: BotState table, register_player_as, cast_spell_as and the spawn_bot/remove_bot reducers
+ *    - player_logic.rs: facing_direction, bounds and separation helpers
+ */
+
+use spacetimedb::{ReducerContext, Identity, Table, Timestamp};
+use crate::common::{
+    GAME_TICK_MICROS, BOT_AGGRO_RANGE, BOT_PREFERRED_RANGE, BOT_CAST_INTERVAL_MICROS, BOT_WANDER_INTERVAL_MICROS,
+    BOT_WANDER_TURN_RADIANS,
+};
+use crate::player_logic::{clamp_to_bounds, facing_direction, separate_from_players};
+use crate::{
+    calculate_distance, cast_spell_as, class_logic, config_logic, logout_player, register_player_as, team_logic, BotState,
+    PlayerData,
+};
+// Import table traits
+use crate::{bot_state, player};
+
+// Synthetic identity for bot number `n`, recognizable by its "bot:" prefix
+fn bot_identity(n: u64) -> Identity {
+    let mut bytes = [0u8; 32];
+    bytes[..4].copy_from_slice(b"bot:");
+    bytes[24..].copy_from_slice(&n.to_be_bytes());
+    Identity::from_byte_array(bytes)
+}
+
+pub fn spawn_bot(ctx: &ReducerContext, character_class: String) -> Result<Identity, String> {
+    // Lowest bot number not currently in use
+    let n = (1..)
+        .find(|n| ctx.db.player().identity().find(bot_identity(*n)).is_none())
+        .unwrap_or(0);
+    let identity = bot_identity(n);
+    register_player_as(ctx, identity, format!("Bot {}", n), character_class)?;
+
+    let Some(mut bot) = ctx.db.player().identity().find(identity) else {
+        return Err("Bot registration did not create a player.".to_string());
+    };
+    bot.is_bot = true;
+    bot.pvp_enabled = true; // Anyone with PvP on can fight bots
+    ctx.db.player().identity().update(bot);
+    ctx.db.bot_state().identity().delete(identity);
+    ctx.db.bot_state().insert(BotState {
+        identity,
+        wander_yaw: 0.0,
+        next_wander_at: ctx.timestamp,
+        next_cast_at: ctx.timestamp,
+    });
+    Ok(identity)
+}
+
+pub fn remove_bot(ctx: &ReducerContext, identity: Identity) -> Result<(), String> {
+    let Some(bot) = ctx.db.player().identity().find(identity).filter(|p| p.is_bot) else {
+        return Err("Bot not found.".to_string());
+    };
+    ctx.db.bot_state().identity().delete(identity);
+    logout_player(ctx, bot);
+    Ok(())
+}
+
+// Move and cast for every bot (called from game_tick)
+pub fn update_bots(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let config = config_logic::get_config(ctx);
+    let delta_time = GAME_TICK_MICROS as f32 / 1_000_000.0;
+
+    for mut state in ctx.db.bot_state().iter() {
+        let Some(mut bot) = ctx.db.player().identity().find(state.identity) else {
+            // The bot left (removed or logged out at shutdown)
+            ctx.db.bot_state().identity().delete(state.identity);
+            continue;
+        };
+        if bot.health <= 0 {
+            continue;
+        }
+
+        let target = nearest_enemy(ctx, &bot, config.team_count);
+        let (step, cast) = match &target {
+            Some((enemy, distance)) => {
+                let dx = enemy.position.x - bot.position.x;
+                let dz = enemy.position.z - bot.position.z;
+                bot.rotation.y = dx.atan2(dz); // Inverse of facing_direction
+                let step = chase_step(config.player_speed * delta_time, *distance);
+                (step, now_micros >= state.next_cast_at.to_micros_since_unix_epoch())
+            }
+            None => {
+                if now_micros >= state.next_wander_at.to_micros_since_unix_epoch() {
+                    state.wander_yaw = (state.wander_yaw + BOT_WANDER_TURN_RADIANS) % std::f32::consts::TAU;
+                    state.next_wander_at = Timestamp::from_micros_since_unix_epoch(now_micros + BOT_WANDER_INTERVAL_MICROS);
+                }
+                bot.rotation.y = state.wander_yaw;
+                (config.player_speed * delta_time, false)
+            }
+        };
+
+        let direction = facing_direction(&bot.rotation);
+        bot.position.x += direction.x * step;
+        bot.position.z += direction.z * step;
+        clamp_to_bounds(&mut bot.position);
+        separate_from_players(ctx, &mut bot);
+        bot.is_moving = step > 0.0;
+        bot.is_running = false;
+        bot.current_speed = if bot.is_moving { config.player_speed } else { 0.0 };
+        let animation_state = if bot.is_moving { "walk-forward" } else { "idle" };
+        bot.current_animation = class_logic::class_animation(ctx, &bot.character_class, animation_state);
+        bot.last_input_at = ctx.timestamp; // Bots are never AFK
+        let spell = bot.equipped_spells.first().cloned();
+        let identity = bot.identity;
+        ctx.db.player().identity().update(bot);
+
+        if cast {
+            if let Some(spell) = spell {
+                if let Err(e) = cast_spell_as(ctx, identity, spell) {
+                    spacetimedb::log::debug!("Bot {} could not cast: {}", identity, e);
+                }
+            }
+            state.next_cast_at = Timestamp::from_micros_since_unix_epoch(now_micros + BOT_CAST_INTERVAL_MICROS);
+        }
+        ctx.db.bot_state().identity().update(state);
+    }
+}
+
+// Close in on a target by at most `max_step`, stopping at BOT_PREFERRED_RANGE
+fn chase_step(max_step: f32, distance: f32) -> f32 {
+    max_step.min((distance - BOT_PREFERRED_RANGE).max(0.0))
+}
+
+// Closest living enemy of the bot within BOT_AGGRO_RANGE, with its distance
+fn nearest_enemy(ctx: &ReducerContext, bot: &PlayerData, team_count: u32) -> Option<(PlayerData, f32)> {
+    ctx.db.player().iter()
+        .filter(|p| p.identity != bot.identity && p.health > 0)
+        .filter(|p| !team_logic::is_ally(team_count, bot.team, p.team))
+        .map(|p| {
+            let distance = calculate_distance(&bot.position, &p.position);
+            (p, distance)
+        })
+        .filter(|(_, distance)| *distance <= BOT_AGGRO_RANGE)
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bot_identities_are_prefixed_and_distinct() {
+        assert_eq!(&bot_identity(1).to_byte_array()[..4], b"bot:");
+        assert_ne!(bot_identity(1), bot_identity(2));
+    }
+
+    #[test]
+    fn chase_stops_at_preferred_range() {
+        assert!((chase_step(1.0, BOT_PREFERRED_RANGE + 5.0) - 1.0).abs() < 1e-5);
+        assert!((chase_step(1.0, BOT_PREFERRED_RANGE + 0.25) - 0.25).abs() < 1e-5);
+        assert_eq!(chase_step(1.0, BOT_PREFERRED_RANGE - 1.0), 0.0);
+    }
+}
@@ -0,0 +1,209 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - channel_logic.rs
+ *
+ * This file contains channeled spells (beams) that keep draining mana and
+ * dealing damage for as long as the caster sustains them.
+ *
+ * Key components:
+ *
+ * 1. Channel Control:
+ *    - start_channel: Starts a beam, or keeps an active one alive. Clients call it
+ *      repeatedly while the button is held; the beam ends CHANNEL_KEEPALIVE_MICROS
+ *      after the last call
+ *    - stop_channel: Ends the caller's beam immediately (button released)
+ *    - end_channel: Also used by combat_logic::handle_death, dying ends a channel
+ *
+ *    - channel_spell: Channeled spells are SpellDefinitions with channel_mana_per_tick > 0
+ *
+ * 2. Game Tick:
+ *    - update_channels: Ends channels whose keep-alives stopped, pays each channel's
+ *      upkeep and damages the nearest enemy within BEAM_RANGE in front of the caster
+ *      (channel_target, for client beams) by the spell's damage
+ *    - tick_channel_upkeep: Deducts a tick's mana cost. A tick the player can't pay
+ *      for never fires, and the channel ends as soon as the remaining mana can't
+ *      cover the next tick
+ *
+ * Related files:
+ *    - combat_logic.rs: Damage helper (beam hits can be blocked like any frontal hit)
+ *    - lib.rs: start_channel/stop_channel reducers and the channel fields on PlayerData
+ *    - spell_logic.rs: The beam's SpellDefinition (damage and channel_mana_per_tick)
+ */
+
+use spacetimedb::{ReducerContext, Identity, Table, Timestamp};
+use crate::common::{BEAM_RANGE, BEAM_HALF_WIDTH, CHANNEL_KEEPALIVE_MICROS, COLLISION_LAYER_ENEMY};
+use crate::player_logic::facing_direction;
+use crate::projectile_logic::{can_collide, collision_layer_for_player};
+use crate::spell_logic::normalize_spell_name;
+use crate::{combat_logic, config_logic, PlayerData, SpellDefinition};
+// Import table traits
+use crate::{player, spell_definition};
+
+pub const BEAM_SPELL_NAME: &str = "beam";
+
+pub fn start_channel(ctx: &ReducerContext, spell_name: &str) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can channel.".to_string());
+    };
+    let spell_name = normalize_spell_name(spell_name);
+    let Some(spell) = channel_spell(ctx, &spell_name) else {
+        return Err(format!("'{}' is not a channeled spell.", spell_name));
+    };
+    if player.health <= 0 {
+        return Err("Dead players cannot channel.".to_string());
+    }
+    if player.mana < spell.channel_mana_per_tick {
+        return Err("Not enough mana.".to_string());
+    }
+
+    if player.channeling_spell.is_none() {
+        spacetimedb::log::info!("Player {} started channeling {}", player.identity, spell_name);
+    }
+    player.channeling_spell = Some(spell_name);
+    player.channel_until = Timestamp::from_micros_since_unix_epoch(
+        ctx.timestamp.to_micros_since_unix_epoch() + CHANNEL_KEEPALIVE_MICROS
+    );
+    ctx.db.player().identity().update(player);
+    Ok(())
+}
+
+// The definition of a channeled spell, None for unknown or non-channeled spells
+pub fn channel_spell(ctx: &ReducerContext, spell_name: &str) -> Option<SpellDefinition> {
+    ctx.db.spell_definition().name().find(spell_name.to_string())
+        .filter(|spell| spell.channel_mana_per_tick > 0)
+}
+
+pub fn stop_channel(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can channel.".to_string());
+    };
+    if player.channeling_spell.is_some() {
+        end_channel(&mut player);
+        ctx.db.player().identity().update(player);
+    }
+    Ok(())
+}
+
+pub fn end_channel(player: &mut PlayerData) {
+    spacetimedb::log::info!("Player {} stopped channeling", player.identity);
+    player.channeling_spell = None;
+    player.channel_target = None;
+}
+
+// Pay one tick of a channel. Returns false (and ends the channel) if the player can't
+// afford it; after a paid tick the channel also ends once mana can't cover the next one.
+pub fn tick_channel_upkeep(player: &mut PlayerData, mana_per_tick: i32) -> bool {
+    if player.mana < mana_per_tick {
+        end_channel(player);
+        return false;
+    }
+    player.mana -= mana_per_tick;
+    if player.mana < mana_per_tick {
+        spacetimedb::log::info!("Player {} ran out of mana for their channel", player.identity);
+        end_channel(player);
+    }
+    true
+}
+
+pub fn update_channels(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let team_count = config_logic::get_config(ctx).team_count;
+    let channelers: Vec<Identity> = ctx.db.player().iter()
+        .filter(|p| p.channeling_spell.is_some())
+        .map(|p| p.identity)
+        .collect();
+
+    for identity in channelers {
+        // Re-read: an earlier beam this tick may have killed this caster, ending their channel
+        let Some(mut caster) = ctx.db.player().identity().find(identity) else {
+            continue;
+        };
+        if caster.channeling_spell.is_none() {
+            continue;
+        }
+        let spell = caster.channeling_spell.as_deref().and_then(|name| channel_spell(ctx, name));
+        let expired = caster.health <= 0 || now_micros >= caster.channel_until.to_micros_since_unix_epoch();
+        let Some(spell) = spell.filter(|_| !expired) else {
+            end_channel(&mut caster);
+            ctx.db.player().identity().update(caster);
+            continue;
+        };
+        if !tick_channel_upkeep(&mut caster, spell.channel_mana_per_tick) {
+            ctx.db.player().identity().update(caster);
+            continue;
+        }
+
+        // The last paid tick still fires even if it ended the channel
+        let hit = beam_target(ctx, &caster, team_count);
+        if caster.channeling_spell.is_some() {
+            caster.channel_target = hit;
+        }
+        let caster_identity = caster.identity;
+        let origin = caster.position.clone();
+        // Save the caster before damaging, apply_damage re-reads rows itself
+        ctx.db.player().identity().update(caster);
+
+        if let Some(target_identity) = hit {
+            combat_logic::apply_damage(ctx, target_identity, spell.damage, Some(caster_identity), Some(&origin));
+        }
+    }
+}
+
+// Nearest living enemy within BEAM_RANGE ahead of the caster and within BEAM_HALF_WIDTH of the beam's line
+fn beam_target(ctx: &ReducerContext, caster: &PlayerData, team_count: u32) -> Option<Identity> {
+    let facing = facing_direction(&caster.rotation);
+    ctx.db.player().iter()
+        .filter(|p| p.identity != caster.identity && p.health > 0)
+        .filter(|p| {
+            let layer = collision_layer_for_player(caster.identity, caster.team, team_count, p);
+            can_collide(COLLISION_LAYER_ENEMY, layer)
+        })
+        .filter_map(|p| {
+            let dx = p.position.x - caster.position.x;
+            let dz = p.position.z - caster.position.z;
+            let along = dx * facing.x + dz * facing.z;
+            let across = (dx * facing.z - dz * facing.x).abs();
+            (along > 0.0 && along <= BEAM_RANGE && across <= BEAM_HALF_WIDTH).then_some((along, p.identity))
+        })
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(_, identity)| identity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{identity, player};
+
+    fn channeling(n: u8) -> PlayerData {
+        PlayerData {
+            channeling_spell: Some("beam".to_string()),
+            channel_target: Some(identity(9)),
+            ..player(n)
+        }
+    }
+
+    #[test]
+    fn ending_a_channel_clears_the_spell_and_target() {
+        let mut caster = channeling(1);
+        end_channel(&mut caster);
+        assert!(caster.channeling_spell.is_none());
+        assert!(caster.channel_target.is_none());
+    }
+
+    #[test]
+    fn channel_upkeep_drains_mana_and_ends_when_it_runs_out() {
+        let mut caster = PlayerData { mana: 25, ..channeling(1) };
+        assert!(tick_channel_upkeep(&mut caster, 10));
+        assert_eq!(caster.mana, 15);
+        assert!(caster.channeling_spell.is_some());
+
+        // Pays this tick but can't cover the next one
+        assert!(tick_channel_upkeep(&mut caster, 10));
+        assert_eq!(caster.mana, 5);
+        assert!(caster.channeling_spell.is_none());
+
+        let mut broke = PlayerData { mana: 5, ..channeling(2) };
+        assert!(!tick_channel_upkeep(&mut broke, 10));
+        assert_eq!(broke.mana, 5);
+        assert!(broke.channeling_spell.is_none());
+    }
+}
@@ -0,0 +1,125 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - class_logic.rs
+ *
+ * This file contains character class handling shared by registration and class changes.
+ *
+ * Key components:
+ *
+ * 1. Class Names:
+ *    - normalize_class: Trims and lowercases a client-supplied class so "Wizard",
+ *      " wizard" and "WIZARD" all resolve to the same class
+ *
+ * 2. Class Stats:
+ *    - ClassStats: Base stats granted by a class
+ *    - class_stats: Lookup by normalized class name, unknown classes get defaults
+ *      (including PLAYER_RADIUS as their collision_radius)
+ *    - default_loadout: Spells a class starts with equipped (PlayerData.equipped_spells),
+ *      unknown classes get a basic loadout
+ *
+ * 3. Class Animations:
+ *    - class_animation: Maps a logical animation state to the class's animation name
+ *      through the ClassAnimation table; states without a row keep their own name
+ *
+ * When modifying:
+ *    - Always normalize before storing or looking up a class
+ *    - Add new classes to class_stats and default_loadout using their lowercase name
+ *    - Loadouts use SpellDefinition names (lowercase)
+ *
+ * Related files:
+ *    - lib.rs: register_player and change_class reducers, ClassAnimation table and
+ *      set_class_animation reducer
+ */
+
+use spacetimedb::{ReducerContext, Table};
+use crate::common::PLAYER_RADIUS;
+// Import table traits
+use crate::class_animation;
+
+pub struct ClassStats {
+    pub max_health: i32,
+    pub max_mana: i32,
+    pub armor: i32,
+    pub knockback_resistance: f32, // 0 = full knockback, 1 = immovable
+    pub collision_radius: f32, // Players are kept at least the sum of their radii apart
+}
+
+// Canonical form of a class name: trimmed and lowercase
+pub fn normalize_class(character_class: &str) -> String {
+    character_class.trim().to_lowercase()
+}
+
+// Base stats for a class. Expects a normalized class name.
+pub fn class_stats(character_class: &str) -> ClassStats {
+    match character_class {
+        "wizard" => ClassStats { max_health: 90, max_mana: 120, armor: 0, knockback_resistance: 0.0, collision_radius: 0.4 },
+        "paladin" => ClassStats { max_health: 120, max_mana: 80, armor: 50, knockback_resistance: 0.6, collision_radius: 0.7 },
+        _ => ClassStats {
+            max_health: 100, max_mana: 100, armor: 20, knockback_resistance: 0.3, collision_radius: PLAYER_RADIUS,
+        },
+    }
+}
+
+// Spells a class starts with equipped. Expects a normalized class name.
+pub fn default_loadout(character_class: &str) -> Vec<String> {
+    let spells: &[&str] = match character_class {
+        "wizard" => &["fireball", "ice shard", "lightning bolt", "seeker"],
+        "paladin" => &["piercing_bolt", "beam", "fireball"],
+        _ => &["fireball", "ice shard"],
+    };
+    spells.iter().map(|spell| spell.to_string()).collect()
+}
+
+// Animation name a class uses for a logical state (see player_logic::determine_animation).
+// Expects a normalized class name.
+pub fn class_animation(ctx: &ReducerContext, character_class: &str, state: &str) -> String {
+    ctx.db.class_animation().iter()
+        .find(|row| row.character_class == character_class && row.state == state)
+        .map(|row| row.animation)
+        .unwrap_or_else(|| state.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn class_names_are_trimmed_and_lowercased() {
+        assert_eq!(normalize_class("  Wizard "), "wizard");
+        assert_eq!(normalize_class("PALADIN"), "paladin");
+    }
+
+    #[test]
+    fn unknown_classes_get_the_default_stats() {
+        let wizard = class_stats("wizard");
+        let paladin = class_stats("paladin");
+        let unknown = class_stats("bard");
+        assert!(wizard.max_mana > paladin.max_mana);
+        assert!(paladin.max_health > wizard.max_health);
+        assert_eq!(unknown.max_health, 100);
+        assert_eq!(unknown.collision_radius, PLAYER_RADIUS);
+        // Stats are looked up by normalized name only
+        assert_eq!(class_stats("Wizard").max_health, unknown.max_health);
+    }
+
+    #[test]
+    fn default_loadouts_only_equip_known_spells() {
+        let spells: Vec<String> = crate::spell_logic::default_spell_definitions()
+            .into_iter()
+            .map(|spell| spell.name)
+            .collect();
+        for class in ["wizard", "paladin", "bard"] {
+            let loadout = default_loadout(class);
+            assert!(!loadout.is_empty());
+            assert!(loadout.iter().all(|spell| spells.contains(spell)), "{} has an unknown spell", class);
+        }
+    }
+
+    #[test]
+    fn each_class_starts_with_its_own_loadout() {
+        assert_eq!(default_loadout("wizard"), ["fireball", "ice shard", "lightning bolt", "seeker"]);
+        assert_eq!(default_loadout("paladin"), ["piercing_bolt", "beam", "fireball"]);
+        assert_eq!(default_loadout("bard"), ["fireball", "ice shard"]);
+        // Registration normalizes the class before looking up its loadout
+        assert_eq!(default_loadout(&normalize_class(" Paladin ")), default_loadout("paladin"));
+    }
+}
@@ -0,0 +1,519 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - combat_logic.rs
+ *
+ * This file contains the shared damage path used by every damage source
+ * (projectiles, status effects, future melee/hazards).
+ *
+ * Key components:
+ *
+ * 1. Damage:
+ *    - apply_damage: Re-reads the target, respects i-frames, applies the configured
+ *      damage model and blocking (frontal hits against a raised guard), subtracts
+ *      health and triggers death handling when the target reaches 0
+ *    - pvp_allowed: Player-sourced damage only lands when attacker and target both
+ *      have pvp_enabled
+ *    - duel_logic::damage_allowed: Duelists can only hurt each other; a duelist
+ *      reaching 0 health ends the duel instead of dying
+ *    - on_damage_cooldown: With GameConfig.damage_cooldown_micros > 0, a player who just
+ *      lost health ignores further hits until the window ends (stops stacked-projectile bursts)
+ *    - Every hit puts both the target and the attacker in combat (in_combat);
+ *      update_combat_state clears the flag after COMBAT_TIMEOUT_MICROS without hits
+ *    - Empowered attackers (status_logic::outgoing_damage) hit harder before mitigation
+ *    - Targets with thorns (status_logic::reflected_damage) reflect part of the damage they
+ *      took to an attacker within THORNS_RANGE, once (reflected damage isn't reflected)
+ *    - Attackers with a vampiric aura heal nearby allies for part of the damage dealt
+ *      (status_logic::distribute_vampiric_heal)
+ *    - With GameConfig.min_health > 0 (non-lethal practice) damage never takes health
+ *      below that floor, so nobody dies from damage; kill_player still kills
+ *    - level_scaled_damage: With GameConfig.level_damage_scaling > 0, player hits shrink
+ *      when the attacker out-levels the target and grow when they're under-leveled
+ *      (before mitigation, after empower)
+ *    - mitigate_damage: GameConfig.damage_model "flat" uses damage as-is, "armor"
+ *      scales it by 100 / (100 + armor), always leaving at least 1 damage
+ *    - land_hit: Subtracts the final amount from the freshly read row, so hits stack
+ *
+ *    - apply_dummy_damage: Damage for training dummies, which reset instead of dying
+ *
+ * 2. Healing:
+ *    - apply_heal: Restores health up to max_health, never revives dead players
+ *
+ * 3. Death:
+ *    - kill_player: Kills a living player outright (suicide)
+ *    - handle_death: Counts the victim's death, clears their debuffs and channel, schedules the respawn
+ *      (player_logic respawns them respawn_delay_micros later), drops loot at the death
+ *      position (along with any carried flag) and credits the killer (if any) with a kill, score and xp
+ *      (score and xp only while the killer isn't AFK, see player_logic::is_active_participant)
+ *
+ *    - respawn_delay_micros: RESPAWN_DELAY_MICROS plus RESPAWN_DELAY_PER_RECENT_DEATH_MICROS
+ *      for every earlier death still inside RESPAWN_SCALING_WINDOW_MICROS, capped at
+ *      MAX_RESPAWN_DELAY_MICROS. Staying alive for a whole window resets it
+ *
+ * When modifying:
+ *    - Route all health loss through apply_damage so rules (i-frames, kill credit)
+ *      stay consistent across damage sources
+ *    - The helpers take identities, not rows, and re-read the target right before
+ *      changing it, so any number of hits on one player in a tick add up. Callers
+ *      holding a PlayerData must save it before calling them and must not write it
+ *      back afterwards (re-read it instead), or the damage would be overwritten
+ *
+ * Related files:
+ *    - projectile_logic.rs: Projectile hits
+ *    - status_logic.rs: Damage-over-time effects such as burn
+ *    - totem_logic.rs: Healing totems
+ */
+
+use spacetimedb::{ReducerContext, Identity, Timestamp};
+use crate::common::{
+    Vector3, SCORE_PER_KILL, XP_PER_KILL, RESPAWN_DELAY_MICROS, BLOCK_DAMAGE_MULTIPLIER, COMBAT_TIMEOUT_MICROS,
+    RESPAWN_DELAY_PER_RECENT_DEATH_MICROS, RESPAWN_SCALING_WINDOW_MICROS, MAX_RESPAWN_DELAY_MICROS,
+    MIN_LEVEL_DAMAGE_MULTIPLIER, MAX_LEVEL_DAMAGE_MULTIPLIER, THORNS_RANGE,
+};
+use crate::{calculate_distance, channel_logic, class_logic, config_logic, duel_logic, flag_logic, loot_logic, status_logic};
+use crate::player_logic::{self, is_invulnerable};
+use crate::{PlayerData, TrainingDummy};
+// Import table traits
+use crate::{player, training_dummy};
+
+pub const DAMAGE_MODEL_FLAT: &str = "flat";
+pub const DAMAGE_MODEL_ARMOR: &str = "armor";
+
+pub fn is_valid_damage_model(damage_model: &str) -> bool {
+    damage_model == DAMAGE_MODEL_FLAT || damage_model == DAMAGE_MODEL_ARMOR
+}
+
+// Damage left after the damage model is applied. Unknown models fall back to flat.
+pub fn mitigate_damage(damage_model: &str, amount: i32, armor: i32) -> i32 {
+    if damage_model != DAMAGE_MODEL_ARMOR || amount <= 0 {
+        return amount;
+    }
+    let armor = armor.max(0) as i64;
+    let mitigated = (amount as i64 * 100 / (100 + armor)) as i32;
+    mitigated.max(1)
+}
+
+// Damage after level scaling: each level the attacker is above the target takes
+// `level_damage_scaling` off the hit, each level below adds it, within
+// MIN/MAX_LEVEL_DAMAGE_MULTIPLIER. A scaling of 0 leaves damage unchanged.
+pub fn level_scaled_damage(level_damage_scaling: f32, attacker_level: u32, target_level: u32, amount: i32) -> i32 {
+    if level_damage_scaling <= 0.0 || amount <= 0 {
+        return amount;
+    }
+    let level_gap = attacker_level as f32 - target_level as f32;
+    let multiplier = (1.0 - level_damage_scaling * level_gap)
+        .clamp(MIN_LEVEL_DAMAGE_MULTIPLIER, MAX_LEVEL_DAMAGE_MULTIPLIER);
+    ((amount as f32 * multiplier).round() as i32).max(1)
+}
+
+// Health left after a hit. Non-lethal mode floors it at `min_health` (without raising
+// anyone already below it); 0 disables the floor.
+fn health_after_hit(health: i32, amount: i32, min_health: i32) -> i32 {
+    (health - amount).max(min_health.min(health)).max(0)
+}
+
+// Take a hit off the target row as it is now. damage_player re-reads the row for every
+// hit, so this always sees earlier hits from the same tick.
+fn land_hit(target: &mut PlayerData, amount: i32, min_health: i32, now: Timestamp) -> DamageOutcome {
+    let old_health = target.health;
+    target.health = health_after_hit(old_health, amount, min_health);
+    let applied = old_health - target.health;
+    if applied > 0 {
+        target.last_damaged_at = now;
+    }
+    DamageOutcome { applied, killed: target.health == 0 }
+}
+
+// Whether the target is still inside the post-hit window in which further hits are ignored
+pub fn on_damage_cooldown(target: &PlayerData, damage_cooldown_micros: i64, now: Timestamp) -> bool {
+    damage_cooldown_micros > 0
+        && now.to_micros_since_unix_epoch() - target.last_damaged_at.to_micros_since_unix_epoch() < damage_cooldown_micros
+}
+
+pub struct DamageOutcome {
+    pub applied: i32, // Health actually removed
+    pub killed: bool,
+}
+
+impl DamageOutcome {
+    pub fn none() -> Self {
+        DamageOutcome { applied: 0, killed: false }
+    }
+}
+
+// Damage a player. `source` is the player credited for the hit/kill, None for environmental damage.
+// `origin` is where the hit comes from; hits with an origin can be blocked, None (damage-over-time) can't.
+pub fn apply_damage(
+    ctx: &ReducerContext,
+    target_identity: Identity,
+    amount: i32,
+    source: Option<Identity>,
+    origin: Option<&Vector3>,
+) -> DamageOutcome {
+    damage_player(ctx, target_identity, amount, source, origin, false)
+}
+
+// apply_damage, with `reflected` set for thorns damage so it can't be reflected again
+fn damage_player(
+    ctx: &ReducerContext,
+    target_identity: Identity,
+    amount: i32,
+    source: Option<Identity>,
+    origin: Option<&Vector3>,
+    reflected: bool,
+) -> DamageOutcome {
+    // Always work on the latest row, so several hits in one tick accumulate
+    let Some(mut target) = ctx.db.player().identity().find(target_identity) else {
+        return DamageOutcome::none();
+    };
+    if amount <= 0 || target.health <= 0 || is_invulnerable(&target, ctx.timestamp) {
+        return DamageOutcome::none();
+    }
+    if !duel_logic::damage_allowed(ctx, source, target_identity) {
+        return DamageOutcome::none();
+    }
+    // Duelists already opted in to fighting each other by accepting
+    let duel = duel_logic::duel_for(ctx, target_identity);
+    if duel.is_none() && !pvp_allowed(ctx, source, &target) {
+        return DamageOutcome::none();
+    }
+
+    let config = config_logic::get_config(ctx);
+    if on_damage_cooldown(&target, config.damage_cooldown_micros, ctx.timestamp) {
+        return DamageOutcome::none();
+    }
+
+    let amount = match source {
+        Some(source_identity) => status_logic::outgoing_damage(ctx, source_identity, target_identity, amount),
+        None => amount,
+    };
+    // Only hits on others are scaled, and only while the attacker is still around
+    let attacker_level = source
+        .filter(|s| *s != target_identity)
+        .and_then(|s| ctx.db.player().identity().find(s))
+        .map(|attacker| attacker.level);
+    let amount = match attacker_level {
+        Some(level) => level_scaled_damage(config.level_damage_scaling, level, target.level, amount),
+        None => amount,
+    };
+    let mut amount = mitigate_damage(&config.damage_model, amount, target.armor);
+    if origin.is_some_and(|origin| player_logic::blocks_hit_from(&target, origin, ctx.timestamp)) {
+        amount = (amount as f32 * BLOCK_DAMAGE_MULTIPLIER).round() as i32;
+        spacetimedb::log::info!("Player {} blocked a hit", target_identity);
+    }
+    let old_health = target.health;
+    let DamageOutcome { applied, killed } = land_hit(&mut target, amount, config.min_health, ctx.timestamp);
+
+    spacetimedb::log::info!(
+        "Player {} took {} damage (health: {} -> {})",
+        target_identity,
+        applied,
+        old_health,
+        target.health
+    );
+
+    mark_in_combat(&mut target, ctx.timestamp);
+    if killed && duel.is_none() {
+        handle_death(ctx, &mut target, source);
+    }
+    let target_position = target.position.clone();
+    ctx.db.player().identity().update(target);
+    if killed {
+        if let Some(duel) = &duel {
+            // Losing a duel isn't a death, both duelists are restored instead
+            duel_logic::finish_duel(ctx, duel, source);
+        }
+    }
+
+    // The attacker is in combat too (self-damage was already covered above)
+    if let Some(source_identity) = source.filter(|s| *s != target_identity) {
+        if let Some(mut attacker) = ctx.db.player().identity().find(source_identity) {
+            mark_in_combat(&mut attacker, ctx.timestamp);
+            ctx.db.player().identity().update(attacker);
+        }
+        status_logic::distribute_vampiric_heal(ctx, source_identity, applied);
+        if !reflected {
+            reflect_damage(ctx, target_identity, &target_position, source_identity, applied);
+        }
+    }
+
+    DamageOutcome { applied, killed }
+}
+
+// Thorns: send part of the damage the target just took back to a nearby attacker,
+// credited to the target. Reflected damage is never reflected again, so two players
+// with thorns can't bounce a hit back and forth.
+fn reflect_damage(
+    ctx: &ReducerContext,
+    target_identity: Identity,
+    target_position: &Vector3,
+    attacker_identity: Identity,
+    applied: i32,
+) {
+    let reflected = status_logic::reflected_damage(ctx, target_identity, applied);
+    if reflected <= 0 {
+        return;
+    }
+    let in_range = ctx.db.player().identity().find(attacker_identity)
+        .is_some_and(|attacker| calculate_distance(&attacker.position, target_position) <= THORNS_RANGE);
+    if in_range {
+        damage_player(ctx, attacker_identity, reflected, Some(target_identity), None, true);
+    }
+}
+
+// Player-vs-player damage needs PvP on for both sides. Environmental damage (no source)
+// and self-damage always apply. If the attacker has left, only the target's flag counts.
+pub fn pvp_allowed(ctx: &ReducerContext, source: Option<Identity>, target: &PlayerData) -> bool {
+    let Some(attacker_identity) = source else {
+        return true;
+    };
+    let attacker_pvp = ctx.db.player().identity().find(attacker_identity)
+        .map(|attacker| attacker.pvp_enabled)
+        .unwrap_or(true);
+    pvp_allowed_between(attacker_identity, attacker_pvp, target)
+}
+
+// Self-damage always lands, anything else needs both sides opted in
+fn pvp_allowed_between(attacker_identity: Identity, attacker_pvp: bool, target: &PlayerData) -> bool {
+    attacker_identity == target.identity || (attacker_pvp && target.pvp_enabled)
+}
+
+// Damage a training dummy. Dummies reset to full health instead of dying and never
+// credit kills or score. Returns true if this hit "killed" (reset) the dummy.
+pub fn apply_dummy_damage(ctx: &ReducerContext, dummy_id: u64, amount: i32) -> bool {
+    let Some(mut dummy) = ctx.db.training_dummy().id().find(dummy_id) else {
+        return false;
+    };
+    if amount <= 0 {
+        return false;
+    }
+    let reset = damage_dummy(&mut dummy, amount, ctx.timestamp);
+    if reset {
+        spacetimedb::log::info!("Training dummy {} was defeated and reset", dummy_id);
+    }
+    ctx.db.training_dummy().id().update(dummy);
+    reset
+}
+
+fn damage_dummy(dummy: &mut TrainingDummy, amount: i32, now: Timestamp) -> bool {
+    dummy.health = (dummy.health - amount).max(0);
+    dummy.last_hit_at = now;
+    let reset = dummy.health == 0;
+    if reset {
+        dummy.health = dummy.max_health;
+    }
+    reset
+}
+
+fn mark_in_combat(player: &mut PlayerData, now: Timestamp) {
+    player.in_combat = true;
+    player.last_combat_at = now;
+}
+
+// Clears in_combat once COMBAT_TIMEOUT_MICROS have passed without dealing or taking damage.
+// Regen, safe logout and safe zones should treat players with in_combat set as engaged.
+pub fn update_combat_state(player: &mut PlayerData, now: Timestamp) -> bool {
+    if player.in_combat
+        && now.to_micros_since_unix_epoch() - player.last_combat_at.to_micros_since_unix_epoch() >= COMBAT_TIMEOUT_MICROS
+    {
+        player.in_combat = false;
+        return true;
+    }
+    false
+}
+
+// Heal a player, clamped to max_health. Returns the health actually restored.
+pub fn apply_heal(ctx: &ReducerContext, target_identity: Identity, amount: i32) -> i32 {
+    let Some(mut target) = ctx.db.player().identity().find(target_identity) else {
+        return 0;
+    };
+    if amount <= 0 || target.health <= 0 || target.health >= target.max_health {
+        return 0;
+    }
+
+    let old_health = target.health;
+    target.health = (target.health + amount).min(target.max_health);
+    let restored = target.health - old_health;
+    ctx.db.player().identity().update(target);
+    restored
+}
+
+// Kill a living player outright, bypassing i-frames (e.g. the suicide reducer).
+// Returns false if the player isn't active or is already dead.
+pub fn kill_player(ctx: &ReducerContext, target_identity: Identity, killer: Option<Identity>) -> bool {
+    let Some(mut target) = ctx.db.player().identity().find(target_identity) else {
+        return false;
+    };
+    if target.health <= 0 {
+        return false;
+    }
+    target.health = 0;
+    handle_death(ctx, &mut target, killer);
+    ctx.db.player().identity().update(target);
+    true
+}
+
+// Respawn delay for a death given how many deaths (including this one) are in the scaling window
+pub fn respawn_delay_micros(recent_death_count: usize) -> i64 {
+    let extra_deaths = recent_death_count.saturating_sub(1) as i64;
+    (RESPAWN_DELAY_MICROS + extra_deaths * RESPAWN_DELAY_PER_RECENT_DEATH_MICROS).min(MAX_RESPAWN_DELAY_MICROS)
+}
+
+// Record a death on the victim (caller saves the victim row), schedule their respawn and credit the killer
+fn handle_death(ctx: &ReducerContext, victim: &mut PlayerData, killer: Option<Identity>) {
+    victim.deaths += 1;
+    // Input is refused while dead, so the death animation is set here rather than from input
+    victim.current_animation = class_logic::class_animation(ctx, &victim.character_class, "death");
+    // Debuffs and channels end with the life they were on
+    status_logic::clear_negative_effects(ctx, victim.identity);
+    if victim.channeling_spell.is_some() {
+        channel_logic::end_channel(victim);
+    }
+    loot_logic::drop_loot(ctx, &victim.position);
+    flag_logic::drop_carried_flag(ctx, victim.identity, &victim.position);
+
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    victim.recent_deaths.retain(|death| now_micros - death.to_micros_since_unix_epoch() < RESPAWN_SCALING_WINDOW_MICROS);
+    victim.recent_deaths.push(ctx.timestamp);
+    let delay = respawn_delay_micros(victim.recent_deaths.len());
+    victim.respawn_at = Timestamp::from_micros_since_unix_epoch(now_micros + delay);
+    spacetimedb::log::info!("Player {} respawns in {}ms", victim.identity, delay / 1000);
+
+    match killer {
+        Some(killer_identity) if killer_identity != victim.identity => {
+            if let Some(mut killer) = ctx.db.player().identity().find(killer_identity) {
+                killer.kills += 1;
+                // Idle killers (e.g. a lingering projectile or burn) still get the kill, not the rewards
+                let config = config_logic::get_config(ctx);
+                if player_logic::is_active_participant(&killer, &config, ctx.timestamp) {
+                    killer.score += SCORE_PER_KILL;
+                    player_logic::award_xp(&mut killer, XP_PER_KILL);
+                } else {
+                    spacetimedb::log::info!("Player {} is idle, no kill rewards", killer_identity);
+                }
+                ctx.db.player().identity().update(killer);
+            }
+            spacetimedb::log::info!("💀 Player {} was killed by {}", victim.identity, killer_identity);
+        }
+        _ => {
+            spacetimedb::log::info!("💀 Player {} died", victim.identity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at_micros, identity, player, vec3};
+
+    #[test]
+    fn only_known_damage_models_are_valid() {
+        assert!(is_valid_damage_model(DAMAGE_MODEL_FLAT));
+        assert!(is_valid_damage_model(DAMAGE_MODEL_ARMOR));
+        assert!(!is_valid_damage_model("percent"));
+    }
+
+    #[test]
+    fn armor_mitigates_damage_only_under_the_armor_model() {
+        assert_eq!(mitigate_damage(DAMAGE_MODEL_FLAT, 40, 100), 40);
+        assert_eq!(mitigate_damage("unknown", 40, 100), 40);
+        assert_eq!(mitigate_damage(DAMAGE_MODEL_ARMOR, 40, 0), 40);
+        assert_eq!(mitigate_damage(DAMAGE_MODEL_ARMOR, 40, 100), 20);
+        // Negative armor never amplifies, and a hit always deals at least 1
+        assert_eq!(mitigate_damage(DAMAGE_MODEL_ARMOR, 40, -50), 40);
+        assert_eq!(mitigate_damage(DAMAGE_MODEL_ARMOR, 1, 1000), 1);
+    }
+
+    #[test]
+    fn pvp_damage_needs_both_players_opted_in() {
+        let opted_in = player(2);
+        let opted_out = PlayerData { pvp_enabled: false, ..player(3) };
+
+        assert!(pvp_allowed_between(identity(1), true, &opted_in));
+        assert!(!pvp_allowed_between(identity(1), false, &opted_in));
+        assert!(!pvp_allowed_between(identity(1), true, &opted_out));
+        // Players can always hurt themselves
+        assert!(pvp_allowed_between(identity(3), false, &opted_out));
+    }
+
+    #[test]
+    fn training_dummies_reset_instead_of_dying() {
+        let mut dummy = TrainingDummy {
+            id: 1,
+            position: vec3(0.0, 0.0, 0.0),
+            health: 30,
+            max_health: 100,
+            last_hit_at: at_micros(0),
+        };
+        assert!(!damage_dummy(&mut dummy, 20, at_micros(1_000)));
+        assert_eq!(dummy.health, 10);
+        assert_eq!(dummy.last_hit_at, at_micros(1_000));
+
+        assert!(damage_dummy(&mut dummy, 50, at_micros(2_000)));
+        assert_eq!(dummy.health, dummy.max_health);
+    }
+
+    #[test]
+    fn combat_ends_after_the_timeout_without_damage() {
+        let mut fighter = player(1);
+        mark_in_combat(&mut fighter, at_micros(1_000));
+        assert!(!update_combat_state(&mut fighter, at_micros(1_000 + COMBAT_TIMEOUT_MICROS - 1)));
+        assert!(fighter.in_combat);
+        assert!(update_combat_state(&mut fighter, at_micros(1_000 + COMBAT_TIMEOUT_MICROS)));
+        assert!(!fighter.in_combat);
+    }
+
+    #[test]
+    fn hits_inside_the_damage_cooldown_are_ignored() {
+        let target = PlayerData { last_damaged_at: at_micros(1_000_000), ..player(1) };
+        assert!(!on_damage_cooldown(&target, 0, at_micros(1_000_000)));
+        assert!(on_damage_cooldown(&target, 200_000, at_micros(1_199_999)));
+        assert!(!on_damage_cooldown(&target, 200_000, at_micros(1_200_000)));
+    }
+
+    #[test]
+    fn respawn_delay_grows_with_recent_deaths_up_to_the_cap() {
+        assert_eq!(respawn_delay_micros(0), RESPAWN_DELAY_MICROS);
+        assert_eq!(respawn_delay_micros(1), RESPAWN_DELAY_MICROS);
+        assert_eq!(respawn_delay_micros(3), RESPAWN_DELAY_MICROS + 2 * RESPAWN_DELAY_PER_RECENT_DEATH_MICROS);
+        assert_eq!(respawn_delay_micros(100), MAX_RESPAWN_DELAY_MICROS);
+    }
+
+    #[test]
+    fn non_lethal_mode_floors_health_without_raising_it() {
+        assert_eq!(health_after_hit(50, 80, 0), 0);
+        assert_eq!(health_after_hit(50, 20, 0), 30);
+        assert_eq!(health_after_hit(50, 80, 10), 10);
+        assert_eq!(health_after_hit(5, 20, 10), 5);
+    }
+
+    #[test]
+    fn level_gaps_scale_damage_within_the_limits() {
+        assert_eq!(level_scaled_damage(0.0, 10, 1, 40), 40);
+        assert_eq!(level_scaled_damage(0.1, 5, 5, 40), 40);
+        assert_eq!(level_scaled_damage(0.1, 3, 1, 40), 32);
+        assert_eq!(level_scaled_damage(0.1, 1, 3, 40), 48);
+        assert_eq!(level_scaled_damage(0.1, 50, 1, 40), ((40.0 * MIN_LEVEL_DAMAGE_MULTIPLIER).round() as i32).max(1));
+        assert_eq!(level_scaled_damage(0.1, 1, 50, 40), (40.0 * MAX_LEVEL_DAMAGE_MULTIPLIER).round() as i32);
+        assert_eq!(level_scaled_damage(0.5, 50, 1, 1), 1);
+    }
+
+    #[test]
+    fn hits_in_the_same_tick_accumulate() {
+        let now = at_micros(1_000_000);
+        let mut target = player(1);
+        let first = land_hit(&mut target, 30, 0, now);
+        let second = land_hit(&mut target, 25, 0, now);
+        assert_eq!(first.applied + second.applied, 55);
+        assert_eq!(target.health, 45);
+        assert_eq!(target.last_damaged_at, now);
+        assert!(!second.killed);
+    }
+
+    #[test]
+    fn only_the_killing_hit_counts_as_a_kill() {
+        let mut target = PlayerData { health: 40, ..player(1) };
+        assert!(!land_hit(&mut target, 30, 0, at_micros(0)).killed);
+        let outcome = land_hit(&mut target, 30, 0, at_micros(0));
+        assert_eq!(outcome.applied, 10);
+        assert!(outcome.killed);
+    }
+}
@@ -7,6 +7,9 @@
  * - Vector3: 3D vector struct for positions, rotations and movement
  * - InputState: Player input tracking with all possible input actions
  * - Game constants: Speed values that affect player movement
+ * - KNOWN_ANIMATIONS: Logical animation states the server can determine
+ * - ArenaGeometry: Static arena pieces listed in the public ArenaInfo table
+ * - Collision layers: Bit flags matched against a projectile's collision_mask
  * 
  * These structures are used by:
  * - lib.rs: For database table definitions
@@ -16,7 +19,10 @@
  * - Changes to Vector3 or InputState will affect database schema
  * - You may need to run 'spacetime delete <db_name>' after schema changes
  * - Adjust PLAYER_SPEED and SPRINT_MULTIPLIER to change movement feel
+ *   (these are defaults; the live values are in the GameConfig table)
  * - Adding new input types requires updates to InputState and UI event handlers
+ * - GROUND_HEIGHT is the only place the floor height is defined: gravity, projectile
+ *   ground contact, spawn points and out-of-bounds correction all read it
  */
 
 use spacetimedb::{SpacetimeType};
@@ -42,10 +48,191 @@ pub struct InputState {
     pub jump: bool,
     pub attack: bool,
     pub cast_spell: bool,
+    pub dash: bool,
+    pub block: bool,
     pub sequence: u32,
 }
 
+// Static piece of the arena published in ArenaInfo, an axis-aligned box (size y 0 = flat area)
+#[derive(SpacetimeType, Clone, Debug)]
+pub struct ArenaGeometry {
+    pub kind: String, // arena_logic::GEOMETRY_* name
+    pub center: Vector3,
+    pub size: Vector3, // Full extent along each axis
+}
+
 // --- Game Constants ---
 
 pub const PLAYER_SPEED: f32 = 7.5;
 pub const SPRINT_MULTIPLIER: f32 = 1.8;
+pub const MOVEMENT_ACCELERATION: f32 = 6.0; // Speed gained per second while input is held
+pub const MOVEMENT_DECELERATION: f32 = 15.0; // Speed lost per second after input is released
+pub const MOVEMENT_SMOOTHING_FACTOR: f32 = 0.5; // Fraction of the gap smoothed_position closes per tick
+pub const MOVEMENT_SMOOTHING_SNAP_DISTANCE: f32 = 10.0; // Gaps larger than this (teleports) snap instead
+pub const GRAVITY: f32 = 20.0;
+pub const JUMP_FORCE: f32 = 8.0;
+pub const DEFAULT_TEAM_COUNT: u32 = 1; // 1 = free-for-all
+pub const DEFAULT_MAX_PLAYERS: u32 = 32;
+pub const MAX_TEAM_COUNT: u32 = 8;
+pub const MIN_MAX_STAT: i32 = 1; // Floor for max_health/max_mana, keeps ratio math safe
+pub const SCORE_PER_KILL: u32 = 100;
+pub const XP_PER_KILL: u32 = 50;
+pub const XP_PER_LEVEL: u32 = 200;
+pub const GAME_TICK_MICROS: i64 = 1_000_000; // Players, effects and cleanup
+pub const PROJECTILE_TICK_MICROS: i64 = 50_000; // Projectile movement and hits
+pub const MAX_CONSECUTIVE_SHED_TICKS: u32 = 10; // Low-priority tick work is never deferred longer than this
+pub const AUTOSAVE_INTERVAL_MICROS: i64 = 30_000_000; // How often active players' progression is backed up
+pub const WORLD_HALF_EXTENT: f32 = 100.0; // Playable area is -extent..extent on x and z
+pub const PLAYER_RADIUS: f32 = 0.5; // Collision radius of classes without their own (see class_logic::class_stats)
+pub const GROUND_HEIGHT: f32 = 1.0; // Floor for players (their origin) and projectiles; spawns and the duel arena stand on it
+pub const OUT_OF_BOUNDS_DAMAGE_PER_TICK: i32 = 10;
+pub const SPAWN_POINT_COUNT: usize = 8; // Spawn slots along the x axis, see player_logic::spawn_position
+pub const SPAWN_PROTECTION_RADIUS: f32 = 10.0; // Default: avoid spawns with an enemy this close
+pub const MAX_SPAWN_PRIVACY_MICROS: i64 = 10_000_000; // Upper limit on GameConfig.spawn_privacy_micros
+pub const SPAWN_PRIVACY_RADIUS: f32 = 5.0; // Moving this far from the spawn point ends spawn privacy early
+pub const SPAWN_PRIVACY_GRID_SIZE: f32 = 20.0; // Published positions snap to this grid during spawn privacy
+pub const PVP_ENABLED_BY_DEFAULT: bool = true; // Set to false for worlds where PvP is opt-in via set_pvp
+pub const LOOT_HEALTH: i32 = 20;
+pub const LOOT_MANA: i32 = 20;
+pub const LOOT_SCORE: u32 = 10;
+pub const LOOT_LIFETIME_MICROS: i64 = 30_000_000;
+pub const LOOT_PICKUP_RADIUS: f32 = 1.5;
+pub const HASTE_SPEED_MULTIPLIER: f32 = 1.5; // Movement speed while hasted by a speed pickup
+pub const HASTE_DURATION_MICROS: i64 = 8_000_000; // Refreshed (not stacked) by another pickup
+pub const DUMMY_REGEN_DELAY_MICROS: i64 = 5_000_000; // Training dummies heal fully after this long without hits
+pub const DEFAULT_AFK_TIMEOUT_MICROS: i64 = 60_000_000; // Players without input this long earn no score or xp
+pub const COMBAT_TIMEOUT_MICROS: i64 = 5_000_000; // in_combat clears this long after the last hit dealt or taken
+pub const AUTO_REJOIN_WINDOW_MICROS: i64 = 60_000_000; // Reconnects within this long of logging out are restored automatically
+pub const RESPAWN_DELAY_MICROS: i64 = 3_000_000; // Time spent dead before respawning, with no other recent deaths
+pub const RESPAWN_DELAY_PER_RECENT_DEATH_MICROS: i64 = 2_000_000; // Added for each other death within the window
+pub const RESPAWN_SCALING_WINDOW_MICROS: i64 = 60_000_000; // Deaths older than this no longer lengthen respawns
+pub const MAX_RESPAWN_DELAY_MICROS: i64 = 15_000_000;
+pub const SUICIDE_COOLDOWN_MICROS: i64 = 10_000_000; // Minimum time alive after a respawn before suicide is allowed
+pub const BLOCK_WINDOW_MICROS: i64 = 500_000; // How long a block press keeps the guard up
+pub const BLOCK_DAMAGE_MULTIPLIER: f32 = 0.25; // Fraction of a blocked frontal hit that still lands
+pub const ATTACK_DURATION_MICROS: i64 = 600_000; // How long a single attack keeps is_attacking set
+pub const PIERCING_BOLT_MAX_PIERCE: u32 = 2;
+pub const PROJECTILE_SPAWN_FORWARD_OFFSET: f32 = 1.5; // Spawn in front of the caster...
+pub const PROJECTILE_SPAWN_HEIGHT_OFFSET: f32 = 0.5; // ...and slightly above their origin
+pub const IMPACT_EFFECT_LIFETIME_MICROS: i64 = 1_000_000;
+pub const MAX_PROJECTILE_STEP_SECS: f32 = 1.0; // Longest time a single projectile update may cover
+pub const MAX_PROJECTILE_LIFETIME_SECS: f32 = 10.0; // Hard cap on any spell's lifetime_secs
+pub const MAX_PROJECTILE_SPEED: f32 = 60.0; // Hard cap on any projectile's speed (units per second)
+pub const MAX_PROJECTILES_PER_CASTER: usize = 20; // Casts that would leave a caster with more in flight are refused
+pub const SHOTGUN_PROJECTILE_COUNT: u32 = 5;
+pub const SHOTGUN_SPREAD_RADIANS: f32 = 0.6; // Fan width of the whole volley
+pub const LAG_COMPENSATION_HISTORY_MICROS: i64 = 1_000_000; // Positions kept for rewinding, also the cap on lag_compensation_max_micros
+pub const CLUSTER_FRAGMENT_COUNT: u32 = 6;
+pub const CLUSTER_FRAGMENT_SPREAD_RADIANS: f32 = std::f32::consts::FRAC_PI_2; // Fan width around the parent's direction
+pub const CLUSTER_FRAGMENT_SPEED: f32 = 18.0;
+pub const CLUSTER_FRAGMENT_LIFETIME_SECS: f32 = 0.5;
+pub const CLUSTER_FRAGMENT_DAMAGE: i32 = 4;
+pub const GRENADE_MAX_THROW_RANGE: f32 = 20.0;
+pub const GRENADE_HORIZONTAL_SPEED: f32 = 10.0;
+pub const GRENADE_FUSE_SECS: f32 = 3.0; // Detonates mid-air if it hasn't landed by then
+pub const GRENADE_AOE_RADIUS: f32 = 4.0;
+pub const GRENADE_DAMAGE: i32 = 25;
+pub const EXPLOSION_KNOCKBACK_DISTANCE: f32 = 3.0; // Push from grenades and mines before knockback_resistance
+pub const BEAM_RANGE: f32 = 15.0;
+pub const BEAM_HALF_WIDTH: f32 = 1.0; // How far off the beam's line a target can stand and still be hit
+pub const BEAM_DAMAGE_PER_TICK: i32 = 8;
+pub const BEAM_MANA_PER_TICK: i32 = 10;
+pub const CHANNEL_KEEPALIVE_MICROS: i64 = 1_500_000; // A channel ends this long after the last start_channel call
+pub const BURN_DAMAGE_PER_STACK: i32 = 2; // Per game tick
+pub const BURN_DURATION_MICROS: i64 = 4_000_000; // Refreshed on every new stack
+pub const BURN_MAX_STACKS: u32 = 3;
+pub const BLOOD_SACRIFICE_HEALTH_COST: i32 = 20;
+pub const BLOOD_SACRIFICE_MANA_GAIN: i32 = 30;
+pub const MIN_LEVEL_DAMAGE_MULTIPLIER: f32 = 0.25; // Level scaling never cuts a hit below this share
+pub const MAX_LEVEL_DAMAGE_MULTIPLIER: f32 = 2.0; // ...or boosts an underdog's hit above this
+pub const CLEANSE_MANA_COST: i32 = 25;
+pub const CLEANSE_COOLDOWN_MICROS: i64 = 15_000_000;
+pub const EMPOWER_DAMAGE_MULTIPLIER: f32 = 1.25; // Outgoing damage while empowered by a blood sacrifice
+pub const EMPOWER_DURATION_MICROS: i64 = 5_000_000; // Refreshed (not stacked) by another sacrifice
+pub const VAMPIRIC_AURA_FRACTION: f32 = 0.3; // Share of the wearer's dealt damage healed to nearby allies
+pub const VAMPIRIC_AURA_RADIUS: f32 = 10.0;
+pub const VAMPIRIC_AURA_DURATION_MICROS: i64 = 30_000_000; // Toggling again ends it early
+pub const THORNS_REFLECT_FRACTION: f32 = 0.25; // Share of damage taken that thorns sends back to the attacker
+pub const THORNS_RANGE: f32 = 15.0; // Attackers further away than this aren't hit by reflected damage
+pub const THORNS_DURATION_MICROS: i64 = 30_000_000; // Toggling again ends it early
+pub const SHARE_RESOURCE_RANGE: f32 = 10.0; // Max distance to the ally receiving shared health/mana
+pub const SHARE_RESOURCE_MIN_HEALTH: i32 = 10; // Sharing never takes the giver below this much health
+pub const SWAP_POSITIONS_RANGE: f32 = 20.0; // Max distance to the ally swapped with
+pub const DODGE_ROLL_DISTANCE: f32 = 4.0;
+pub const DODGE_ROLL_IFRAME_MICROS: i64 = 400_000; // Invulnerability window during a roll
+pub const DODGE_ROLL_COOLDOWN_MICROS: i64 = 1_500_000;
+pub const CHARGE_DISTANCE: f32 = 8.0; // Full charge length when nothing is in the way
+pub const CHARGE_HIT_RADIUS: f32 = 1.0; // How far off the charge line an enemy can stand and still be hit
+pub const CHARGE_STOP_DISTANCE: f32 = 1.0; // The charger stops this short of the enemy they hit
+pub const CHARGE_DAMAGE: i32 = 15;
+pub const GRAVITY_WELL_CAST_RANGE: f32 = 30.0;
+pub const GRAVITY_WELL_RADIUS: f32 = 8.0;
+pub const GRAVITY_WELL_STRENGTH: f32 = 3.0; // Units per second toward the center
+pub const GRAVITY_WELL_DURATION_MICROS: i64 = 5_000_000;
+pub const PORTAL_CAST_RANGE: f32 = 30.0; // Both ends of a portal must be this close to the caster
+pub const PORTAL_RADIUS: f32 = 1.5;
+pub const PORTAL_DURATION_MICROS: i64 = 30_000_000;
+pub const PORTAL_COOLDOWN_MICROS: i64 = 2_000_000; // After a teleport, portals ignore the player this long
+pub const JUMP_PAD_ARM_DELAY_MICROS: i64 = 1_000_000; // New pads don't launch anyone for this long
+pub const MAX_JUMP_PAD_BOOST: f32 = 40.0; // Upper limit on a pad's vertical_velocity impulse
+pub const GROUND_EFFECT_CAST_DISTANCE: f32 = 6.0; // Ground effects are centered this far ahead of the caster
+pub const FIRE_PATCH_RADIUS: f32 = 3.0;
+pub const FIRE_PATCH_DAMAGE_PER_TICK: i32 = 5;
+pub const FIRE_PATCH_DURATION_SECS: f32 = 6.0;
+pub const TOTEM_CAST_RANGE: f32 = 10.0;
+pub const TOTEM_RADIUS: f32 = 6.0;
+pub const TOTEM_HEAL_PER_TICK: i32 = 5;
+pub const TOTEM_DURATION_MICROS: i64 = 10_000_000;
+pub const MINE_CAST_RANGE: f32 = 10.0;
+pub const MINE_ARM_DELAY_MICROS: i64 = 2_000_000; // Placed mines are inert for this long
+pub const MINE_TRIGGER_RADIUS: f32 = 2.0; // An enemy this close sets the mine off
+pub const MINE_AOE_RADIUS: f32 = 4.0;
+pub const MINE_DAMAGE: i32 = 35;
+pub const MINE_DURATION_MICROS: i64 = 60_000_000;
+pub const DUEL_REQUEST_TIMEOUT_MICROS: i64 = 30_000_000; // Unanswered duel requests are dropped after this
+pub const DUEL_DURATION_MICROS: i64 = 120_000_000; // A duel with no winner by then ends in a draw
+pub const DUEL_ARENA_CENTER: Vector3 = Vector3 { x: 0.0, y: GROUND_HEIGHT, z: 80.0 };
+pub const DUEL_ARENA_HALF_DISTANCE: f32 = 8.0; // Duelists start this far either side of the center
+pub const TAUNT_RADIUS: f32 = 20.0; // Enemies this close to a taunting player get revealed
+pub const REVEAL_DURATION_MICROS: i64 = 5_000_000;
+pub const FLAG_PICKUP_RADIUS: f32 = 2.0; // Touching a loose flag picks it up (enemies) or returns it (owners)
+pub const FLAG_CAPTURE_RADIUS: f32 = 3.0; // A carrier this close to their own flag's base captures
+pub const FLAG_CAPTURE_SCORE: u32 = 500;
+pub const BOT_AGGRO_RANGE: f32 = 30.0; // Bots go after enemies this close, otherwise they wander
+pub const BOT_PREFERRED_RANGE: f32 = 8.0; // Bots stop closing in at this distance
+pub const BOT_CAST_INTERVAL_MICROS: i64 = 3_000_000;
+pub const BOT_WANDER_INTERVAL_MICROS: i64 = 4_000_000; // How often a wandering bot picks a new heading
+pub const BOT_WANDER_TURN_RADIANS: f32 = 2.4; // Heading change per pick, spreads wander paths out
+pub const VOTEKICK_DURATION_MICROS: i64 = 30_000_000; // Vote kicks without a majority by then fail
+pub const MIN_VOTEKICK_VOTERS: usize = 3; // Eligible voters needed, so two players can't kick each other
+pub const MAX_ANNOUNCEMENT_LENGTH: usize = 200;
+pub const MAX_TITLE_LENGTH: usize = 24; // Characters, for set_title
+pub const MIN_ACCOUNT_KEY_LENGTH: usize = 8; // Characters, for link_account; short keys are too easy to guess
+pub const MAX_ACCOUNT_KEY_LENGTH: usize = 64;
+pub const MAX_ANNOUNCEMENT_DURATION_SECS: u32 = 300;
+pub const VOICE_CHAT_RADIUS: f32 = 25.0; // Max distance at which players can hear each other
+pub const USERNAME_CHECK_TTL_MICROS: i64 = 30_000_000; // check_username results are pruned after this
+
+// Logical animation states, named like the client's ANIMATIONS in Player.tsx. The server
+// picks one from input (player_logic::determine_animation) and ClassAnimation rows can
+// map it to a class-specific animation name.
+pub const KNOWN_ANIMATIONS: [&str; 14] = [
+    "idle",
+    "walk-forward", "walk-back", "walk-left", "walk-right",
+    "run-forward", "run-back", "run-left", "run-right",
+    "jump", "attack1", "cast", "damage", "death",
+];
+
+// Palette used for automatic color assignment; set_color also accepts "#rrggbb" hex colors
+pub const PLAYER_COLORS: [&str; 6] = ["cyan", "magenta", "yellow", "lightgreen", "white", "orange"];
+
+// --- Collision Layers ---
+// Each entity a projectile can touch belongs to one layer (relative to the caster).
+// A projectile only interacts with entities whose layer bit is set in its collision_mask.
+
+pub const COLLISION_LAYER_SELF: u32 = 1 << 0;
+pub const COLLISION_LAYER_ALLY: u32 = 1 << 1;
+pub const COLLISION_LAYER_ENEMY: u32 = 1 << 2;
+
+// Default mask for damaging spells: hits other players, and the caster when self-targeted
+pub const COLLISION_MASK_DEFAULT: u32 = COLLISION_LAYER_SELF | COLLISION_LAYER_ENEMY;
@@ -0,0 +1,166 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - config_logic.rs
+ *
+ * This file contains helpers for reading and publishing the server's runtime tunables.
+ *
+ * Key components:
+ *
+ * 1. Config Access:
+ *    - default_game_config: Compiled-in defaults built from the constants in common.rs
+ *    - get_config: Reads the active GameConfig row, falling back to defaults
+ *    - save_config: Writes GameConfig and republishes the snapshot
+ *    - validate_movement_config: Rejects non-finite or negative movement tunables
+ *
+ * 2. Client Snapshot:
+ *    - sync_config_snapshot: Copies the client-relevant values into the public
+ *      ConfigSnapshot table so clients can predict movement with authoritative constants
+ *    - config_snapshot_for: The GameConfig to ConfigSnapshot mapping it publishes
+ *
+ * When modifying:
+ *    - Write GameConfig through save_config so the snapshot never goes stale
+ *    - New movement tunables should be added to both GameConfig and ConfigSnapshot
+ *
+ * Related files:
+ *    - common.rs: Default values for the tunables
+ *    - lib.rs: GameConfig/ConfigSnapshot tables and the admin reducers that change them
+ */
+
+use spacetimedb::{ReducerContext, Table, Timestamp};
+use crate::common::{
+    PLAYER_SPEED, SPRINT_MULTIPLIER, GRAVITY, JUMP_FORCE, DEFAULT_TEAM_COUNT, SPAWN_PROTECTION_RADIUS,
+    DEFAULT_MAX_PLAYERS, DEFAULT_AFK_TIMEOUT_MICROS,
+};
+use crate::combat_logic::DAMAGE_MODEL_FLAT;
+use crate::player_logic::OUT_OF_BOUNDS_CLAMP;
+use crate::{ConfigSnapshot, GameConfig};
+// Import table traits
+use crate::{config_snapshot, game_config};
+
+// GameConfig and ConfigSnapshot are single-row tables keyed by this id
+pub const GAME_CONFIG_ID: u32 = 0;
+
+pub fn default_game_config() -> GameConfig {
+    GameConfig {
+        id: GAME_CONFIG_ID,
+        player_speed: PLAYER_SPEED,
+        sprint_multiplier: SPRINT_MULTIPLIER,
+        gravity: GRAVITY,
+        jump_force: JUMP_FORCE,
+        team_count: DEFAULT_TEAM_COUNT,
+        damage_model: DAMAGE_MODEL_FLAT.to_string(),
+        self_aoe_damage: false,
+        spawn_protection_radius: SPAWN_PROTECTION_RADIUS,
+        out_of_bounds_mode: OUT_OF_BOUNDS_CLAMP.to_string(),
+        max_players: DEFAULT_MAX_PLAYERS,
+        max_turn_rate: 0.0, // Unlimited unless an admin turns the clamp on
+        damage_cooldown_micros: 0, // Off: every hit lands
+        lag_compensation_max_micros: 0, // Off: hits are checked against current positions
+        afk_timeout_micros: DEFAULT_AFK_TIMEOUT_MICROS,
+        min_health: 0, // Off: damage can kill
+        level_damage_scaling: 0.0, // Off: levels don't change damage
+        cull_dead_caster_projectiles: false, // Projectiles outlive their caster, still crediting them
+        spawn_privacy_micros: 0, // Off: views always show the exact position
+        tick_entity_budget: 0, // Off: every system runs every tick
+    }
+}
+
+// Read the active config, falling back to defaults if init hasn't seeded it yet
+pub fn get_config(ctx: &ReducerContext) -> GameConfig {
+    ctx.db.game_config().id().find(GAME_CONFIG_ID).unwrap_or_else(default_game_config)
+}
+
+// Movement tunables must be finite (NaN and infinities would spread into every player's
+// position); speed and sprint must be positive, gravity and jump force may be 0
+pub fn validate_movement_config(player_speed: f32, sprint_multiplier: f32, gravity: f32, jump_force: f32) -> Result<(), String> {
+    if ![player_speed, sprint_multiplier, gravity, jump_force].iter().all(|value| value.is_finite()) {
+        return Err("Movement config values must be finite numbers.".to_string());
+    }
+    if player_speed <= 0.0 || sprint_multiplier <= 0.0 || gravity < 0.0 || jump_force < 0.0 {
+        return Err("Movement config values must be positive.".to_string());
+    }
+    Ok(())
+}
+
+// Store the config row (inserting it if init hasn't) and republish the snapshot
+pub fn save_config(ctx: &ReducerContext, config: GameConfig) {
+    if ctx.db.game_config().id().find(GAME_CONFIG_ID).is_some() {
+        ctx.db.game_config().id().update(config);
+    } else {
+        ctx.db.game_config().insert(config);
+    }
+    sync_config_snapshot(ctx);
+}
+
+// Publish the current config values to the public snapshot table
+pub fn sync_config_snapshot(ctx: &ReducerContext) {
+    let snapshot = config_snapshot_for(&get_config(ctx), ctx.timestamp);
+
+    if ctx.db.config_snapshot().id().find(GAME_CONFIG_ID).is_some() {
+        ctx.db.config_snapshot().id().update(snapshot);
+    } else {
+        ctx.db.config_snapshot().insert(snapshot);
+    }
+}
+
+// The client-relevant subset of `config`
+fn config_snapshot_for(config: &GameConfig, now: Timestamp) -> ConfigSnapshot {
+    ConfigSnapshot {
+        id: GAME_CONFIG_ID,
+        player_speed: config.player_speed,
+        sprint_multiplier: config.sprint_multiplier,
+        gravity: config.gravity,
+        jump_force: config.jump_force,
+        updated_at: now,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::at_micros;
+
+    #[test]
+    fn defaults_use_the_compiled_in_movement_constants() {
+        let config = default_game_config();
+        assert_eq!(config.id, GAME_CONFIG_ID);
+        assert_eq!(config.player_speed, PLAYER_SPEED);
+        assert_eq!(config.sprint_multiplier, SPRINT_MULTIPLIER);
+        assert_eq!(config.gravity, GRAVITY);
+        assert_eq!(config.jump_force, JUMP_FORCE);
+    }
+
+    #[test]
+    fn movement_config_accepts_positive_values() {
+        assert!(validate_movement_config(PLAYER_SPEED, SPRINT_MULTIPLIER, GRAVITY, JUMP_FORCE).is_ok());
+        // No gravity and no jumping are allowed
+        assert!(validate_movement_config(5.0, 1.5, 0.0, 0.0).is_ok());
+    }
+
+    #[test]
+    fn movement_config_rejects_non_positive_or_non_finite_values() {
+        assert!(validate_movement_config(0.0, 1.5, 20.0, 8.0).is_err());
+        assert!(validate_movement_config(5.0, -1.0, 20.0, 8.0).is_err());
+        assert!(validate_movement_config(5.0, 1.5, -20.0, 8.0).is_err());
+        assert!(validate_movement_config(f32::NAN, 1.5, 20.0, 8.0).is_err());
+        assert!(validate_movement_config(5.0, 1.5, f32::INFINITY, 8.0).is_err());
+    }
+
+    #[test]
+    fn snapshot_follows_config_changes() {
+        let mut config = default_game_config();
+        let before = config_snapshot_for(&config, at_micros(0));
+        assert_eq!(before.id, GAME_CONFIG_ID);
+        assert_eq!(before.player_speed, PLAYER_SPEED);
+
+        config.player_speed = PLAYER_SPEED * 2.0;
+        config.sprint_multiplier = 2.5;
+        config.gravity = 5.0;
+        config.jump_force = 3.0;
+        let after = config_snapshot_for(&config, at_micros(1_000));
+        assert_eq!(after.player_speed, PLAYER_SPEED * 2.0);
+        assert_eq!(after.sprint_multiplier, 2.5);
+        assert_eq!(after.gravity, 5.0);
+        assert_eq!(after.jump_force, 3.0);
+        assert_eq!(after.updated_at, at_micros(1_000));
+    }
+}
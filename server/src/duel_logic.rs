@@ -0,0 +1,220 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - duel_logic.rs
+ *
+ * This file contains structured 1v1 duels: a request/accept handshake, an arena
+ * both duelists are moved to, and damage isolation while the duel runs.
+ *
+ * Key components:
+ *
+ * 1. Handshake:
+ *    - request_duel: Records a pending Duel from the caller to another active player.
+ *      Pending requests expire after DUEL_REQUEST_TIMEOUT_MICROS
+ *    - accept_duel: The challenged player accepts; both are healed to full, their
+ *      positions remembered and they're teleported to opposite sides of the arena
+ *
+ * 2. Damage Isolation:
+ *    - duel_for: The accepted duel a player is in, if any
+ *    - damage_allowed: While dueling, a player can only be hurt by (and only hurt)
+ *      their opponent. PvP flags don't matter, accepting the duel is the opt-in
+ *
+ * 3. Ending:
+ *    - finish_duel: Called by combat_logic::apply_damage instead of normal death
+ *      handling when a duelist drops to 0, so duels never count kills, deaths or drop loot
+ *    - update_duels (game tick): Ends duels whose timer ran out or whose duelist left
+ *    - Ending restores both duelists to full health and mana at the positions they
+ *      had before the duel
+ *
+ * Related files:
+ *    - combat_logic.rs: Consults damage_allowed and finish_duel
+ *    - lib.rs: Duel table and request_duel/accept_duel reducers
+ */
+
+use spacetimedb::{ReducerContext, Identity, Table, Timestamp};
+use crate::common::{
+    Vector3, DUEL_ARENA_CENTER, DUEL_ARENA_HALF_DISTANCE, DUEL_DURATION_MICROS, DUEL_REQUEST_TIMEOUT_MICROS,
+};
+use crate::{Duel, PlayerData};
+// Import table traits
+use crate::{duel, player};
+
+pub fn request_duel(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    let Some(challenger) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can request a duel.".to_string());
+    };
+    if target == challenger.identity {
+        return Err("You cannot duel yourself.".to_string());
+    }
+    if ctx.db.player().identity().find(target).is_none() {
+        return Err("That player is not in the game.".to_string());
+    }
+    if duel_for(ctx, challenger.identity).is_some() || duel_for(ctx, target).is_some() {
+        return Err("One of you is already in a duel.".to_string());
+    }
+    if ctx.db.duel().iter().any(|d| !d.accepted && d.challenger == challenger.identity && d.target == target) {
+        return Err("You already challenged that player.".to_string());
+    }
+
+    ctx.db.duel().try_insert(Duel {
+        id: 0, // auto_inc will set this
+        challenger: challenger.identity,
+        target,
+        accepted: false,
+        expires_at: Timestamp::from_micros_since_unix_epoch(
+            ctx.timestamp.to_micros_since_unix_epoch() + DUEL_REQUEST_TIMEOUT_MICROS
+        ),
+        // Return positions are taken when the duel is accepted
+        challenger_return: challenger.position.clone(),
+        target_return: challenger.position,
+    }).map_err(|e| format!("Failed to request duel: {}", e))?;
+
+    spacetimedb::log::info!("Player {} challenged {} to a duel.", ctx.sender, target);
+    Ok(())
+}
+
+pub fn accept_duel(ctx: &ReducerContext, challenger_identity: Identity) -> Result<(), String> {
+    let Some(mut request) = ctx.db.duel().iter()
+        .find(|d| !d.accepted && d.challenger == challenger_identity && d.target == ctx.sender)
+    else {
+        return Err("No pending duel from that player.".to_string());
+    };
+    if duel_for(ctx, ctx.sender).is_some() || duel_for(ctx, challenger_identity).is_some() {
+        return Err("One of you is already in a duel.".to_string());
+    }
+    let (Some(mut challenger), Some(mut target)) = (
+        ctx.db.player().identity().find(challenger_identity),
+        ctx.db.player().identity().find(ctx.sender),
+    ) else {
+        ctx.db.duel().id().delete(request.id);
+        return Err("Both players must be in the game to duel.".to_string());
+    };
+    if challenger.health <= 0 || target.health <= 0 {
+        return Err("Both players must be alive to duel.".to_string());
+    }
+
+    request.accepted = true;
+    request.expires_at = Timestamp::from_micros_since_unix_epoch(
+        ctx.timestamp.to_micros_since_unix_epoch() + DUEL_DURATION_MICROS
+    );
+    request.challenger_return = challenger.position.clone();
+    request.target_return = target.position.clone();
+
+    enter_arena(&mut challenger, -DUEL_ARENA_HALF_DISTANCE);
+    enter_arena(&mut target, DUEL_ARENA_HALF_DISTANCE);
+    ctx.db.player().identity().update(challenger);
+    ctx.db.player().identity().update(target);
+    // Other requests involving either duelist are void now
+    let stale: Vec<u64> = ctx.db.duel().iter()
+        .filter(|d| !d.accepted && d.id != request.id)
+        .filter(|d| [d.challenger, d.target].iter().any(|id| *id == challenger_identity || *id == ctx.sender))
+        .map(|d| d.id)
+        .collect();
+    for id in stale {
+        ctx.db.duel().id().delete(id);
+    }
+    ctx.db.duel().id().update(request);
+
+    spacetimedb::log::info!("Duel started between {} and {}.", challenger_identity, ctx.sender);
+    Ok(())
+}
+
+fn enter_arena(player: &mut PlayerData, x_offset: f32) {
+    let position = Vector3 { x: DUEL_ARENA_CENTER.x + x_offset, y: DUEL_ARENA_CENTER.y, z: DUEL_ARENA_CENTER.z };
+    restore(player, position);
+}
+
+// Full health and mana at `position`, used on entering and leaving the arena
+fn restore(player: &mut PlayerData, position: Vector3) {
+    player.health = player.max_health;
+    player.mana = player.max_mana;
+    player.position = position.clone();
+    player.smoothed_position = position;
+    player.vertical_velocity = 0.0;
+    player.is_grounded = true;
+}
+
+// The accepted duel `identity` is fighting in, if any
+pub fn duel_for(ctx: &ReducerContext, identity: Identity) -> Option<Duel> {
+    ctx.db.duel().iter().find(|d| d.accepted && (d.challenger == identity || d.target == identity))
+}
+
+fn opponent(duel: &Duel, identity: Identity) -> Identity {
+    if duel.challenger == identity { duel.target } else { duel.challenger }
+}
+
+// Duelists only damage each other, and nobody else can interfere
+pub fn damage_allowed(ctx: &ReducerContext, source: Option<Identity>, target: Identity) -> bool {
+    match duel_for(ctx, target) {
+        Some(duel) => source == Some(opponent(&duel, target)),
+        None => source.is_none_or(|attacker| attacker == target || duel_for(ctx, attacker).is_none()),
+    }
+}
+
+// End a duel, returning both duelists to where they stood before it
+pub fn finish_duel(ctx: &ReducerContext, duel: &Duel, winner: Option<Identity>) {
+    match winner {
+        Some(winner) => spacetimedb::log::info!("Player {} won the duel {}", winner, duel.id),
+        None => spacetimedb::log::info!("Duel {} ended without a winner", duel.id),
+    }
+    for (identity, position) in [(duel.challenger, &duel.challenger_return), (duel.target, &duel.target_return)] {
+        if let Some(mut player) = ctx.db.player().identity().find(identity) {
+            restore(&mut player, position.clone());
+            ctx.db.player().identity().update(player);
+        }
+    }
+    ctx.db.duel().id().delete(duel.id);
+}
+
+// Expire unanswered requests and end duels that timed out or lost a duelist
+pub fn update_duels(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    for duel in ctx.db.duel().iter() {
+        let timed_out = now_micros >= duel.expires_at.to_micros_since_unix_epoch();
+        if !duel.accepted {
+            if timed_out {
+                ctx.db.duel().id().delete(duel.id);
+            }
+            continue;
+        }
+        let challenger_here = ctx.db.player().identity().find(duel.challenger).is_some();
+        let target_here = ctx.db.player().identity().find(duel.target).is_some();
+        if !challenger_here || !target_here {
+            let winner = if challenger_here { Some(duel.challenger) } else if target_here { Some(duel.target) } else { None };
+            finish_duel(ctx, &duel, winner);
+        } else if timed_out {
+            finish_duel(ctx, &duel, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at_micros, identity, player, vec3};
+
+    #[test]
+    fn each_duelist_fights_the_other() {
+        let duel = Duel {
+            id: 1,
+            challenger: identity(1),
+            target: identity(2),
+            accepted: true,
+            expires_at: at_micros(DUEL_DURATION_MICROS),
+            challenger_return: vec3(0.0, 0.0, 0.0),
+            target_return: vec3(0.0, 0.0, 0.0),
+        };
+        assert_eq!(opponent(&duel, identity(1)), identity(2));
+        assert_eq!(opponent(&duel, identity(2)), identity(1));
+    }
+
+    #[test]
+    fn duelists_enter_the_arena_at_full_health_and_mana() {
+        let mut duelist = PlayerData { health: 12, mana: 3, vertical_velocity: 4.0, is_grounded: false, ..player(1) };
+        enter_arena(&mut duelist, -DUEL_ARENA_HALF_DISTANCE);
+
+        assert_eq!((duelist.health, duelist.mana), (duelist.max_health, duelist.max_mana));
+        assert!((duelist.position.x - (DUEL_ARENA_CENTER.x - DUEL_ARENA_HALF_DISTANCE)).abs() < 1e-5);
+        assert!((duelist.smoothed_position.x - duelist.position.x).abs() < 1e-5);
+        assert!(duelist.is_grounded);
+        assert_eq!(duelist.vertical_velocity, 0.0);
+    }
+}
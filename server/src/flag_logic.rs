@@ -0,0 +1,153 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - flag_logic.rs
+ *
+ * This file contains capture-the-flag objectives.
+ *
+ * Key components:
+ *
+ * 1. Setup:
+ *    - place_flag: Admin-placed flag for a team at its base position (one per team)
+ *
+ * 2. Game Tick:
+ *    - update_flags: Carried flags follow their carrier; a carrier who left or died
+ *      drops the flag where it was last seen. A living enemy of the flag's team within
+ *      FLAG_PICKUP_RADIUS picks a loose flag up, a teammate touching their own dropped
+ *      flag returns it to base. A carrier reaching their own team's flag while it sits
+ *      at its base captures: FLAG_CAPTURE_SCORE for the carrier (unless AFK), flag back to base
+ *
+ * 3. Death:
+ *    - drop_carried_flag: Called from combat_logic's death handling, leaves the flag
+ *      at the death position
+ *
+ * Related files:
+ *    - lib.rs: Flag table and the place_flag/remove_flag reducers
+ *    - team_logic.rs: Team assignment (flags only make sense with team_count >= 2)
+ */
+
+use spacetimedb::{ReducerContext, Identity, Table};
+use crate::common::{Vector3, FLAG_PICKUP_RADIUS, FLAG_CAPTURE_RADIUS, FLAG_CAPTURE_SCORE};
+use crate::{calculate_distance, config_logic, player_logic, Flag, PlayerData};
+// Import table traits
+use crate::{flag, player};
+
+pub fn place_flag(ctx: &ReducerContext, team: u32, base_position: Vector3) -> Result<Flag, String> {
+    if ctx.db.flag().iter().any(|f| f.team == team) {
+        return Err(format!("Team {} already has a flag.", team));
+    }
+    ctx.db.flag().try_insert(Flag {
+        id: 0, // auto_inc will set this
+        team,
+        position: base_position.clone(),
+        base_position,
+        carrier: None,
+    }).map_err(|e| format!("Failed to create flag: {}", e))
+}
+
+// Drop whatever flag `carrier` holds at `position`
+pub fn drop_carried_flag(ctx: &ReducerContext, carrier: Identity, position: &Vector3) {
+    for mut flag in ctx.db.flag().iter().filter(|f| f.carrier == Some(carrier)) {
+        flag.carrier = None;
+        flag.position = position.clone();
+        spacetimedb::log::info!("Player {} dropped the team {} flag", carrier, flag.team);
+        ctx.db.flag().id().update(flag);
+    }
+}
+
+fn at_base(flag: &Flag) -> bool {
+    flag.carrier.is_none() && flag.position == flag.base_position
+}
+
+fn return_to_base(flag: &mut Flag) {
+    flag.carrier = None;
+    flag.position = flag.base_position.clone();
+}
+
+pub fn update_flags(ctx: &ReducerContext) {
+    let flags: Vec<Flag> = ctx.db.flag().iter().collect();
+    for flag in flags {
+        // Re-read, an earlier flag's capture may have changed this one
+        let Some(mut flag) = ctx.db.flag().id().find(flag.id) else {
+            continue;
+        };
+        match flag.carrier {
+            Some(carrier_identity) => update_carried_flag(ctx, &mut flag, carrier_identity),
+            None => update_loose_flag(ctx, &mut flag),
+        }
+        ctx.db.flag().id().update(flag);
+    }
+}
+
+fn update_carried_flag(ctx: &ReducerContext, flag: &mut Flag, carrier_identity: Identity) {
+    let carrier = ctx.db.player().identity().find(carrier_identity).filter(|p| p.health > 0);
+    let Some(mut carrier) = carrier else {
+        // Carrier logged out (deaths already drop the flag), leave it where it was last seen
+        flag.carrier = None;
+        spacetimedb::log::info!("Team {} flag dropped, its carrier {} is gone", flag.team, carrier_identity);
+        return;
+    };
+    flag.position = carrier.position.clone();
+
+    let home_flag = ctx.db.flag().iter().find(|f| f.team == carrier.team && f.id != flag.id);
+    let Some(home_flag) = home_flag else {
+        return;
+    };
+    if at_base(&home_flag) && calculate_distance(&carrier.position, &home_flag.base_position) <= FLAG_CAPTURE_RADIUS {
+        let config = config_logic::get_config(ctx);
+        if player_logic::is_active_participant(&carrier, &config, ctx.timestamp) {
+            carrier.score += FLAG_CAPTURE_SCORE;
+        }
+        ctx.db.player().identity().update(carrier);
+        return_to_base(flag);
+        spacetimedb::log::info!("🚩 Player {} captured the team {} flag", carrier_identity, flag.team);
+    }
+}
+
+fn update_loose_flag(ctx: &ReducerContext, flag: &mut Flag) {
+    let nearest = ctx.db.player().iter()
+        .filter(|p| p.health > 0)
+        .map(|p| (calculate_distance(&p.position, &flag.position), p))
+        .filter(|(distance, _)| *distance <= FLAG_PICKUP_RADIUS)
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(_, p)| p);
+    let Some(player) = nearest else {
+        return;
+    };
+
+    if player.team == flag.team {
+        if !at_base(flag) {
+            return_to_base(flag);
+            spacetimedb::log::info!("Player {} returned the team {} flag", player.identity, flag.team);
+        }
+    } else if !is_carrying(ctx, &player) {
+        flag.carrier = Some(player.identity);
+        flag.position = player.position.clone();
+        spacetimedb::log::info!("Player {} picked up the team {} flag", player.identity, flag.team);
+    }
+}
+
+fn is_carrying(ctx: &ReducerContext, player: &PlayerData) -> bool {
+    ctx.db.flag().iter().any(|f| f.carrier == Some(player.identity))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{identity, vec3};
+
+    #[test]
+    fn returned_flags_are_back_at_base() {
+        let mut flag = Flag {
+            id: 1,
+            team: 1,
+            position: vec3(5.0, 0.0, 5.0),
+            base_position: vec3(-10.0, 0.0, 0.0),
+            carrier: Some(identity(2)),
+        };
+        assert!(!at_base(&flag));
+
+        return_to_base(&mut flag);
+        assert!(at_base(&flag));
+        assert!(flag.carrier.is_none());
+        assert_eq!(flag.position, flag.base_position);
+    }
+}
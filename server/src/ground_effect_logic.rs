@@ -0,0 +1,110 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - ground_effect_logic.rs
+ *
+ * This file contains persistent ground hazards left by spells, such as fire patches.
+ *
+ * Key components:
+ *
+ * 1. Placement:
+ *    - place_ground_effect: Called by cast_spell for spells with ground_effect_radius > 0.
+ *      Stores a GroundEffect GROUND_EFFECT_CAST_DISTANCE ahead of the caster, on the
+ *      ground, dealing the spell's damage every game tick for its lifetime_secs
+ *
+ * 2. Game Tick:
+ *    - update_ground_effects: Removes expired effects and damages every living enemy of
+ *      the owner standing inside (horizontal distance within radius) through
+ *      combat_logic::apply_damage, crediting the owner. Damage has no origin, so it
+ *      can't be blocked, and PvP/duel rules apply as for any other hit
+ *
+ * Related files:
+ *    - combat_logic.rs: Shared damage helper
+ *    - spell_logic.rs: The "fire patch" SpellDefinition
+ *    - lib.rs: GroundEffect table and cast_spell
+ */
+
+use spacetimedb::{ReducerContext, Table};
+use crate::common::{Vector3, GROUND_EFFECT_CAST_DISTANCE, GROUND_HEIGHT};
+use crate::player_logic::facing_direction;
+use crate::{combat_logic, config_logic, spell_logic, team_logic, GroundEffect, PlayerData, SpellDefinition};
+// Import table traits
+use crate::{ground_effect, player};
+
+pub fn place_ground_effect(ctx: &ReducerContext, caster: &PlayerData, spell: &SpellDefinition) -> Result<(), String> {
+    let direction = facing_direction(&caster.rotation);
+    let position = Vector3 {
+        x: caster.position.x + direction.x * GROUND_EFFECT_CAST_DISTANCE,
+        y: GROUND_HEIGHT,
+        z: caster.position.z + direction.z * GROUND_EFFECT_CAST_DISTANCE,
+    };
+    ctx.db.ground_effect().try_insert(GroundEffect {
+        id: 0, // auto_inc will set this
+        kind: spell.projectile_type.clone(),
+        owner_identity: caster.identity,
+        owner_team: caster.team,
+        position,
+        radius: spell_logic::clamp_aoe_radius(spell.ground_effect_radius),
+        damage_per_tick: spell.damage,
+        expires_at: spell_logic::projectile_expires_at(ctx.timestamp, spell.lifetime_secs),
+    }).map_err(|e| format!("Failed to place {}: {}", spell.name, e))?;
+    Ok(())
+}
+
+// Expire old ground effects and damage the enemies standing in the rest
+pub fn update_ground_effects(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let team_count = config_logic::get_config(ctx).team_count;
+
+    for effect in ctx.db.ground_effect().iter() {
+        if now_micros >= effect.expires_at.to_micros_since_unix_epoch() {
+            ctx.db.ground_effect().id().delete(effect.id);
+            continue;
+        }
+
+        let targets: Vec<_> = ctx.db.player().iter()
+            .filter(|p| burns(&effect, p, team_count))
+            .map(|p| p.identity)
+            .collect();
+        for target in targets {
+            combat_logic::apply_damage(ctx, target, effect.damage_per_tick, Some(effect.owner_identity), None);
+        }
+    }
+}
+
+// Living enemies of the owner standing inside the patch (horizontal distance only)
+fn burns(effect: &GroundEffect, player: &PlayerData, team_count: u32) -> bool {
+    let dx = player.position.x - effect.position.x;
+    let dz = player.position.z - effect.position.z;
+    player.health > 0
+        && player.identity != effect.owner_identity
+        && !team_logic::is_ally(team_count, effect.owner_team, player.team)
+        && (dx * dx + dz * dz).sqrt() <= effect.radius
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at_micros, player, vec3};
+
+    #[test]
+    fn ground_effects_burn_living_enemies_inside_the_patch() {
+        let owner = PlayerData { team: 1, ..player(1) };
+        let patch = GroundEffect {
+            id: 1,
+            kind: "fire_patch".to_string(),
+            owner_identity: owner.identity,
+            owner_team: owner.team,
+            position: vec3(0.0, 0.0, 0.0),
+            radius: 3.0,
+            damage_per_tick: 2,
+            expires_at: at_micros(1_000_000),
+        };
+        // Height doesn't matter, only the horizontal distance
+        let enemy = PlayerData { team: 2, position: vec3(2.0, 5.0, 0.0), ..player(2) };
+
+        assert!(burns(&patch, &enemy, 2));
+        assert!(!burns(&patch, &owner, 2));
+        assert!(!burns(&patch, &PlayerData { team: 1, ..player(3) }, 2));
+        assert!(!burns(&patch, &PlayerData { health: 0, ..enemy.clone() }, 2));
+        assert!(!burns(&patch, &PlayerData { position: vec3(4.0, 0.0, 0.0), ..enemy.clone() }, 2));
+    }
+}
@@ -0,0 +1,98 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - high_score_logic.rs
+ *
+ * This file keeps the all-time HighScore table: each player's best kills, score and
+ * level ever reached. Rows are never removed, so they survive logouts and show up
+ * even for players who aren't online.
+ *
+ * Key components:
+ *
+ * 1. Recording:
+ *    - record_high_score: Raises any of a player's bests their current stats beat
+ *      (and refreshes the stored username); a no-op if nothing improved or for bots
+ *
+ * 2. Game Tick:
+ *    - update_high_scores: Records every active player's current stats
+ *
+ * Related files:
+ *    - lib.rs: HighScore table; identity_disconnected records a final time before
+ *      the player row is removed
+ */
+
+use spacetimedb::{ReducerContext, Table, Timestamp};
+use crate::{HighScore, PlayerData};
+// Import table traits
+use crate::{high_score, player};
+
+pub fn record_high_score(ctx: &ReducerContext, player: &PlayerData) {
+    if player.is_bot {
+        return;
+    }
+    match ctx.db.high_score().identity().find(player.identity) {
+        Some(mut best) => {
+            if merge_high_score(&mut best, player, ctx.timestamp) {
+                ctx.db.high_score().identity().update(best);
+            }
+        }
+        None => {
+            ctx.db.high_score().insert(HighScore {
+                identity: player.identity,
+                username: player.username.clone(),
+                best_kills: player.kills,
+                best_score: player.score,
+                best_level: player.level,
+                updated_at: ctx.timestamp,
+            });
+        }
+    }
+}
+
+// Raise each best to the player's current value and pick up a new username.
+// Returns false (leaving `best` untouched) when nothing improved.
+fn merge_high_score(best: &mut HighScore, player: &PlayerData, now: Timestamp) -> bool {
+    if player.kills <= best.best_kills
+        && player.score <= best.best_score
+        && player.level <= best.best_level
+        && player.username == best.username
+    {
+        return false;
+    }
+    best.username = player.username.clone();
+    best.best_kills = best.best_kills.max(player.kills);
+    best.best_score = best.best_score.max(player.score);
+    best.best_level = best.best_level.max(player.level);
+    best.updated_at = now;
+    true
+}
+
+pub fn update_high_scores(ctx: &ReducerContext) {
+    for player in ctx.db.player().iter() {
+        record_high_score(ctx, &player);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at_micros, player};
+
+    #[test]
+    fn high_scores_keep_the_best_of_each_stat() {
+        let mut best = HighScore {
+            identity: player(1).identity,
+            username: "player1".to_string(),
+            best_kills: 10,
+            best_score: 500,
+            best_level: 3,
+            updated_at: at_micros(0),
+        };
+        let worse = PlayerData { kills: 2, score: 100, level: 1, ..player(1) };
+        assert!(!merge_high_score(&mut best, &worse, at_micros(1_000)));
+        assert_eq!(best.updated_at, at_micros(0));
+
+        let more_kills = PlayerData { kills: 12, score: 100, level: 1, ..player(1) };
+        assert!(merge_high_score(&mut best, &more_kills, at_micros(2_000)));
+        assert_eq!((best.best_kills, best.best_score, best.best_level), (12, 500, 3));
+        assert_eq!(best.updated_at, at_micros(2_000));
+    }
+}
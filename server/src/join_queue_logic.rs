@@ -0,0 +1,92 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - join_queue_logic.rs
+ *
+ * This file contains the join queue used when the server is at GameConfig.max_players.
+ *
+ * Key components:
+ *
+ * 1. Queueing:
+ *    - should_queue: New registrations are queued while the server is full, or while
+ *      others are already waiting (so nobody skips the line when a slot frees up)
+ *    - enqueue: Stores (or refreshes) the caller's JoinQueue row; queued_at is kept
+ *      on refresh so re-sending register_player doesn't lose your place
+ *
+ * 2. Game Tick:
+ *    - admit_queued_players: Registers the oldest queued players while slots are free,
+ *      through the same path as register_player
+ *
+ * Related files:
+ *    - lib.rs: JoinQueue table, register_player and identity_disconnected (which
+ *      removes a disconnecting client from the queue)
+ */
+
+use spacetimedb::{ReducerContext, Identity, Table};
+use crate::{config_logic, register_player_as, JoinQueue};
+// Import table traits
+use crate::{join_queue, player};
+
+fn free_slots(ctx: &ReducerContext) -> usize {
+    let max_players = config_logic::get_config(ctx).max_players as usize;
+    max_players.saturating_sub(ctx.db.player().count() as usize)
+}
+
+pub fn should_queue(ctx: &ReducerContext) -> bool {
+    free_slots(ctx) == 0 || ctx.db.join_queue().count() > 0
+}
+
+pub fn enqueue(ctx: &ReducerContext, identity: Identity, username: String, character_class: String) -> Result<(), String> {
+    let queued_at = match ctx.db.join_queue().identity().find(identity) {
+        Some(existing) => {
+            ctx.db.join_queue().identity().delete(identity);
+            existing.queued_at
+        }
+        None => ctx.timestamp,
+    };
+    ctx.db.join_queue().insert(JoinQueue { identity, username, character_class, queued_at });
+    spacetimedb::log::info!("Server full, queued {} ({} waiting).", identity, ctx.db.join_queue().count());
+    Ok(())
+}
+
+pub fn admit_queued_players(ctx: &ReducerContext) {
+    let slots = free_slots(ctx);
+    if slots == 0 {
+        return;
+    }
+
+    for entry in next_admitted(ctx.db.join_queue().iter().collect(), slots) {
+        ctx.db.join_queue().identity().delete(entry.identity);
+        match register_player_as(ctx, entry.identity, entry.username, entry.character_class) {
+            Ok(()) => spacetimedb::log::info!("Admitted {} from the join queue.", entry.identity),
+            Err(e) => spacetimedb::log::warn!("Could not admit {} from the join queue: {}", entry.identity, e),
+        }
+    }
+}
+
+// The `slots` longest-waiting queue entries, in the order they're admitted
+fn next_admitted(mut queued: Vec<JoinQueue>, slots: usize) -> Vec<JoinQueue> {
+    queued.sort_by_key(|entry| entry.queued_at.to_micros_since_unix_epoch());
+    queued.truncate(slots);
+    queued
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at_micros, identity};
+
+    fn queued(n: u8, queued_at_micros: i64) -> JoinQueue {
+        JoinQueue {
+            identity: identity(n),
+            username: format!("player{}", n),
+            character_class: "wizard".to_string(),
+            queued_at: at_micros(queued_at_micros),
+        }
+    }
+
+    #[test]
+    fn the_longest_waiting_players_are_admitted_first() {
+        let admitted = next_admitted(vec![queued(1, 300), queued(2, 100), queued(3, 200)], 2);
+        let order: Vec<Identity> = admitted.iter().map(|entry| entry.identity).collect();
+        assert_eq!(order, vec![identity(2), identity(3)]);
+    }
+}
@@ -0,0 +1,139 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - lag_compensation_logic.rs
+ *
+ * This file contains optional lag compensation for projectile hits.
+ *
+ * Key components:
+ *
+ * 1. Position History:
+ *    - record_position_history: Run from projectile_tick while compensation is on.
+ *      Stores every active player's position in PositionHistory and prunes samples
+ *      older than LAG_COMPENSATION_HISTORY_MICROS, so each player keeps a short
+ *      ring buffer of recent positions
+ *
+ * 2. Rewinding:
+ *    - rewind_micros_for: How far a caster's projectiles rewind their targets, the
+ *      caster's reported latency capped by GameConfig.lag_compensation_max_micros
+ *      (0 = compensation off). Fixed on the projectile when it is cast
+ *    - hit_position: Where a target is checked for a hit: its newest recorded
+ *      position at or before now - rewind_micros, or its current position when
+ *      nothing is rewound or no sample is old enough
+ *
+ * When modifying:
+ *    - Only direct hits (homing and piercing) are rewound; area detonations use the
+ *      current positions so everyone standing in the blast is hit as they see it
+ *
+ * Related files:
+ *    - projectile_logic.rs: Hit checks that use hit_position
+ *    - lib.rs: PositionHistory table, report_latency and set_lag_compensation reducers
+ */
+
+use spacetimedb::{ReducerContext, Identity, Table};
+use crate::common::{Vector3, LAG_COMPENSATION_HISTORY_MICROS};
+use crate::{config_logic, PlayerData, PositionHistory};
+// Import table traits
+use crate::{player, position_history};
+
+pub fn record_position_history(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let stale: Vec<u64> = ctx.db.position_history().iter()
+        .filter(|sample| now_micros - sample.recorded_at.to_micros_since_unix_epoch() > LAG_COMPENSATION_HISTORY_MICROS)
+        .map(|sample| sample.id)
+        .collect();
+    for sample_id in stale {
+        ctx.db.position_history().id().delete(sample_id);
+    }
+
+    if config_logic::get_config(ctx).lag_compensation_max_micros <= 0 {
+        return;
+    }
+    for player in ctx.db.player().iter() {
+        if let Err(e) = ctx.db.position_history().try_insert(PositionHistory {
+            id: 0, // auto_inc will set this
+            identity: player.identity,
+            position: player.position.clone(),
+            recorded_at: ctx.timestamp,
+        }) {
+            spacetimedb::log::error!("Failed to record position history for {}: {}", player.identity, e);
+        }
+    }
+}
+
+pub fn rewind_micros_for(ctx: &ReducerContext, caster: &PlayerData) -> i64 {
+    clamp_rewind(caster.reported_latency_micros, config_logic::get_config(ctx).lag_compensation_max_micros)
+}
+
+// The caster's reported latency, within 0..max_micros (0 when lag compensation is off)
+fn clamp_rewind(reported_latency_micros: i64, max_micros: i64) -> i64 {
+    if max_micros <= 0 {
+        return 0;
+    }
+    reported_latency_micros.clamp(0, max_micros)
+}
+
+pub fn hit_position(ctx: &ReducerContext, target: &PlayerData, rewind_micros: i64) -> Vector3 {
+    if rewind_micros <= 0 {
+        return target.position.clone();
+    }
+    let rewind_to = ctx.timestamp.to_micros_since_unix_epoch() - rewind_micros;
+    rewound_position(ctx, target.identity, rewind_to).unwrap_or_else(|| target.position.clone())
+}
+
+fn rewound_position(ctx: &ReducerContext, identity: Identity, rewind_to_micros: i64) -> Option<Vector3> {
+    latest_sample_before(ctx.db.position_history().iter(), identity, rewind_to_micros)
+}
+
+// Where `identity` was at the newest sample no later than `rewind_to_micros`
+fn latest_sample_before(
+    samples: impl Iterator<Item = PositionHistory>,
+    identity: Identity,
+    rewind_to_micros: i64,
+) -> Option<Vector3> {
+    samples
+        .filter(|sample| sample.identity == identity && sample.recorded_at.to_micros_since_unix_epoch() <= rewind_to_micros)
+        .max_by_key(|sample| sample.recorded_at.to_micros_since_unix_epoch())
+        .map(|sample| sample.position)
+}
+
+// Drop a player's samples, e.g. when they log out
+pub fn clear_position_history(ctx: &ReducerContext, identity: Identity) {
+    let samples: Vec<u64> = ctx.db.position_history().iter()
+        .filter(|sample| sample.identity == identity)
+        .map(|sample| sample.id)
+        .collect();
+    for sample_id in samples {
+        ctx.db.position_history().id().delete(sample_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at_micros, identity, vec3};
+
+    fn sample(n: u8, x: f32, recorded_at_micros: i64) -> PositionHistory {
+        PositionHistory {
+            id: 0,
+            identity: identity(n),
+            position: vec3(x, 0.0, 0.0),
+            recorded_at: at_micros(recorded_at_micros),
+        }
+    }
+
+    #[test]
+    fn rewind_is_capped_by_the_configured_maximum() {
+        assert_eq!(clamp_rewind(80_000, 0), 0);
+        assert_eq!(clamp_rewind(80_000, 150_000), 80_000);
+        assert_eq!(clamp_rewind(500_000, 150_000), 150_000);
+        assert_eq!(clamp_rewind(-5, 150_000), 0);
+    }
+
+    #[test]
+    fn rewinding_uses_the_newest_sample_at_or_before_the_time() {
+        let samples = vec![sample(1, 1.0, 100), sample(1, 2.0, 200), sample(1, 3.0, 300), sample(2, 9.0, 200)];
+        let at = |micros| latest_sample_before(samples.clone().into_iter(), identity(1), micros).map(|p| p.x);
+        assert_eq!(at(250), Some(2.0));
+        assert_eq!(at(300), Some(3.0));
+        assert_eq!(at(50), None);
+    }
+}
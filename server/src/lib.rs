@@ -4,20 +4,117 @@
  * Main entry point for the SpacetimeDB module. This file contains:
  * 
  * 1. Database Schema:
- *    - PlayerData: Active player information
+ *    - PlayerData: Active player information (private)
+ *    - PlayerView: Public per-player view with only the fields clients render
  *    - LoggedOutPlayerData: Persistent data for disconnected players
+ *    - JoinQueue: Registrations waiting for a free slot when the server is full
+ *    - PlayerSnapshot: Periodic backup of active players' progression (private)
  *    - GameTickSchedule: Periodic update scheduling
+ *    - ProjectileTickSchedule: Faster schedule that only moves projectiles
+ *    - GameConfig: Runtime tunables (private), mirrored to the public ConfigSnapshot
+ *    - ClassAnimation: Class-specific animation names for logical animation states
+ *    - SpellDefinition: Per-spell projectile settings, seeded in init
+ *    - StatusEffect: Timed buffs/debuffs on players (e.g. burn)
+ *    - LookTarget: Per-observer stats snapshot of the player they're targeting
+ *    - GravityWell: Crowd-control zones pulling enemies toward their center
+ *    - Portal: Linked pair of points that teleport players from one end to the other
+ *    - GroundEffect: Spell-made ground hazards (fire patches) that damage enemies each tick
+ *    - JumpPad: Pads that launch grounded players upward, permanent or temporary
+ *    - TrainingDummy: Practice targets that reset instead of dying
+ *    - LootDrop: Pickups left where a player died, and admin-placed speed pickups
+ *    - Totem: Healing totems that heal their owner and allies in range each tick
+ *    - Reveal: Enemies revealed to a taunter's team for a few seconds
+ *    - HighScore: All-time best kills/score/level per identity, kept across sessions
+ *    - Duel: Pending duel requests and running 1v1 duels
+ *    - Mine: Proximity mines that explode when an enemy of their owner comes close
+ *    - Flag: Capture-the-flag objectives, one per team, with their carrier
+ *    - PositionHistory: Short history of player positions for lag compensation (private)
+ *    - Admin: Identities allowed to call admin reducers
+ *    - Ban: Identities that may not play or spectate (private)
+ *    - AccountLink: Player-chosen keys that carry progression to a new identity (private)
+ *    - BotState: AI state of server-driven bots (private)
+ *    - TickLoad: game_tick load and how often low-priority work was deferred (private)
+ *    - VoteKick: Open player votes to kick someone, with who voted yes and no
+ *    - MatchState: Whether the server accepts joins (closed by graceful_shutdown)
+ *    - ArenaInfo: World bounds, ground height, spawn points and static geometry for clients
+ *    - Spectator: Connected non-players and the player their follow-cam targets
+ *    - Announcement: Admin broadcast messages shown as banners until they expire
+ *    - UsernameCheck: Per-caller result of the latest check_username call
+ *    - ImpactEffect: Short-lived projectile impact markers for client particle effects
+ *    - VoiceProximity: Pairs of players within voice chat range, refreshed each tick
  * 
  * 2. Reducer Functions (Server Endpoints):
  *    - init: Module initialization and game tick scheduling
- *    - identity_connected/disconnected: Connection lifecycle management
- *    - register_player: Player registration with username and character class
+ *    - identity_connected/disconnected: Connection lifecycle management; players who
+ *      reconnect within AUTO_REJOIN_WINDOW_MICROS are restored without register_player
+ *    - register_player: Player registration with username and character class,
+ *      queued in JoinQueue once GameConfig.max_players are active
+ *    - change_class: Switches an active player's class, base stats and loadout
+ *    - bind_location: Sets the caller's current position as their respawn point
+ *    - set_pvp: Opts the caller in or out of player-vs-player damage
+ *    - taunt: Taunt emote that briefly reveals nearby enemies to the caller's team
+ *    - charge: Dashes forward and hits the first enemy in the way
+ *    - blood_sacrifice: Converts some of the caster's health into mana and a damage buff
+ *    - share_resource: Gives some of the caller's health or mana to a nearby ally
+ *    - swap_positions: Trades places with a nearby ally
+ *    - cleanse: Spends mana to remove the caller's debuffs (e.g. burn), with a cooldown
+ *    - toggle_thorns: Turns on/off thorns, reflecting part of the damage the caller takes
+ *    - toggle_vampiric_aura: Turns on/off an aura that heals nearby allies from damage dealt
+ *    - suicide: Kills the caller (a death, no kill credit) so they respawn fresh
+ *    - check_username: Writes whether a username is free into UsernameCheck
+ *    - set_color: Picks a palette or hex color not used by another active player
+ *    - set_title: Sets the caller's title shown under their username (empty clears it)
+ *    - link_account: Links the caller to a key, or claims a key's progression for a new identity
+ *    - set_look_target: Selects the player shown in the caller's target frame
+ *    - cast_gravity_well: Places a gravity well at a point within range
+ *    - create_portal: Links two points within range, replacing the caller's previous portal
+ *    - place_totem: Places a healing totem at a point within range
+ *    - place_mine: Places a proximity mine at a point within range
+ *    - request_duel/accept_duel: 1v1 duel handshake, accepting moves both players to the arena
+ *    - start_votekick/cast_votekick_vote: Vote to kick a player, passing on a majority of active players
  *    - update_player_input: Processes player movement and state updates
- *    - game_tick: Periodic update for game state (scheduled)
+ *    - report_latency: Client-measured latency, used to rewind targets for the caster's hits
+ *    - start_channel/stop_channel: Starts/keeps alive or ends a channeled spell (e.g. the beam)
+ *    - throw_projectile: Lobs a grenade that detonates on the ground or when its fuse ends
+ *    - game_tick: Periodic update for game state (scheduled, rejects calls from clients)
+ *    - projectile_tick: Projectile movement and hits, plus jump/gravity arcs, on their own faster
+ *      schedule (also scheduler-only)
+ *    - start_spectating/stop_spectating/set_spectate_target: Spectator follow-cam
+ *    - set_movement_config: Admin-only update of movement tunables
+ *    - broadcast_announcement: Admin-only server-wide banner message
+ *    - spawn_bot/remove_bot: Admin-only server-driven bots for load testing and PvE filler
+ *    - ban_player/unban_player: Admin-only bans; banning evicts an active player immediately
+ *    - set_team_count: Admin-only switch between free-for-all and N teams
+ *    - set_spawn_protection_radius: Admin-only distance enemies must keep from a chosen spawn
+ *    - set_max_players: Admin-only cap on active players (existing players are never kicked)
+ *    - spawn_training_dummy/remove_training_dummy: Admin-only practice targets
+ *    - place_flag/remove_flag: Admin-only capture-the-flag setup
+ *    - place_jump_pad/remove_jump_pad: Admin-only jump pads (duration 0 = permanent)
+ *    - place_speed_pickup: Admin-only pickup that hastes whoever collects it
+ *    - set_max_turn_rate: Admin-only limit on how fast players can turn (0 = unlimited)
+ *    - set_class_animation: Admin-only mapping of a class's animation for a logical state
+ *    - set_damage_cooldown: Admin-only post-hit window in which further hits are ignored (0 = off)
+ *    - set_lag_compensation: Admin-only cap on how far projectile hits rewind targets (0 = off)
+ *    - set_afk_timeout: Admin-only idle time after which players stop earning rewards (0 = off)
+ *    - set_level_damage_scaling: Admin-only damage change per level of difference between attacker and target (0 = off)
+ *    - set_min_health: Admin-only health floor for non-lethal practice lobbies (0 = off)
+ *    - set_out_of_bounds_mode: Admin-only choice of clamp, damage or teleport at the world edge
+ *    - set_damage_model: Admin-only switch between flat and armor-mitigated damage
+ *    - set_self_aoe_damage: Admin-only toggle for casters being hit by their own blasts
+ *    - set_tick_entity_budget: Admin-only load above which game_tick defers low-priority work (0 = off)
+ *    - set_spawn_privacy: Admin-only window in which freshly spawned players' views show only a coarse position (0 = off)
+ *    - set_cull_dead_caster_projectiles: Admin-only toggle for removing a dead caster's projectiles
+ *    - graceful_shutdown: Admin-only flush of every active player before a planned restart;
+ *      clears transient tables and refuses joins until reopen_server
  * 
  * 3. Table Structure:
  *    - All tables use Identity as primary keys where appropriate
  *    - Connection between tables maintained through identity references
+ *    - #[auto_inc] u64 ids (projectiles, effects, announcements) are opaque handles:
+ *      they only grow, are never reused after pruning and may have gaps, so clients
+ *      must only compare them for equality, never for ordering or counting.
+ *      Inserts into these tables use try_insert so an exhausted sequence is logged
+ *      and skipped instead of panicking the reducer.
  * 
  * When modifying:
  *    - Table changes require regenerating TypeScript bindings
@@ -29,43 +126,152 @@
  * Related files:
  *    - common.rs: Shared data structures used in table definitions
  *    - player_logic.rs: Player movement and state update calculations
+ *    - projectile_logic.rs: Projectile movement, hits and collision layers
+ *    - config_logic.rs: GameConfig access and ConfigSnapshot publishing
+ *    - class_logic.rs: Class name normalization and base stats
+ *    - spectator_logic.rs: Spectate target cleanup
+ *    - voice_logic.rs: VoiceProximity pair computation
+ *    - spell_logic.rs: Spell definitions and projectile lifetime clamping
+ *    - combat_logic.rs: Shared damage helper and death handling
+ *    - status_logic.rs: Status effects and their per-tick processing
+ *    - look_target_logic.rs: LookTarget snapshots
+ *    - team_logic.rs: Team assignment and ally checks
+ *    - totem_logic.rs: Healing totem placement and per-tick healing
+ *    - ground_effect_logic.rs: Fire patches and other ground hazards from spells
+ *    - reveal_logic.rs: Taunts and the reveals they create
+ *    - player_view_logic.rs: Keeping PlayerView in sync with PlayerData
+ *    - high_score_logic.rs: Recording all-time high scores
+ *    - duel_logic.rs: Duel handshake, damage isolation and restoring duelists afterwards
+ *    - mine_logic.rs: Proximity mine placement, arming and detonation
+ *    - flag_logic.rs: Flag pickup, capture scoring and dropping on death
+ *    - lag_compensation_logic.rs: Position history and rewound hit positions
+ *    - autosave_logic.rs: PlayerSnapshot autosave and restore
+ *    - channel_logic.rs: Channeled beam spells and their per-tick mana upkeep
+ *    - loot_logic.rs: Death loot drops and pickup
+ *    - join_queue_logic.rs: Queueing and admitting registrations on a full server
+ *    - shutdown_logic.rs: Graceful shutdown and the MatchState join flag
+ *    - arena_logic.rs: The ArenaInfo descriptor seeded in init
+ *    - ban_logic.rs: Bans and evicting banned players
+ *    - votekick_logic.rs: Player-run vote kicks
+ *    - bot_logic.rs: Server-driven bots
+ *    - tick_budget_logic.rs: Deferring low-priority game_tick work under heavy load
+ *    - account_link_logic.rs: Account keys and restoring progression across identities
  */
 
 // Declare modules
+mod account_link_logic;
+mod arena_logic;
+mod autosave_logic;
+mod ban_logic;
+mod bot_logic;
+mod channel_logic;
+mod class_logic;
+mod combat_logic;
 mod common;
+mod config_logic;
+mod duel_logic;
+mod flag_logic;
+mod ground_effect_logic;
+mod high_score_logic;
+mod join_queue_logic;
+mod lag_compensation_logic;
+mod look_target_logic;
+mod loot_logic;
+mod mine_logic;
 mod player_logic;
+mod player_view_logic;
+mod projectile_logic;
+mod reveal_logic;
+mod shutdown_logic;
+mod spectator_logic;
+mod spell_logic;
+mod status_logic;
+mod team_logic;
+mod tick_budget_logic;
+mod totem_logic;
+mod voice_logic;
+mod votekick_logic;
+#[cfg(test)]
+mod test_support;
 
 use spacetimedb::{ReducerContext, Identity, Table, Timestamp, ScheduleAt};
 use std::time::Duration; // Import standard Duration
 
 // Use items from common module (structs are needed for table definitions)
-use crate::common::{Vector3, InputState};
+use crate::common::{
+    Vector3, InputState, ArenaGeometry, PLAYER_COLORS, MAX_TEAM_COUNT, MAX_ANNOUNCEMENT_LENGTH,
+    MAX_ANNOUNCEMENT_DURATION_SECS, MAX_TITLE_LENGTH, USERNAME_CHECK_TTL_MICROS, GRAVITY_WELL_CAST_RANGE,
+    GRAVITY_WELL_DURATION_MICROS, GRAVITY_WELL_RADIUS, GRAVITY_WELL_STRENGTH, GAME_TICK_MICROS,
+    JUMP_PAD_ARM_DELAY_MICROS, MAX_JUMP_PAD_BOOST, PORTAL_CAST_RANGE, PORTAL_RADIUS, PORTAL_DURATION_MICROS,
+    PROJECTILE_TICK_MICROS, SUICIDE_COOLDOWN_MICROS, PVP_ENABLED_BY_DEFAULT, DUMMY_REGEN_DELAY_MICROS,
+    AUTO_REJOIN_WINDOW_MICROS, LAG_COMPENSATION_HISTORY_MICROS, MAX_PROJECTILES_PER_CASTER, MAX_SPAWN_PRIVACY_MICROS,
+};
 
 // --- Schema Definitions ---
 
-#[spacetimedb::table(name = player, public)]
+// Authoritative player state, private. Clients read PlayerView instead.
+#[spacetimedb::table(name = player)]
 #[derive(Clone)]
 pub struct PlayerData {
     #[primary_key]
     identity: Identity,
     username: String,
     character_class: String,
-    position: Vector3,
+    position: Vector3, // Authoritative position (all accepted inputs applied), used for reconciliation
+    smoothed_position: Vector3, // Eases toward `position` each tick, for clients that just render other players
     rotation: Vector3,
     health: i32,
     max_health: i32,
     mana: i32,
     max_mana: i32,
+    armor: i32, // Set from the class, only reduces damage under DAMAGE_MODEL_ARMOR
+    knockback_resistance: f32, // Set from the class, 0..1 fraction of knockback ignored
+    collision_radius: f32, // Set from the class, see player_logic::separate_from_players
     current_animation: String,
     is_moving: bool,
     is_running: bool,
+    current_speed: f32, // Ramped toward walk/run speed with every input
+    speed_multiplier: f32, // Scales walk/run speed, 1.0 unless hasted (status_logic::apply_haste)
     is_attacking: bool,
+    attack_until: Timestamp, // Server-owned end of the current attack
+    invulnerable_until: Timestamp, // I-frames, e.g. from a dodge-roll
+    dash_cooldown_until: Timestamp,
+    cleanse_cooldown_until: Timestamp, // See status_logic::cleanse
+    portal_cooldown_until: Timestamp, // Portals ignore the player until then, so they don't bounce straight back
+    block_until: Timestamp, // Guard raised by the block input, frontal hits are reduced until then
+    respawn_at: Timestamp, // While health is 0 the player respawns at this time; afterwards, when they last respawned
+    last_spawn_position: Vector3, // Where the player last joined or respawned, for spawn privacy
     is_casting: bool,
+    channeling_spell: Option<String>, // Beam being channeled, None when not channeling
+    channel_until: Timestamp, // Channel ends unless start_channel is called again before this
+    channel_target: Option<Identity>, // Player the beam is currently hitting
     last_input_seq: u32,
+    last_input_at: Timestamp, // When the last accepted input arrived, for turn-rate limiting and AFK checks
     input: InputState,
     color: String,
     vertical_velocity: f32,
     is_grounded: bool,
+    jump_consumed: bool, // Set when a jump fires, cleared when the client releases jump
+    gravity_scale: f32, // Multiplies GameConfig gravity, 1.0 = normal
+    jump_scale: f32, // Multiplies GameConfig jump_force, 1.0 = normal
+    kills: u32,
+    deaths: u32,
+    level: u32,
+    xp: u32,
+    score: u32,
+    shots_fired: u32, // Projectiles cast/thrown
+    shots_hit: u32, // Shots that hit a target at least once; accuracy = shots_hit / shots_fired
+    team: u32, // Always 0 in free-for-all
+    pvp_enabled: bool, // Players only damage each other when both have PvP on
+    in_combat: bool, // Set when dealing or taking damage, cleared COMBAT_TIMEOUT_MICROS after the last hit
+    last_combat_at: Timestamp, // Last time the player dealt or took damage
+    last_damaged_at: Timestamp, // Last hit that removed health, for GameConfig.damage_cooldown_micros
+    bound_position: Option<Vector3>, // Personal respawn point set by bind_location, kept across sessions
+    title: String, // Shown under the username, set by set_title and kept across sessions (empty = none)
+    is_bot: bool, // Server-driven bot (see bot_logic), left out of high scores and persistence
+    equipped_spells: Vec<String>, // Spell bar, starts as the class's default loadout
+    reported_latency_micros: i64, // Round-trip estimate sent by the client, used for lag compensation
+    recent_deaths: Vec<Timestamp>, // Deaths within RESPAWN_SCALING_WINDOW_MICROS, each one lengthens the respawn delay
 }
 
 #[spacetimedb::table(name = logged_out_player)]
@@ -81,9 +287,59 @@ pub struct LoggedOutPlayerData {
     max_health: i32,
     mana: i32,
     max_mana: i32,
+    // Progression - every field here must be copied on logout and restored on rejoin
+    kills: u32,
+    deaths: u32,
+    level: u32,
+    xp: u32,
+    score: u32,
+    shots_fired: u32,
+    shots_hit: u32,
+    bound_position: Option<Vector3>,
+    title: String,
     last_seen: Timestamp,
 }
 
+// Registrations waiting for a free slot, admitted oldest first by game_tick
+#[spacetimedb::table(name = join_queue, public)]
+#[derive(Clone)]
+pub struct JoinQueue {
+    #[primary_key]
+    identity: Identity,
+    username: String,
+    character_class: String,
+    queued_at: Timestamp,
+}
+
+// Crash backup of an active player's progression, refreshed by game_tick every AUTOSAVE_INTERVAL_MICROS
+#[spacetimedb::table(name = player_snapshot)]
+#[derive(Clone)]
+pub struct PlayerSnapshot {
+    #[primary_key]
+    identity: Identity,
+    kills: u32,
+    deaths: u32,
+    level: u32,
+    xp: u32,
+    score: u32,
+    shots_fired: u32,
+    shots_hit: u32,
+    saved_at: Timestamp,
+}
+
+// All-time bests per identity, kept across sessions (rows are never deleted)
+#[spacetimedb::table(name = high_score, public)]
+#[derive(Clone)]
+pub struct HighScore {
+    #[primary_key]
+    identity: Identity,
+    username: String, // Latest username seen for this identity
+    best_kills: u32,
+    best_score: u32,
+    best_level: u32,
+    updated_at: Timestamp,
+}
+
 #[spacetimedb::table(name = game_tick_schedule, public, scheduled(game_tick))]
 pub struct GameTickSchedule {
     #[primary_key]
@@ -92,6 +348,15 @@ pub struct GameTickSchedule {
     scheduled_at: ScheduleAt,
 }
 
+// Projectiles move fast, so they update more often than the rest of the game state
+#[spacetimedb::table(name = projectile_tick_schedule, public, scheduled(projectile_tick))]
+pub struct ProjectileTickSchedule {
+    #[primary_key]
+    #[auto_inc]
+    scheduled_id: u64,
+    scheduled_at: ScheduleAt,
+}
+
 #[spacetimedb::table(name = projectile, public)]
 #[derive(Clone)]
 pub struct ProjectileData {
@@ -103,8 +368,473 @@ pub struct ProjectileData {
     target_identity: Identity,
     speed: f32,
     created_at: Timestamp,
+    last_moved_at: Timestamp, // Movement each update covers the real time since this
     expires_at: Timestamp,
     projectile_type: String, // "homing_sphere", etc.
+    collision_mask: u32, // COLLISION_LAYER_* bits this projectile interacts with
+    direction: Vector3, // Normalized travel direction from the last update
+    piercing: bool, // Passes through targets instead of stopping at the first hit
+    max_pierce: u32, // Number of targets a piercing projectile can hit before stopping
+    hit_identities: Vec<Identity>, // Targets already hit, so a piercing projectile never re-hits
+    hit_counted: bool, // Whether this shot already counted toward the caster's shots_hit
+    damage: i32,
+    status_effect: String, // Status applied on hit ("burn"), empty for none
+    caster_team: u32, // Caster's team at spawn, so ally checks work even if the caster leaves
+    caster_color: String, // Caster's color at spawn, for client rendering without a player lookup
+    fragment_count: u32, // Fragments spawned on hit/expiry, 0 for projectiles that don't split
+    arcing: bool, // Thrown: falls under gravity and detonates on the ground or when the fuse (expires_at) runs out
+    vertical_velocity: f32, // Only used by arcing projectiles
+    aoe_radius: f32, // Detonations damage everyone in this radius, 0 for single-target projectiles
+    homing_strength: f32, // 0..1 share of the turn toward the target taken each update, 1 = perfect homing
+    gravity: f32, // Downward acceleration, 0 flies flat. Non-arcing projectiles that drop to the ground fizzle
+    rewind_micros: i64, // Lag compensation: hits are checked against targets' positions this long ago, 0 = current
+    lifetime_fraction: f32, // Lifetime left, 1.0 at spawn down to 0.0 at expires_at, refreshed each projectile tick
+    on_expire: String, // spell_logic::ON_EXPIRE_FIZZLE or ON_EXPIRE_EXPLODE (detonates in aoe_radius when expires_at passes)
+}
+
+#[spacetimedb::table(name = spell_definition, public)]
+#[derive(Clone)]
+pub struct SpellDefinition {
+    #[primary_key]
+    name: String, // Lowercase spell name as sent by the client
+    projectile_type: String,
+    speed: f32, // units per second
+    lifetime_secs: f32, // Clamped to MAX_PROJECTILE_LIFETIME_SECS at spawn
+    damage: i32,
+    collision_mask: u32,
+    piercing: bool,
+    max_pierce: u32,
+    status_effect: String, // Status applied on hit ("burn"), empty for none
+    fragment_count: u32, // Cluster spells split into this many fragments, 0 for none
+    homing_strength: f32, // Homing projectiles only: 1.0 tracks perfectly, lower values are dodgeable
+    projectile_gravity: f32, // Gravity on the spell's projectiles, 0 = flat flight
+    channel_mana_per_tick: i32, // > 0 marks a channeled spell (start_channel), paid every game tick; damage is per tick
+    on_expire: String, // What projectiles do when lifetime_secs runs out: "fizzle" or "explode"
+    aoe_radius: f32, // Radius of the expiry explosion, only used with on_expire "explode"
+    projectile_count: u32, // Projectiles per cast; more than 1 fires a fan that flies straight instead of homing
+    spread_angle: f32, // Radians between the outermost projectiles of a volley
+    ground_effect_radius: f32, // > 0 places a GroundEffect (damage per tick, lasting lifetime_secs) instead of a projectile
+}
+
+// Target frame data for one observer, refreshed in game_tick while the target is active
+#[spacetimedb::table(name = look_target, public)]
+#[derive(Clone)]
+pub struct LookTarget {
+    #[primary_key]
+    observer: Identity,
+    target_identity: Identity,
+    username: String,
+    character_class: String,
+    health: i32,
+    max_health: i32,
+    level: u32,
+    updated_at: Timestamp,
+}
+
+// Pulls the owner's enemies toward `center` each tick until it expires
+#[spacetimedb::table(name = gravity_well, public)]
+#[derive(Clone)]
+pub struct GravityWell {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    owner_identity: Identity,
+    owner_team: u32,
+    center: Vector3,
+    radius: f32,
+    strength: f32, // Pull in units per second
+    expires_at: Timestamp,
+}
+
+// Two linked points; a player entering either end's radius comes out at the other (see player_logic::apply_portals)
+#[spacetimedb::table(name = portal, public)]
+#[derive(Clone)]
+pub struct Portal {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    owner_identity: Identity,
+    position_a: Vector3,
+    position_b: Vector3,
+    radius: f32,
+    expires_at: Timestamp,
+}
+
+// Public projection of PlayerData with only what clients render, see player_view_logic
+#[spacetimedb::table(name = player_view, public)]
+#[derive(Clone, PartialEq)]
+pub struct PlayerView {
+    #[primary_key]
+    identity: Identity,
+    username: String,
+    character_class: String,
+    position: Vector3,
+    rotation: Vector3,
+    current_animation: String,
+    health: i32,
+    max_health: i32,
+    mana: i32,
+    max_mana: i32,
+    color: String,
+    team: u32,
+    title: String,
+}
+
+// An enemy revealed by a taunt. Visible to the taunter's team (`team`), or only to the
+// taunter in free-for-all, until expires_at.
+#[spacetimedb::table(name = reveal, public)]
+#[derive(Clone)]
+pub struct Reveal {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    revealed: Identity,
+    taunter: Identity,
+    team: u32, // Taunter's team when the reveal was made
+    expires_at: Timestamp,
+}
+
+// Per-class override of a logical animation state, e.g. wizard "walk-forward" -> "float-forward".
+// States without a row for a class use the state name itself.
+#[spacetimedb::table(name = class_animation, public)]
+#[derive(Clone)]
+pub struct ClassAnimation {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    character_class: String, // Normalized class name
+    state: String, // One of common::KNOWN_ANIMATIONS
+    animation: String, // Animation name clients play for this class
+}
+
+// Stationary practice target. Resets to max_health when depleted and regenerates
+// fully once left alone for DUMMY_REGEN_DELAY_MICROS.
+#[spacetimedb::table(name = training_dummy, public)]
+#[derive(Clone)]
+pub struct TrainingDummy {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    position: Vector3,
+    health: i32,
+    max_health: i32,
+    last_hit_at: Timestamp,
+}
+
+// Recent player positions for lag compensation (private), pruned to LAG_COMPENSATION_HISTORY_MICROS
+#[spacetimedb::table(name = position_history)]
+#[derive(Clone)]
+pub struct PositionHistory {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    identity: Identity,
+    position: Vector3,
+    recorded_at: Timestamp,
+}
+
+// Spell-made ground hazard (e.g. a fire patch) damaging enemies of its owner standing in it
+#[spacetimedb::table(name = ground_effect, public)]
+#[derive(Clone)]
+pub struct GroundEffect {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    kind: String, // The spell's projectile_type, e.g. "fire_patch", for client rendering
+    owner_identity: Identity,
+    owner_team: u32,
+    position: Vector3,
+    radius: f32,
+    damage_per_tick: i32,
+    expires_at: Timestamp,
+}
+
+// Launches grounded players upward. Permanent map features have no expires_at
+#[spacetimedb::table(name = jump_pad, public)]
+#[derive(Clone)]
+pub struct JumpPad {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    position: Vector3,
+    radius: f32,
+    boost: f32, // vertical_velocity given to a player standing on the pad
+    armed_at: Timestamp, // Inert until then
+    expires_at: Option<Timestamp>, // None = permanent
+}
+
+// Capture-the-flag objective. Sits at base_position until an enemy picks it up
+#[spacetimedb::table(name = flag, public)]
+#[derive(Clone)]
+pub struct Flag {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    team: u32, // Owning team, enemies of this team can carry it
+    position: Vector3, // Follows the carrier, stays where it was dropped otherwise
+    base_position: Vector3,
+    carrier: Option<Identity>,
+}
+
+// Dropped where a player died, collected by walking over it until it expires
+#[spacetimedb::table(name = loot_drop, public)]
+#[derive(Clone)]
+pub struct LootDrop {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    position: Vector3,
+    kind: String, // loot_logic::LOOT_KIND_DEATH or LOOT_KIND_SPEED (hastes the collector)
+    health: i32, // Restored on pickup, up to max_health
+    mana: i32, // Restored on pickup, up to max_mana
+    score: u32,
+    expires_at: Timestamp,
+}
+
+// Heals the owner and their allies within `radius` each tick until it expires
+#[spacetimedb::table(name = totem, public)]
+#[derive(Clone)]
+pub struct Totem {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    owner_identity: Identity,
+    owner_team: u32,
+    position: Vector3,
+    radius: f32,
+    heal_per_tick: i32,
+    expires_at: Timestamp,
+}
+
+// A duel request (accepted = false) or a running duel. expires_at is the request
+// timeout while pending and the end of the fight once accepted.
+#[spacetimedb::table(name = duel, public)]
+#[derive(Clone)]
+pub struct Duel {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    challenger: Identity,
+    target: Identity,
+    accepted: bool,
+    expires_at: Timestamp,
+    challenger_return: Vector3, // Where each duelist is put back when the duel ends
+    target_return: Vector3,
+}
+
+// Explodes once armed when an enemy of the owner enters trigger_radius, removed afterwards
+#[spacetimedb::table(name = mine, public)]
+#[derive(Clone)]
+pub struct Mine {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    owner_identity: Identity,
+    owner_team: u32,
+    position: Vector3,
+    armed_at: Timestamp, // Placement time + arm delay, the mine can't trigger before this
+    trigger_radius: f32,
+    damage: i32,
+    expires_at: Timestamp,
+}
+
+#[spacetimedb::table(name = status_effect, public)]
+#[derive(Clone)]
+pub struct StatusEffect {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    target_identity: Identity,
+    source_identity: Identity, // Credited for damage/kills caused by the effect
+    kind: String, // "burn", ...
+    stacks: u32,
+    magnitude: f32, // Kind-specific strength, e.g. burn damage per stack per tick
+    applied_at: Timestamp,
+    expires_at: Timestamp,
+}
+
+#[spacetimedb::table(name = game_config)]
+#[derive(Clone)]
+pub struct GameConfig {
+    #[primary_key]
+    id: u32, // Always config_logic::GAME_CONFIG_ID
+    player_speed: f32,
+    sprint_multiplier: f32,
+    gravity: f32,
+    jump_force: f32,
+    team_count: u32, // 1 = free-for-all (no friendly-fire protection), 2+ = team modes
+    damage_model: String, // combat_logic::DAMAGE_MODEL_FLAT or DAMAGE_MODEL_ARMOR
+    self_aoe_damage: bool, // Whether area detonations also damage their caster
+    spawn_protection_radius: f32, // Spawn points with an enemy this close are avoided
+    out_of_bounds_mode: String, // player_logic::OUT_OF_BOUNDS_CLAMP, _DAMAGE or _TELEPORT
+    max_players: u32, // register_player queues new players once this many are active
+    max_turn_rate: f32, // Radians per second the stored facing may turn, 0 = unlimited
+    damage_cooldown_micros: i64, // After taking damage a player ignores further hits this long, 0 = off
+    lag_compensation_max_micros: i64, // Most a projectile hit check rewinds its target for the caster's latency, 0 = off
+    afk_timeout_micros: i64, // Players with no input for this long earn no kill or capture rewards, 0 = off
+    min_health: i32, // Damage never takes health below this (non-lethal practice), 0 = off
+    level_damage_scaling: f32, // Damage change per level the attacker is above (-) or below (+) the target, 0 = off
+    cull_dead_caster_projectiles: bool, // Whether projectiles vanish once their caster is dead or gone
+    spawn_privacy_micros: i64, // After (re)spawning, PlayerView shows only a coarse position this long, 0 = off
+    tick_entity_budget: u64, // Above this many simulated rows game_tick defers low-priority work, 0 = off
+}
+
+// Public copy of the client-relevant GameConfig values, kept in sync by config_logic
+#[spacetimedb::table(name = config_snapshot, public)]
+#[derive(Clone)]
+pub struct ConfigSnapshot {
+    #[primary_key]
+    id: u32,
+    player_speed: f32,
+    sprint_multiplier: f32,
+    gravity: f32,
+    jump_force: f32,
+    updated_at: Timestamp,
+}
+
+#[spacetimedb::table(name = admin)]
+#[derive(Clone)]
+pub struct Admin {
+    #[primary_key]
+    identity: Identity,
+}
+
+// Single row describing the arena (bounds, ground, spawn points, static geometry), see arena_logic
+#[spacetimedb::table(name = arena_info, public)]
+#[derive(Clone)]
+pub struct ArenaInfo {
+    #[primary_key]
+    id: u32, // Always arena_logic::ARENA_INFO_ID
+    half_extent: f32, // Playable area is -half_extent..half_extent on x and z
+    ground_height: f32,
+    spawn_points: Vec<Vector3>,
+    geometry: Vec<ArenaGeometry>,
+}
+
+// Single row, see shutdown_logic. A missing row means joins are accepted.
+#[spacetimedb::table(name = match_state, public)]
+#[derive(Clone)]
+pub struct MatchState {
+    #[primary_key]
+    id: u32, // Always shutdown_logic::MATCH_STATE_ID
+    accepting_joins: bool,
+    changed_at: Timestamp,
+}
+
+// Per-bot AI state, see bot_logic
+#[spacetimedb::table(name = bot_state)]
+#[derive(Clone)]
+pub struct BotState {
+    #[primary_key]
+    identity: Identity,
+    wander_yaw: f32, // Heading while no enemy is in range
+    next_wander_at: Timestamp,
+    next_cast_at: Timestamp,
+}
+
+// Single row tracking game_tick load and shedding, see tick_budget_logic
+#[spacetimedb::table(name = tick_load)]
+#[derive(Clone)]
+pub struct TickLoad {
+    #[primary_key]
+    id: u32, // Always tick_budget_logic::TICK_LOAD_ID
+    last_load: u64,
+    consecutive_shed_ticks: u32,
+    total_shed_ticks: u64,
+}
+
+#[spacetimedb::table(name = ban)]
+#[derive(Clone)]
+pub struct Ban {
+    #[primary_key]
+    identity: Identity,
+    reason: String,
+    banned_by: Identity,
+    banned_at: Timestamp,
+}
+
+// Player-chosen key linked to one identity, see account_link_logic. Private: the key is the secret
+#[spacetimedb::table(name = account_link)]
+#[derive(Clone)]
+pub struct AccountLink {
+    #[primary_key]
+    key: String,
+    #[unique]
+    identity: Identity,
+    previous_identity: Option<Identity>, // Set when the key was claimed, cleared once registration restores it
+    linked_at: Timestamp,
+}
+
+// An open vote to kick `target`, resolved in game_tick (see votekick_logic)
+#[spacetimedb::table(name = vote_kick, public)]
+#[derive(Clone)]
+pub struct VoteKick {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    #[unique]
+    target: Identity, // One open vote per target
+    started_by: Identity,
+    yes_votes: Vec<Identity>, // Includes the starter
+    no_votes: Vec<Identity>,
+    started_at: Timestamp,
+    expires_at: Timestamp,
+}
+
+#[spacetimedb::table(name = spectator, public)]
+#[derive(Clone)]
+pub struct Spectator {
+    #[primary_key]
+    identity: Identity,
+    spectate_target: Option<Identity>, // Active player the follow-cam should track
+    joined_at: Timestamp,
+}
+
+// Written when a projectile is removed on impact/expiry, pruned shortly after
+#[spacetimedb::table(name = impact_effect, public)]
+#[derive(Clone)]
+pub struct ImpactEffect {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    position: Vector3,
+    kind: String, // "explosion", "spark", "fizzle"
+    at: Timestamp,
+}
+
+// One row per pair of players close enough to hear each other (player_a < player_b)
+#[spacetimedb::table(name = voice_proximity, public)]
+#[derive(Clone)]
+pub struct VoiceProximity {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    player_a: Identity,
+    player_b: Identity,
+    distance: f32,
+}
+
+// Reducers can't return values, so check_username writes its answer here for the caller
+#[spacetimedb::table(name = username_check, public)]
+#[derive(Clone)]
+pub struct UsernameCheck {
+    #[primary_key]
+    identity: Identity,
+    username: String,
+    available: bool,
+    checked_at: Timestamp,
+}
+
+#[spacetimedb::table(name = announcement, public)]
+#[derive(Clone)]
+pub struct Announcement {
+    #[primary_key]
+    #[auto_inc]
+    id: u64,
+    text: String,
+    sender: Identity,
+    created_at: Timestamp,
+    expires_at: Timestamp,
 }
 
 // --- Lifecycle Reducers ---
@@ -114,7 +844,7 @@ pub fn init(ctx: &ReducerContext) -> Result<(), String> {
     spacetimedb::log::info!("[INIT] Initializing Vibe Multiplayer module...");
     if ctx.db.game_tick_schedule().count() == 0 {
         spacetimedb::log::info!("[INIT] Scheduling initial game tick (every 1 second)...");
-        let loop_duration = Duration::from_secs(1);
+        let loop_duration = Duration::from_micros(GAME_TICK_MICROS as u64);
         let schedule = GameTickSchedule {
             scheduled_id: 0,
             scheduled_at: ScheduleAt::Interval(loop_duration.into()),
@@ -126,13 +856,60 @@ pub fn init(ctx: &ReducerContext) -> Result<(), String> {
     } else {
         spacetimedb::log::info!("[INIT] Game tick already scheduled.");
     }
+
+    if ctx.db.projectile_tick_schedule().count() == 0 {
+        spacetimedb::log::info!("[INIT] Scheduling projectile tick (every {} ms)...", PROJECTILE_TICK_MICROS / 1000);
+        let loop_duration = Duration::from_micros(PROJECTILE_TICK_MICROS as u64);
+        let schedule = ProjectileTickSchedule {
+            scheduled_id: 0,
+            scheduled_at: ScheduleAt::Interval(loop_duration.into()),
+        };
+        match ctx.db.projectile_tick_schedule().try_insert(schedule) {
+            Ok(row) => spacetimedb::log::info!("[INIT] Projectile tick schedule inserted successfully. ID: {}", row.scheduled_id),
+            Err(e) => spacetimedb::log::error!("[INIT] FAILED to insert projectile tick schedule: {}", e),
+        }
+    } else {
+        spacetimedb::log::info!("[INIT] Projectile tick already scheduled.");
+    }
+
+    if ctx.db.game_config().id().find(config_logic::GAME_CONFIG_ID).is_none() {
+        spacetimedb::log::info!("[INIT] Seeding default game config...");
+        ctx.db.game_config().insert(config_logic::default_game_config());
+    }
+    config_logic::sync_config_snapshot(ctx);
+    arena_logic::sync_arena_info(ctx);
+
+    for spell in spell_logic::default_spell_definitions() {
+        if ctx.db.spell_definition().name().find(&spell.name).is_none() {
+            ctx.db.spell_definition().insert(spell);
+        }
+    }
+
+    // The identity that publishes the module is the first admin
+    if ctx.db.admin().identity().find(ctx.sender).is_none() {
+        spacetimedb::log::info!("[INIT] Registering {} as admin.", ctx.sender);
+        ctx.db.admin().insert(Admin { identity: ctx.sender });
+    }
     Ok(())
 }
 
 #[spacetimedb::reducer(client_connected)]
 pub fn identity_connected(ctx: &ReducerContext) {
     spacetimedb::log::info!("Client connected: {}", ctx.sender);
-    // Player registration/re-joining happens in register_player reducer called by client
+    // New players register through the register_player reducer called by the client.
+    // Players who dropped only moments ago are restored right away; a register_player
+    // call arriving afterwards finds them active and does nothing.
+    let Some(logged_out_player) = ctx.db.logged_out_player().identity().find(ctx.sender) else {
+        return;
+    };
+    if !within_rejoin_window(logged_out_player.last_seen, ctx.timestamp) || join_queue_logic::should_queue(ctx) {
+        return;
+    }
+    spacetimedb::log::info!("Auto-restoring player {} after a short disconnect.", ctx.sender);
+    if let Err(e) = register_player_as(ctx, ctx.sender, logged_out_player.username, logged_out_player.character_class) {
+        // Never refuse the connection, the client can still register normally
+        spacetimedb::log::warn!("Auto-restore of {} failed: {}", ctx.sender, e);
+    }
 }
 
 #[spacetimedb::reducer(client_disconnected)]
@@ -142,10 +919,39 @@ pub fn identity_disconnected(ctx: &ReducerContext) {
     let logout_time: Timestamp = ctx.timestamp;
 
     if let Some(player) = ctx.db.player().identity().find(player_identity) {
-        spacetimedb::log::info!("Moving player {} to logged_out_player table.", player_identity);
-        let logged_out_player = LoggedOutPlayerData {
-            identity: player.identity,
-            username: player.username.clone(),
+        logout_player(ctx, player);
+    } else {
+        spacetimedb::log::warn!("Disconnect by player {} not found in active player table.", player_identity);
+        if let Some(mut logged_out_player) = ctx.db.logged_out_player().identity().find(player_identity) {
+            logged_out_player.last_seen = logout_time;
+            ctx.db.logged_out_player().identity().update(logged_out_player);
+            spacetimedb::log::warn!("Updated last_seen for already logged out player {}.", player_identity);
+        }
+    }
+
+    if ctx.db.spectator().identity().delete(player_identity) {
+        spacetimedb::log::info!("Removed spectator {}.", player_identity);
+    }
+    ctx.db.look_target().observer().delete(player_identity);
+    if ctx.db.join_queue().identity().delete(player_identity) {
+        spacetimedb::log::info!("Removed {} from the join queue.", player_identity);
+    }
+}
+
+// Move an active player to logged_out_player, persisting their progression (bots are
+// just removed). Used by disconnects and graceful_shutdown.
+fn logout_player(ctx: &ReducerContext, player: PlayerData) {
+    let player_identity = player.identity;
+    let logout_time = ctx.timestamp;
+    if player.is_bot {
+        // Bots have no progression to keep
+        spacetimedb::log::info!("Removing bot {}.", player_identity);
+    } else {
+        spacetimedb::log::info!("Moving player {} to logged_out_player table.", player_identity);
+        high_score_logic::record_high_score(ctx, &player);
+        let logged_out_player = LoggedOutPlayerData {
+            identity: player.identity,
+            username: player.username.clone(),
             character_class: player.character_class.clone(),
             position: player.position.clone(),
             rotation: player.rotation.clone(),
@@ -153,25 +959,44 @@ pub fn identity_disconnected(ctx: &ReducerContext) {
             max_health: player.max_health,
             mana: player.mana,
             max_mana: player.max_mana,
+            kills: player.kills,
+            deaths: player.deaths,
+            level: player.level,
+            xp: player.xp,
+            score: player.score,
+            shots_fired: player.shots_fired,
+            shots_hit: player.shots_hit,
+            bound_position: player.bound_position.clone(),
+            title: player.title.clone(),
             last_seen: logout_time,
         };
         ctx.db.logged_out_player().insert(logged_out_player);
-        ctx.db.player().identity().delete(player_identity);
-    } else {
-        spacetimedb::log::warn!("Disconnect by player {} not found in active player table.", player_identity);
-        if let Some(mut logged_out_player) = ctx.db.logged_out_player().identity().find(player_identity) {
-            logged_out_player.last_seen = logout_time;
-            ctx.db.logged_out_player().identity().update(logged_out_player);
-            spacetimedb::log::warn!("Updated last_seen for already logged out player {}.", player_identity);
-        }
     }
+    ctx.db.player().identity().delete(player_identity);
+    ctx.db.player_view().identity().delete(player_identity);
+    // The logged-out row now holds the progression, the crash backup is no longer needed
+    ctx.db.player_snapshot().identity().delete(player_identity);
+    spectator_logic::clear_spectate_targets_for(ctx, player_identity);
+    lag_compensation_logic::clear_position_history(ctx, player_identity);
 }
 
 // --- Game Specific Reducers ---
 
 #[spacetimedb::reducer]
-pub fn register_player(ctx: &ReducerContext, username: String, character_class: String) {
-    let player_identity: Identity = ctx.sender;
+pub fn register_player(ctx: &ReducerContext, username: String, character_class: String) -> Result<(), String> {
+    check_can_join(ban_logic::is_banned(ctx, ctx.sender), shutdown_logic::accepting_joins(ctx))?;
+    // A full server (or one with people already waiting) queues the caller instead.
+    // This must not return Err, or the queue insert would be rolled back with it.
+    if ctx.db.player().identity().find(ctx.sender).is_none() && join_queue_logic::should_queue(ctx) {
+        return join_queue_logic::enqueue(ctx, ctx.sender, username, character_class);
+    }
+    register_player_as(ctx, ctx.sender, username, character_class)
+}
+
+// Registration shared by the reducer and join queue admission (where ctx.sender is the module)
+fn register_player_as(ctx: &ReducerContext, player_identity: Identity, username: String, character_class: String) -> Result<(), String> {
+    // Clients may send any casing/whitespace, store the canonical form
+    let character_class = class_logic::normalize_class(&character_class);
     spacetimedb::log::info!(
         "Registering player {} ({}) with class {}",
         username,
@@ -181,15 +1006,32 @@ pub fn register_player(ctx: &ReducerContext, username: String, character_class:
 
     if ctx.db.player().identity().find(player_identity).is_some() {
         spacetimedb::log::warn!("Player {} is already active.", player_identity);
-        return;
+        return Ok(());
+    }
+    // Also covers auto-rejoin and queue admission, which don't go through register_player
+    if let Err(e) = check_can_join(ban_logic::is_banned(ctx, player_identity), shutdown_logic::accepting_joins(ctx)) {
+        spacetimedb::log::warn!("Rejecting {}: {}", player_identity, e);
+        return Err(e);
     }
 
-    // Assign color and position based on current player count
+    // Color and team come from the active rows as seen inside this transaction, so
+    // back-to-back registrations each see the earlier insert and pick differently
     let player_count = ctx.db.player().iter().count();
-    let colors = ["cyan", "magenta", "yellow", "lightgreen", "white", "orange"];
-    let assigned_color = colors[player_count % colors.len()].to_string();
-    // Simple horizontal offset for spawning, start Y at 1.0
-    let spawn_position = Vector3 { x: (player_count as f32 * 5.0) - 2.5, y: 1.0, z: 0.0 };
+    let config = config_logic::get_config(ctx);
+    if is_server_full(player_count, config.max_players) {
+        spacetimedb::log::warn!("Rejecting {}: server full ({} players).", player_identity, player_count);
+        return Err("Server full.".to_string());
+    }
+
+    // Joining the game ends spectating
+    ctx.db.spectator().identity().delete(player_identity);
+
+    let assigned_color = pick_free_color(ctx, player_count);
+    let assigned_team = team_logic::assign_team(ctx, config.team_count);
+    let spawn_position = player_logic::choose_spawn_position(ctx, player_identity, assigned_team, player_count, &config);
+
+    // A freshly claimed account key brings its progression along as this identity's logged-out row
+    account_link_logic::restore_linked_progression(ctx, player_identity);
 
     if let Some(logged_out_player) = ctx.db.logged_out_player().identity().find(player_identity) {
         spacetimedb::log::info!("Player {} is rejoining.", player_identity);
@@ -197,61 +1039,340 @@ pub fn register_player(ctx: &ReducerContext, username: String, character_class:
             forward: false, backward: false, left: false, right: false,
             sprint: false, jump: false, attack: false, cast_spell: false,
             dash: false,
+            block: false,
             sequence: 0
         };
+        let character_class = class_logic::normalize_class(&logged_out_player.character_class);
+        let class_stats = class_logic::class_stats(&character_class);
+        let equipped_spells = class_logic::default_loadout(&character_class);
         let rejoining_player = PlayerData {
             identity: logged_out_player.identity,
             username: logged_out_player.username.clone(),
-            character_class: logged_out_player.character_class.clone(),
-            position: spawn_position,
+            character_class,
+            position: spawn_position.clone(),
+            smoothed_position: spawn_position.clone(),
+            last_spawn_position: spawn_position,
             rotation: logged_out_player.rotation.clone(),
             health: logged_out_player.health,
             max_health: logged_out_player.max_health,
             mana: logged_out_player.mana,
             max_mana: logged_out_player.max_mana,
+            armor: class_stats.armor,
+            knockback_resistance: class_stats.knockback_resistance,
+            collision_radius: class_stats.collision_radius,
             current_animation: "idle".to_string(),
             is_moving: false,
             is_running: false,
+            current_speed: 0.0,
+            speed_multiplier: 1.0,
             is_attacking: false,
+            attack_until: ctx.timestamp,
+            invulnerable_until: ctx.timestamp,
+            dash_cooldown_until: ctx.timestamp,
+            cleanse_cooldown_until: ctx.timestamp,
+            portal_cooldown_until: ctx.timestamp,
+            block_until: ctx.timestamp,
+            respawn_at: ctx.timestamp,
             is_casting: false,
+            channeling_spell: None,
+            channel_until: ctx.timestamp,
+            channel_target: None,
             last_input_seq: 0,
+            last_input_at: ctx.timestamp,
             input: default_input,
             color: assigned_color,
             vertical_velocity: 0.0,
             is_grounded: true,
+            jump_consumed: false,
+            gravity_scale: 1.0,
+            jump_scale: 1.0,
+            kills: logged_out_player.kills,
+            deaths: logged_out_player.deaths,
+            level: logged_out_player.level,
+            xp: logged_out_player.xp,
+            score: logged_out_player.score,
+            shots_fired: logged_out_player.shots_fired,
+            shots_hit: logged_out_player.shots_hit,
+            team: assigned_team,
+            pvp_enabled: PVP_ENABLED_BY_DEFAULT,
+            in_combat: false,
+            last_combat_at: ctx.timestamp,
+            last_damaged_at: ctx.timestamp,
+            bound_position: logged_out_player.bound_position.clone(),
+            title: logged_out_player.title.clone(),
+            is_bot: false,
+            equipped_spells,
+            reported_latency_micros: 0,
+            recent_deaths: Vec::new(),
         };
         ctx.db.player().insert(rejoining_player);
         ctx.db.logged_out_player().identity().delete(player_identity);
     } else {
         spacetimedb::log::info!("Registering new player {}.", player_identity);
+        let stats = class_logic::class_stats(&character_class);
+        let equipped_spells = class_logic::default_loadout(&character_class);
         let default_input = InputState {
             forward: false, backward: false, left: false, right: false,
             sprint: false, jump: false, attack: false, cast_spell: false,
             dash: false,
+            block: false,
             sequence: 0
         };
-        ctx.db.player().insert(PlayerData {
+        let mut new_player = PlayerData {
             identity: player_identity,
             username,
             character_class,
-            position: spawn_position,
+            position: spawn_position.clone(),
+            smoothed_position: spawn_position.clone(),
+            last_spawn_position: spawn_position,
             rotation: Vector3 { x: 0.0, y: 0.0, z: 0.0 },
-            health: 100,
-            max_health: 100,
-            mana: 100,
-            max_mana: 100,
+            health: stats.max_health,
+            max_health: stats.max_health,
+            mana: stats.max_mana,
+            max_mana: stats.max_mana,
+            armor: stats.armor,
+            knockback_resistance: stats.knockback_resistance,
+            collision_radius: stats.collision_radius,
             current_animation: "idle".to_string(),
             is_moving: false,
             is_running: false,
+            current_speed: 0.0,
+            speed_multiplier: 1.0,
             is_attacking: false,
+            attack_until: ctx.timestamp,
+            invulnerable_until: ctx.timestamp,
+            dash_cooldown_until: ctx.timestamp,
+            cleanse_cooldown_until: ctx.timestamp,
+            portal_cooldown_until: ctx.timestamp,
+            block_until: ctx.timestamp,
+            respawn_at: ctx.timestamp,
             is_casting: false,
+            channeling_spell: None,
+            channel_until: ctx.timestamp,
+            channel_target: None,
             last_input_seq: 0,
+            last_input_at: ctx.timestamp,
             input: default_input,
             color: assigned_color,
             vertical_velocity: 0.0,
             is_grounded: true,
-        });
+            jump_consumed: false,
+            gravity_scale: 1.0,
+            jump_scale: 1.0,
+            kills: 0,
+            deaths: 0,
+            level: 1,
+            xp: 0,
+            score: 0,
+            shots_fired: 0,
+            shots_hit: 0,
+            team: assigned_team,
+            pvp_enabled: PVP_ENABLED_BY_DEFAULT,
+            in_combat: false,
+            last_combat_at: ctx.timestamp,
+            last_damaged_at: ctx.timestamp,
+            bound_position: None,
+            title: String::new(),
+            is_bot: false,
+            equipped_spells,
+            reported_latency_micros: 0,
+            recent_deaths: Vec::new(),
+        };
+        // No clean logout on record, recover progression from the last autosave if there is one
+        autosave_logic::restore_from_snapshot(ctx, &mut new_player);
+        ctx.db.player().insert(new_player);
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn check_username(ctx: &ReducerContext, username: String) {
+    let username = username.trim().to_string();
+    let available = !username.is_empty() && !is_username_taken(ctx, &username, ctx.sender);
+    let check = UsernameCheck {
+        identity: ctx.sender,
+        username,
+        available,
+        checked_at: ctx.timestamp,
+    };
+
+    if ctx.db.username_check().identity().find(ctx.sender).is_some() {
+        ctx.db.username_check().identity().update(check);
+    } else {
+        ctx.db.username_check().insert(check);
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn set_color(ctx: &ReducerContext, color: String) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can set a color.".to_string());
+    };
+    let color = color.trim().to_lowercase();
+    if !is_valid_color(&color) {
+        return Err(format!("'{}' is not a palette color or #rrggbb hex color.", color));
+    }
+    let taken = ctx.db.player().iter()
+        .any(|p| p.identity != ctx.sender && p.color.eq_ignore_ascii_case(&color));
+    if taken {
+        return Err(format!("Color '{}' is already in use.", color));
+    }
+
+    player.color = color;
+    ctx.db.player().identity().update(player);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_title(ctx: &ReducerContext, title: String) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can set a title.".to_string());
+    };
+    player.title = validate_title(&title)?;
+    ctx.db.player().identity().update(player);
+    Ok(())
+}
+
+// Calling with the same key from a new identity (before registering) claims the account
+#[spacetimedb::reducer]
+pub fn link_account(ctx: &ReducerContext, key: String) -> Result<(), String> {
+    account_link_logic::link_account(ctx, key)
+}
+
+// Pass None to clear the target frame
+#[spacetimedb::reducer]
+pub fn set_look_target(ctx: &ReducerContext, target_identity: Option<Identity>) -> Result<(), String> {
+    look_target_logic::set_look_target(ctx, ctx.sender, target_identity)
+}
+
+#[spacetimedb::reducer]
+pub fn change_class(ctx: &ReducerContext, character_class: String) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can change class.".to_string());
+    };
+    let character_class = class_logic::normalize_class(&character_class);
+    if character_class.is_empty() {
+        return Err("Class cannot be empty.".to_string());
+    }
+
+    let stats = class_logic::class_stats(&character_class);
+    spacetimedb::log::info!("Player {} changing class {} -> {}", ctx.sender, player.character_class, character_class);
+    player.equipped_spells = class_logic::default_loadout(&character_class);
+    player.character_class = character_class;
+    player_logic::set_max_health(&mut player, stats.max_health);
+    player_logic::set_max_mana(&mut player, stats.max_mana);
+    player.armor = stats.armor;
+    player.knockback_resistance = stats.knockback_resistance;
+    player.collision_radius = stats.collision_radius;
+    ctx.db.player().identity().update(player);
+    Ok(())
+}
+
+// Make the caller's current position their respawn point
+#[spacetimedb::reducer]
+pub fn bind_location(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can bind a location.".to_string());
+    };
+    if player.health <= 0 {
+        return Err("Dead players cannot bind a location.".to_string());
+    }
+    if player.in_combat {
+        return Err("Cannot bind a location while in combat.".to_string());
+    }
+    if player_logic::is_out_of_bounds(&player.position) {
+        return Err("Cannot bind a location outside the world.".to_string());
+    }
+    spacetimedb::log::info!("Player {} bound their respawn point.", ctx.sender);
+    player.bound_position = Some(player.position.clone());
+    ctx.db.player().identity().update(player);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_pvp(ctx: &ReducerContext, enabled: bool) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can change their PvP flag.".to_string());
+    };
+    player.pvp_enabled = enabled;
+    ctx.db.player().identity().update(player);
+    spacetimedb::log::info!("Player {} set PvP to {}.", ctx.sender, enabled);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn taunt(ctx: &ReducerContext) -> Result<(), String> {
+    reveal_logic::taunt(ctx)
+}
+
+#[spacetimedb::reducer]
+pub fn charge(ctx: &ReducerContext) -> Result<(), String> {
+    player_logic::charge(ctx)
+}
+
+#[spacetimedb::reducer]
+pub fn blood_sacrifice(ctx: &ReducerContext) -> Result<(), String> {
+    player_logic::blood_sacrifice(ctx)
+}
+
+#[spacetimedb::reducer]
+pub fn share_resource(ctx: &ReducerContext, target_identity: Identity, resource: String, amount: i32) -> Result<(), String> {
+    player_logic::share_resource(ctx, target_identity, &resource, amount)
+}
+
+#[spacetimedb::reducer]
+pub fn swap_positions(ctx: &ReducerContext, target_identity: Identity) -> Result<(), String> {
+    player_logic::swap_positions(ctx, target_identity)
+}
+
+#[spacetimedb::reducer]
+pub fn cleanse(ctx: &ReducerContext) -> Result<(), String> {
+    let removed = status_logic::cleanse(ctx)?;
+    spacetimedb::log::info!("Player {} cleansed {} status effect(s).", ctx.sender, removed);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn toggle_thorns(ctx: &ReducerContext) -> Result<(), String> {
+    let active = status_logic::toggle_thorns(ctx)?;
+    spacetimedb::log::info!("Player {} turned their thorns {}.", ctx.sender, if active { "on" } else { "off" });
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn toggle_vampiric_aura(ctx: &ReducerContext) -> Result<(), String> {
+    let active = status_logic::toggle_vampiric_aura(ctx)?;
+    spacetimedb::log::info!("Player {} turned their vampiric aura {}.", ctx.sender, if active { "on" } else { "off" });
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn suicide(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can respawn.".to_string());
+    };
+    if player.health <= 0 {
+        return Err("You are already dead.".to_string());
+    }
+    let since_respawn = ctx.timestamp.to_micros_since_unix_epoch() - player.respawn_at.to_micros_since_unix_epoch();
+    if since_respawn < SUICIDE_COOLDOWN_MICROS {
+        return Err("You respawned too recently.".to_string());
     }
+
+    // A normal death with no killer: counts as a death and credits nobody
+    combat_logic::kill_player(ctx, player.identity, None);
+    Ok(())
+}
+
+// Clients report their measured round-trip time; only used while lag compensation is on
+#[spacetimedb::reducer]
+pub fn report_latency(ctx: &ReducerContext, latency_micros: i64) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can report latency.".to_string());
+    };
+    player.reported_latency_micros = latency_micros.clamp(0, LAG_COMPENSATION_HISTORY_MICROS);
+    ctx.db.player().identity().update(player);
+    Ok(())
 }
 
 #[spacetimedb::reducer]
@@ -260,37 +1381,78 @@ pub fn update_player_input(
     input: InputState,
     _client_pos: Vector3,
     client_rot: Vector3,
-    client_animation: String,
-) {
+    _client_animation: String, // Animation is determined server-side now
+) -> Result<(), String> {
     if let Some(mut player) = ctx.db.player().identity().find(ctx.sender) {
-        player_logic::update_input_state(&mut player, input, client_rot, client_animation);
+        // Waiting to respawn: no moving, rolling, jumping or blocking (handle_death set the death animation)
+        if player.health <= 0 {
+            return Err("Dead players cannot move.".to_string());
+        }
+        if player_logic::is_stale_input(&player, &input) {
+            spacetimedb::log::debug!(
+                "Dropping stale input {} from {} (last applied {})",
+                input.sequence,
+                ctx.sender,
+                player.last_input_seq
+            );
+            return Ok(());
+        }
+        let config = config_logic::get_config(ctx);
+        let animation_state = player_logic::determine_animation(&input, true);
+        player_logic::update_input_state(&mut player, input, client_rot, &config, ctx.timestamp);
+        player_logic::separate_from_players(ctx, &mut player);
+        player.current_animation = class_logic::class_animation(ctx, &player.character_class, animation_state);
+        player_view_logic::sync_player_view(ctx, &player);
         ctx.db.player().identity().update(player);
     } else {
         spacetimedb::log::warn!("Player {} tried to update input but is not active.", ctx.sender);
     }
+    Ok(())
 }
 
 #[spacetimedb::reducer]
 pub fn cast_spell(
     ctx: &ReducerContext,
     spell_name: String,
-) {
-    let caster_identity = ctx.sender;
+) -> Result<(), String> {
+    cast_spell_as(ctx, ctx.sender, spell_name)
+}
+
+// Casting shared by the reducer and bots (where ctx.sender is the module)
+fn cast_spell_as(ctx: &ReducerContext, caster_identity: Identity, spell_name: String) -> Result<(), String> {
     spacetimedb::log::info!("🔥 CAST_SPELL CALLED: {} casting {}", caster_identity, spell_name);
     
     // Find the caster
     spacetimedb::log::info!("🔍 Looking for caster: {}", caster_identity);
     if let Some(caster) = ctx.db.player().identity().find(caster_identity) {
         spacetimedb::log::info!("✅ Found caster: {}", caster_identity);
+        if caster.health <= 0 {
+            return Err("Dead players cannot cast spells.".to_string());
+        }
         
         spacetimedb::log::info!("Player {} cast {}", caster_identity, spell_name);
         
-        // Find nearest player (excluding caster)
+        let spell = spell_logic::find_spell(ctx, &spell_name);
+        if spell.channel_mana_per_tick > 0 {
+            spacetimedb::log::warn!("Player {} tried to cast channeled spell {}, use start_channel.", caster_identity, spell.name);
+            return Err(format!("'{}' is a channeled spell, use start_channel.", spell.name));
+        }
+        if spell.ground_effect_radius > 0.0 {
+            ground_effect_logic::place_ground_effect(ctx, &caster, &spell)?;
+            spacetimedb::log::info!("Player {} placed {}", caster_identity, spell.name);
+            return Ok(());
+        }
+        let collision_mask = spell.collision_mask;
+        let team_count = config_logic::get_config(ctx).team_count;
+
+        // Find nearest player (excluding caster) that this projectile can collide with
         let mut nearest_player: Option<PlayerData> = None;
         let mut nearest_distance = f32::MAX;
         
         for player in ctx.db.player().iter() {
-            if player.identity != caster_identity {
+            let layer = projectile_logic::collision_layer_for_player(caster_identity, caster.team, team_count, &player);
+            // Corpses aren't targets, homing on one would waste the cast
+            if player.identity != caster_identity && player.health > 0 && projectile_logic::can_collide(collision_mask, layer) {
                 let distance = calculate_distance(&caster.position, &player.position);
                 if distance < nearest_distance {
                     nearest_distance = distance;
@@ -299,162 +1461,1075 @@ pub fn cast_spell(
             }
         }
         
+        // The whole volley counts against the per-caster cap
+        let projectile_count = spell_logic::clamp_projectile_count(spell.projectile_count);
+        if projectile_logic::projectiles_in_flight(ctx, caster_identity) + projectile_count as usize > MAX_PROJECTILES_PER_CASTER {
+            spacetimedb::log::warn!("Player {} has too many projectiles in flight to cast {}.", caster_identity, spell.name);
+            return Err("Too many projectiles in flight.".to_string());
+        }
+
         let current_time = ctx.timestamp;
-        let expires_at = Timestamp::from_micros_since_unix_epoch(
-            current_time.to_micros_since_unix_epoch() + 60_000_000 // 60 seconds
-        );
+        let expires_at = spell_logic::projectile_expires_at(current_time, spell.lifetime_secs);
         
-        // Create homing sphere - if target found, target them; otherwise create a projectile that moves forward
-        if let Some(target) = nearest_player {
-            let projectile = ProjectileData {
-                id: 0, // auto_inc will set this
-                caster_identity,
-                position: caster.position.clone(),
-                target_identity: target.identity,
-                speed: 15.0, // units per second
-                created_at: current_time,
-                expires_at,
-                projectile_type: "homing_sphere".to_string(),
-            };
-            
-            ctx.db.projectile().insert(projectile);
-            spacetimedb::log::info!("Created homing sphere targeting player {}", target.identity);
+        // Create homing sphere - if target found, target them; otherwise target the caster.
+        // For single-player testing, targeting the caster keeps the projectile visible
+        let target_identity = match &nearest_player {
+            Some(target) => target.identity,
+            None => caster_identity,
+        };
+        let projectile = ProjectileData {
+            id: 0, // auto_inc will set this
+            caster_identity,
+            position: projectile_logic::projectile_spawn_position(&caster),
+            target_identity,
+            speed: spell_logic::clamp_projectile_speed(spell.speed),
+            created_at: current_time,
+            last_moved_at: current_time,
+            expires_at,
+            projectile_type: spell.projectile_type.clone(),
+            collision_mask,
+            direction: player_logic::facing_direction(&caster.rotation),
+            piercing: spell.piercing,
+            max_pierce: spell.max_pierce,
+            hit_identities: Vec::new(),
+            hit_counted: false,
+            damage: spell.damage,
+            status_effect: spell.status_effect.clone(),
+            caster_team: caster.team,
+            caster_color: caster.color.clone(),
+            fragment_count: spell.fragment_count,
+            arcing: false,
+            vertical_velocity: 0.0,
+            aoe_radius: spell_logic::clamp_aoe_radius(spell.aoe_radius),
+            homing_strength: spell_logic::clamp_homing_strength(spell.homing_strength),
+            gravity: spell_logic::clamp_projectile_gravity(spell.projectile_gravity),
+            on_expire: spell_logic::normalize_on_expire(&spell.on_expire),
+            rewind_micros: lag_compensation_logic::rewind_micros_for(ctx, &caster),
+            lifetime_fraction: 1.0,
+        };
+        
+        // A volley fans around the facing direction and flies straight. Only piercing projectiles
+        // fly straight (homing ones chase target_identity), so every pellet is made piercing,
+        // stopping at its first hit unless the spell already pierces further
+        let volley: Vec<ProjectileData> = if projectile_count > 1 {
+            let spread_angle = spell_logic::clamp_spread_angle(spell.spread_angle);
+            projectile_logic::fan_directions(&projectile.direction, projectile_count, spread_angle)
+                .into_iter()
+                .map(|direction| ProjectileData {
+                    target_identity: caster_identity,
+                    direction,
+                    homing_strength: 0.0,
+                    piercing: true,
+                    max_pierce: if spell.piercing { spell.max_pierce.max(1) } else { 1 },
+                    ..projectile.clone()
+                })
+                .collect()
+        } else {
+            vec![projectile]
+        };
+        let mut fired = 0;
+        for projectile in volley {
+            if let Err(e) = ctx.db.projectile().try_insert(projectile) {
+                spacetimedb::log::error!("Failed to create projectile for {}: {}", caster_identity, e);
+                break;
+            }
+            fired += 1;
+        }
+        if fired == 0 {
+            return Err("Failed to create projectile.".to_string());
+        }
+        let mut caster = caster;
+        // Every projectile is a shot, so accuracy stays within 0..1 when several of a volley hit
+        caster.shots_fired += fired;
+        ctx.db.player().identity().update(caster);
+        if nearest_player.is_some() {
+            spacetimedb::log::info!("Created homing sphere targeting player {}", target_identity);
         } else {
-            // No other players found - create a projectile that targets a position in front of the caster
-            // For single-player testing, we'll target the caster themselves so the projectile is visible
-            let projectile = ProjectileData {
-                id: 0, // auto_inc will set this
-                caster_identity,
-                position: caster.position.clone(),
-                target_identity: caster_identity, // Target self for single-player testing
-                speed: 15.0, // units per second
-                created_at: current_time,
-                expires_at,
-                projectile_type: "homing_sphere".to_string(),
-            };
-            
-            ctx.db.projectile().insert(projectile);
             spacetimedb::log::info!("Created homing sphere targeting self (single-player mode)");
         }
+        Ok(())
     } else {
         spacetimedb::log::warn!("Player {} tried to cast spell but is not active.", caster_identity);
+        Err("Only active players can cast spells.".to_string())
     }
 }
 
-// Helper function to calculate distance between two points
-fn calculate_distance(pos1: &Vector3, pos2: &Vector3) -> f32 {
-    let dx = pos1.x - pos2.x;
-    let dy = pos1.y - pos2.y;
-    let dz = pos1.z - pos2.z;
-    (dx * dx + dy * dy + dz * dz).sqrt()
+#[spacetimedb::reducer]
+pub fn start_channel(ctx: &ReducerContext, spell_name: String) -> Result<(), String> {
+    channel_logic::start_channel(ctx, &spell_name)
 }
 
-#[spacetimedb::reducer(update)]
-pub fn game_tick(ctx: &ReducerContext, _tick_info: GameTickSchedule) {
-    // Just use a simple log message without timestamp conversion
-    let delta_time = 1.0; // Fixed 1-second tick for simplicity
-    
-    player_logic::update_players_logic(ctx, delta_time);
-    
-    // Update projectiles
-    update_projectiles(ctx, delta_time);
-    
-    spacetimedb::log::debug!("Game tick completed");
+#[spacetimedb::reducer]
+pub fn stop_channel(ctx: &ReducerContext) -> Result<(), String> {
+    channel_logic::stop_channel(ctx)
 }
 
-// Update all projectiles - move them toward targets and handle expiration
-fn update_projectiles(ctx: &ReducerContext, delta_time: f64) {
-    let current_time = ctx.timestamp;
-    let mut projectiles_to_delete = Vec::new();
-    
-    for projectile in ctx.db.projectile().iter() {
-        // Debug: Log projectile lifetime info
-        let time_alive = (current_time.to_micros_since_unix_epoch() - projectile.created_at.to_micros_since_unix_epoch()) as f64 / 1_000_000.0;
-        let time_remaining = (projectile.expires_at.to_micros_since_unix_epoch() - current_time.to_micros_since_unix_epoch()) as f64 / 1_000_000.0;
-        
-        spacetimedb::log::info!(
-            "🚀 Projectile {} - Alive: {:.1}s, Remaining: {:.1}s", 
-            projectile.id, 
-            time_alive, 
-            time_remaining
-        );
-        
-        // Check if projectile has expired
-        if current_time.to_micros_since_unix_epoch() >= projectile.expires_at.to_micros_since_unix_epoch() {
-            projectiles_to_delete.push(projectile.id);
-            spacetimedb::log::info!("⏰ Projectile {} EXPIRED after {:.1}s", projectile.id, time_alive);
-            continue;
+#[spacetimedb::reducer]
+pub fn throw_projectile(ctx: &ReducerContext, target: Vector3) -> Result<(), String> {
+    projectile_logic::throw_grenade(ctx, target)
+}
+
+// --- Spectator Reducers ---
+
+#[spacetimedb::reducer]
+pub fn start_spectating(ctx: &ReducerContext) -> Result<(), String> {
+    if ban_logic::is_banned(ctx, ctx.sender) {
+        return Err("You are banned from this server.".to_string());
+    }
+    if ctx.db.player().identity().find(ctx.sender).is_some() {
+        return Err("Active players cannot spectate.".to_string());
+    }
+    if ctx.db.spectator().identity().find(ctx.sender).is_none() {
+        ctx.db.spectator().insert(Spectator {
+            identity: ctx.sender,
+            spectate_target: None,
+            joined_at: ctx.timestamp,
+        });
+        spacetimedb::log::info!("{} started spectating.", ctx.sender);
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn stop_spectating(ctx: &ReducerContext) {
+    ctx.db.spectator().identity().delete(ctx.sender);
+}
+
+// Pass None to stop following anyone
+#[spacetimedb::reducer]
+pub fn set_spectate_target(ctx: &ReducerContext, target_identity: Option<Identity>) -> Result<(), String> {
+    let Some(mut spectator) = ctx.db.spectator().identity().find(ctx.sender) else {
+        return Err("Only spectators can set a spectate target.".to_string());
+    };
+    if let Some(target) = target_identity {
+        if ctx.db.player().identity().find(target).is_none() {
+            return Err(format!("Player {} is not active.", target));
         }
-        
-        // Find the target player
-        if let Some(target) = ctx.db.player().identity().find(projectile.target_identity) {
-            // Calculate direction to target
-            let direction = Vector3 {
-                x: target.position.x - projectile.position.x,
-                y: target.position.y - projectile.position.y,
-                z: target.position.z - projectile.position.z,
-            };
-            
-            // Calculate distance to target
-            let distance = calculate_distance(&projectile.position, &target.position);
-            
-            // Check if projectile reached target (within 1 unit)
-            if distance <= 1.0 {
-                projectiles_to_delete.push(projectile.id);
-                spacetimedb::log::info!("🎯 Projectile {} HIT target {} at distance {:.2}", projectile.id, target.identity, distance);
-                
-                // Apply 10hp damage to target (prevent self-damage)
-                if target.identity != projectile.caster_identity {
-                    let new_health = (target.health - 10).max(0);
-                    let mut updated_target = target.clone();
-                    updated_target.health = new_health;
-                    ctx.db.player().identity().update(updated_target);
-                    
-                    spacetimedb::log::info!(
-                        "Projectile {} dealt 10 damage to player {} (health: {} -> {})", 
-                        projectile.id, 
-                        target.identity, 
-                        target.health, 
-                        new_health
-                    );
-                } else {
-                    spacetimedb::log::info!("Projectile {} hit caster {} - no self-damage", projectile.id, target.identity);
-                }
-                
-                continue;
-            }
-            
-            // Normalize direction vector
-            let magnitude = (direction.x * direction.x + direction.y * direction.y + direction.z * direction.z).sqrt();
-            if magnitude > 0.01 {
-                let normalized_direction = Vector3 {
-                    x: direction.x / magnitude,
-                    y: direction.y / magnitude,
-                    z: direction.z / magnitude,
-                };
-                
-                // Move projectile toward target
-                let movement_distance = projectile.speed * delta_time as f32;
-                let new_position = Vector3 {
-                    x: projectile.position.x + normalized_direction.x * movement_distance,
-                    y: projectile.position.y + normalized_direction.y * movement_distance,
-                    z: projectile.position.z + normalized_direction.z * movement_distance,
-                };
-                
-                // Update projectile position
-                let mut updated_projectile = projectile.clone();
-                updated_projectile.position = new_position;
-                ctx.db.projectile().id().update(updated_projectile);
-            }
-        } else {
-            // Target player no longer exists, remove projectile
-            projectiles_to_delete.push(projectile.id);
-            spacetimedb::log::info!("👻 Projectile {} TARGET NO LONGER EXISTS (target_identity: {})", projectile.id, projectile.target_identity);
+    }
+
+    spectator.spectate_target = target_identity;
+    ctx.db.spectator().identity().update(spectator);
+    Ok(())
+}
+
+// --- Admin Reducers ---
+
+#[spacetimedb::reducer]
+pub fn set_movement_config(
+    ctx: &ReducerContext,
+    player_speed: f32,
+    sprint_multiplier: f32,
+    gravity: f32,
+    jump_force: f32,
+) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+    config_logic::validate_movement_config(player_speed, sprint_multiplier, gravity, jump_force)?;
+
+    let mut config = config_logic::get_config(ctx);
+    config.player_speed = player_speed;
+    config.sprint_multiplier = sprint_multiplier;
+    config.gravity = gravity;
+    config.jump_force = jump_force;
+    config_logic::save_config(ctx, config);
+
+    spacetimedb::log::info!("Admin {} updated movement config.", ctx.sender);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_spawn_protection_radius(ctx: &ReducerContext, radius: f32) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+    if !radius.is_finite() || radius < 0.0 {
+        return Err("Spawn protection radius must be zero or positive.".to_string());
+    }
+
+    let mut config = config_logic::get_config(ctx);
+    config.spawn_protection_radius = radius;
+    config_logic::save_config(ctx, config);
+
+    spacetimedb::log::info!("Admin {} set spawn protection radius to {}.", ctx.sender, radius);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_out_of_bounds_mode(ctx: &ReducerContext, mode: String) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+    let mode = mode.trim().to_lowercase();
+    if !player_logic::is_valid_out_of_bounds_mode(&mode) {
+        return Err(format!("Unknown out-of-bounds mode '{}'.", mode));
+    }
+
+    let mut config = config_logic::get_config(ctx);
+    config.out_of_bounds_mode = mode.clone();
+    config_logic::save_config(ctx, config);
+
+    spacetimedb::log::info!("Admin {} set out-of-bounds mode to {}.", ctx.sender, mode);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_max_players(ctx: &ReducerContext, max_players: u32) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+    if max_players == 0 {
+        return Err("Max players must be at least 1.".to_string());
+    }
+
+    let mut config = config_logic::get_config(ctx);
+    config.max_players = max_players;
+    config_logic::save_config(ctx, config);
+
+    spacetimedb::log::info!("Admin {} set max players to {}.", ctx.sender, max_players);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_max_turn_rate(ctx: &ReducerContext, max_turn_rate: f32) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+    if !max_turn_rate.is_finite() || max_turn_rate < 0.0 {
+        return Err("Max turn rate must be zero (unlimited) or positive.".to_string());
+    }
+
+    let mut config = config_logic::get_config(ctx);
+    config.max_turn_rate = max_turn_rate;
+    config_logic::save_config(ctx, config);
+
+    spacetimedb::log::info!("Admin {} set max turn rate to {}.", ctx.sender, max_turn_rate);
+    Ok(())
+}
+
+// Map a logical animation state to a class-specific animation name. An empty
+// animation removes the override so the class uses the state name again.
+#[spacetimedb::reducer]
+pub fn set_class_animation(ctx: &ReducerContext, character_class: String, state: String, animation: String) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change class animations.".to_string());
+    }
+    let character_class = class_logic::normalize_class(&character_class);
+    let state = state.trim().to_lowercase();
+    let animation = animation.trim().to_string();
+    if character_class.is_empty() {
+        return Err("Class cannot be empty.".to_string());
+    }
+    if !player_logic::is_known_animation(&state) {
+        return Err(format!("'{}' is not a known animation state.", state));
+    }
+
+    let existing = ctx.db.class_animation().iter()
+        .find(|row| row.character_class == character_class && row.state == state);
+    match (existing, animation.is_empty()) {
+        (Some(row), true) => {
+            ctx.db.class_animation().id().delete(row.id);
+        }
+        (Some(mut row), false) => {
+            row.animation = animation.clone();
+            ctx.db.class_animation().id().update(row);
+        }
+        (None, true) => {}
+        (None, false) => {
+            ctx.db.class_animation().try_insert(ClassAnimation {
+                id: 0, // auto_inc will set this
+                character_class: character_class.clone(),
+                state: state.clone(),
+                animation: animation.clone(),
+            }).map_err(|e| format!("Failed to set class animation: {}", e))?;
         }
     }
-    
-    // Clean up expired/hit projectiles
-    for projectile_id in projectiles_to_delete {
-        ctx.db.projectile().id().delete(projectile_id);
+
+    spacetimedb::log::info!("Admin {} set {} '{}' animation to '{}'.", ctx.sender, character_class, state, animation);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_damage_cooldown(ctx: &ReducerContext, damage_cooldown_micros: i64) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+    if damage_cooldown_micros < 0 {
+        return Err("Damage cooldown must be zero (off) or positive.".to_string());
+    }
+
+    let mut config = config_logic::get_config(ctx);
+    config.damage_cooldown_micros = damage_cooldown_micros;
+    config_logic::save_config(ctx, config);
+
+    spacetimedb::log::info!("Admin {} set damage cooldown to {}us.", ctx.sender, damage_cooldown_micros);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_lag_compensation(ctx: &ReducerContext, max_micros: i64) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+    if !(0..=LAG_COMPENSATION_HISTORY_MICROS).contains(&max_micros) {
+        return Err(format!("Lag compensation must be between 0 (off) and {}us.", LAG_COMPENSATION_HISTORY_MICROS));
+    }
+
+    let mut config = config_logic::get_config(ctx);
+    config.lag_compensation_max_micros = max_micros;
+    config_logic::save_config(ctx, config);
+
+    spacetimedb::log::info!("Admin {} set lag compensation to at most {}us.", ctx.sender, max_micros);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_afk_timeout(ctx: &ReducerContext, afk_timeout_micros: i64) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+    if afk_timeout_micros < 0 {
+        return Err("AFK timeout must be zero (off) or positive.".to_string());
+    }
+
+    let mut config = config_logic::get_config(ctx);
+    config.afk_timeout_micros = afk_timeout_micros;
+    config_logic::save_config(ctx, config);
+
+    spacetimedb::log::info!("Admin {} set AFK timeout to {}us.", ctx.sender, afk_timeout_micros);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_level_damage_scaling(ctx: &ReducerContext, level_damage_scaling: f32) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+    if !level_damage_scaling.is_finite() || !(0.0..=1.0).contains(&level_damage_scaling) {
+        return Err("Level damage scaling must be between 0 (off) and 1.".to_string());
+    }
+
+    let mut config = config_logic::get_config(ctx);
+    config.level_damage_scaling = level_damage_scaling;
+    config_logic::save_config(ctx, config);
+
+    spacetimedb::log::info!("Admin {} set level damage scaling to {}.", ctx.sender, level_damage_scaling);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_min_health(ctx: &ReducerContext, min_health: i32) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+    if min_health < 0 {
+        return Err("Minimum health must be zero (off) or positive.".to_string());
+    }
+
+    let mut config = config_logic::get_config(ctx);
+    config.min_health = min_health;
+    config_logic::save_config(ctx, config);
+
+    spacetimedb::log::info!("Admin {} set minimum health to {}.", ctx.sender, min_health);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn spawn_training_dummy(ctx: &ReducerContext, position: Vector3, max_health: i32) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can place training dummies.".to_string());
+    }
+    if max_health <= 0 {
+        return Err("Training dummy health must be positive.".to_string());
+    }
+    let dummy = ctx.db.training_dummy().try_insert(TrainingDummy {
+        id: 0, // auto_inc will set this
+        position,
+        health: max_health,
+        max_health,
+        last_hit_at: ctx.timestamp,
+    }).map_err(|e| format!("Failed to create training dummy: {}", e))?;
+
+    spacetimedb::log::info!("Admin {} placed training dummy {}.", ctx.sender, dummy.id);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_training_dummy(ctx: &ReducerContext, dummy_id: u64) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can remove training dummies.".to_string());
+    }
+    if !ctx.db.training_dummy().id().delete(dummy_id) {
+        return Err(format!("Training dummy {} does not exist.", dummy_id));
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn place_jump_pad(ctx: &ReducerContext, position: Vector3, radius: f32, boost: f32, duration_secs: u32) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can place jump pads.".to_string());
+    }
+    if !radius.is_finite() || radius <= 0.0 {
+        return Err("Jump pad radius must be positive.".to_string());
+    }
+    if !boost.is_finite() || boost <= 0.0 || boost > MAX_JUMP_PAD_BOOST {
+        return Err(format!("Jump pad boost must be between 0 and {}.", MAX_JUMP_PAD_BOOST));
+    }
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let expires_at = (duration_secs > 0)
+        .then(|| Timestamp::from_micros_since_unix_epoch(now_micros + duration_secs as i64 * 1_000_000));
+    let pad = ctx.db.jump_pad().try_insert(JumpPad {
+        id: 0, // auto_inc will set this
+        position,
+        radius,
+        boost,
+        armed_at: Timestamp::from_micros_since_unix_epoch(now_micros + JUMP_PAD_ARM_DELAY_MICROS),
+        expires_at,
+    }).map_err(|e| format!("Failed to create jump pad: {}", e))?;
+
+    spacetimedb::log::info!("Admin {} placed jump pad {}.", ctx.sender, pad.id);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_jump_pad(ctx: &ReducerContext, pad_id: u64) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can remove jump pads.".to_string());
+    }
+    if !ctx.db.jump_pad().id().delete(pad_id) {
+        return Err("Jump pad not found.".to_string());
+    }
+    spacetimedb::log::info!("Admin {} removed jump pad {}.", ctx.sender, pad_id);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn place_speed_pickup(ctx: &ReducerContext, position: Vector3) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can place pickups.".to_string());
+    }
+    loot_logic::place_speed_pickup(ctx, &position)?;
+    spacetimedb::log::info!("Admin {} placed a speed pickup at ({}, {}, {}).", ctx.sender, position.x, position.y, position.z);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn place_flag(ctx: &ReducerContext, team: u32, base_position: Vector3) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can place flags.".to_string());
+    }
+    let team_count = config_logic::get_config(ctx).team_count;
+    if team >= team_count {
+        return Err(format!("Team must be below the team count ({}).", team_count));
+    }
+    let flag = flag_logic::place_flag(ctx, team, base_position)?;
+
+    spacetimedb::log::info!("Admin {} placed flag {} for team {}.", ctx.sender, flag.id, team);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_flag(ctx: &ReducerContext, flag_id: u64) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can remove flags.".to_string());
+    }
+    if !ctx.db.flag().id().delete(flag_id) {
+        return Err("Flag not found.".to_string());
+    }
+    spacetimedb::log::info!("Admin {} removed flag {}.", ctx.sender, flag_id);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_team_count(ctx: &ReducerContext, team_count: u32) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+    if team_count == 0 || team_count > MAX_TEAM_COUNT {
+        return Err(format!("Team count must be between 1 and {}.", MAX_TEAM_COUNT));
+    }
+
+    let mut config = config_logic::get_config(ctx);
+    config.team_count = team_count;
+    config_logic::save_config(ctx, config);
+    team_logic::rebalance_teams(ctx, team_count);
+
+    spacetimedb::log::info!("Admin {} set team count to {}.", ctx.sender, team_count);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_damage_model(ctx: &ReducerContext, damage_model: String) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+    let damage_model = damage_model.trim().to_lowercase();
+    if !combat_logic::is_valid_damage_model(&damage_model) {
+        return Err(format!("Unknown damage model '{}'.", damage_model));
+    }
+
+    let mut config = config_logic::get_config(ctx);
+    config.damage_model = damage_model.clone();
+    config_logic::save_config(ctx, config);
+
+    spacetimedb::log::info!("Admin {} set damage model to {}.", ctx.sender, damage_model);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_self_aoe_damage(ctx: &ReducerContext, enabled: bool) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+
+    let mut config = config_logic::get_config(ctx);
+    config.self_aoe_damage = enabled;
+    config_logic::save_config(ctx, config);
+
+    spacetimedb::log::info!("Admin {} set self AoE damage to {}.", ctx.sender, enabled);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_tick_entity_budget(ctx: &ReducerContext, tick_entity_budget: u64) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+
+    let mut config = config_logic::get_config(ctx);
+    config.tick_entity_budget = tick_entity_budget;
+    config_logic::save_config(ctx, config);
+
+    spacetimedb::log::info!("Admin {} set the tick entity budget to {}.", ctx.sender, tick_entity_budget);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_spawn_privacy(ctx: &ReducerContext, spawn_privacy_micros: i64) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+    if !(0..=MAX_SPAWN_PRIVACY_MICROS).contains(&spawn_privacy_micros) {
+        return Err(format!("Spawn privacy must be between 0 (off) and {}us.", MAX_SPAWN_PRIVACY_MICROS));
+    }
+
+    let mut config = config_logic::get_config(ctx);
+    config.spawn_privacy_micros = spawn_privacy_micros;
+    config_logic::save_config(ctx, config);
+
+    spacetimedb::log::info!("Admin {} set spawn privacy to {}us.", ctx.sender, spawn_privacy_micros);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_cull_dead_caster_projectiles(ctx: &ReducerContext, enabled: bool) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+
+    let mut config = config_logic::get_config(ctx);
+    config.cull_dead_caster_projectiles = enabled;
+    config_logic::save_config(ctx, config);
+
+    spacetimedb::log::info!("Admin {} set culling of dead casters' projectiles to {}.", ctx.sender, enabled);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn graceful_shutdown(ctx: &ReducerContext) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can shut the server down.".to_string());
+    }
+    shutdown_logic::graceful_shutdown(ctx);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn reopen_server(ctx: &ReducerContext) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can reopen the server.".to_string());
+    }
+    shutdown_logic::reopen_server(ctx);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn spawn_bot(ctx: &ReducerContext, character_class: String) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can spawn bots.".to_string());
+    }
+    let identity = bot_logic::spawn_bot(ctx, character_class)?;
+    spacetimedb::log::info!("Admin {} spawned bot {}.", ctx.sender, identity);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_bot(ctx: &ReducerContext, identity: Identity) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can remove bots.".to_string());
+    }
+    bot_logic::remove_bot(ctx, identity)?;
+    spacetimedb::log::info!("Admin {} removed bot {}.", ctx.sender, identity);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn ban_player(ctx: &ReducerContext, identity: Identity, reason: String) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can ban players.".to_string());
+    }
+    ban_logic::ban_player(ctx, identity, reason)?;
+    spacetimedb::log::info!("Admin {} banned {}.", ctx.sender, identity);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn unban_player(ctx: &ReducerContext, identity: Identity) -> Result<(), String> {
+    if !is_admin(ctx) {
+        return Err("Only admins can unban players.".to_string());
+    }
+    ban_logic::unban_player(ctx, identity)?;
+    spacetimedb::log::info!("Admin {} unbanned {}.", ctx.sender, identity);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn broadcast_announcement(ctx: &ReducerContext, text: String, duration_secs: u32) -> Result<(), String> {
+    let (text, duration_secs) = validate_announcement(is_admin(ctx), &text, duration_secs)?;
+    let expires_at = Timestamp::from_micros_since_unix_epoch(
+        ctx.timestamp.to_micros_since_unix_epoch() + duration_secs * 1_000_000
+    );
+    ctx.db.announcement().try_insert(Announcement {
+        id: 0, // auto_inc will set this
+        text,
+        sender: ctx.sender,
+        created_at: ctx.timestamp,
+        expires_at,
+    }).map_err(|e| format!("Failed to store announcement: {}", e))?;
+
+    spacetimedb::log::info!("Admin {} broadcast an announcement for {}s.", ctx.sender, duration_secs);
+    Ok(())
+}
+
+// Helper function to check whether another active or logged-out player already uses a name
+fn is_username_taken(ctx: &ReducerContext, username: &str, requester: Identity) -> bool {
+    let active = ctx.db.player().iter()
+        .any(|p| name_blocks(p.identity, &p.username, username, requester));
+    let logged_out = ctx.db.logged_out_player().iter()
+        .any(|p| name_blocks(p.identity, &p.username, username, requester));
+    active || logged_out
+}
+
+// Helper function to check whether `owner`'s name keeps `requester` from using `username`.
+// Names match case-insensitively, and a player's own name never blocks them.
+fn name_blocks(owner: Identity, owner_name: &str, username: &str, requester: Identity) -> bool {
+    owner != requester && owner_name.eq_ignore_ascii_case(username)
+}
+
+// Helper function to validate a (lowercased) color: a palette name or "#rrggbb"
+fn is_valid_color(color: &str) -> bool {
+    if PLAYER_COLORS.contains(&color) {
+        return true;
+    }
+    color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+// Helper function to trim a title and check its length and characters
+fn validate_title(title: &str) -> Result<String, String> {
+    let title = title.trim().to_string();
+    if title.chars().count() > MAX_TITLE_LENGTH {
+        return Err(format!("Title cannot be longer than {} characters.", MAX_TITLE_LENGTH));
+    }
+    if title.chars().any(char::is_control) {
+        return Err("Title cannot contain control characters.".to_string());
+    }
+    Ok(title)
+}
+
+// Helper function to pick the first palette color no active player uses,
+// cycling by player count once every palette color is taken
+fn pick_free_color(ctx: &ReducerContext, player_count: usize) -> String {
+    let used: Vec<String> = ctx.db.player().iter().map(|p| p.color.to_lowercase()).collect();
+    first_free_color(&used, player_count)
+}
+
+// Helper function to pick a palette color given the colors already in use
+fn first_free_color(used: &[String], player_count: usize) -> String {
+    PLAYER_COLORS.iter()
+        .find(|color| !used.iter().any(|u| u == *color))
+        .unwrap_or(&PLAYER_COLORS[player_count % PLAYER_COLORS.len()])
+        .to_string()
+}
+
+// Helper function to check whether a reconnect is soon enough to restore the player automatically
+fn within_rejoin_window(last_seen: Timestamp, now: Timestamp) -> bool {
+    now.to_micros_since_unix_epoch() - last_seen.to_micros_since_unix_epoch() <= AUTO_REJOIN_WINDOW_MICROS
+}
+
+// Helper function to check whether an identity may join at all: banned identities never
+// can, and nobody can while the server is shut down
+fn check_can_join(banned: bool, accepting_joins: bool) -> Result<(), String> {
+    if banned {
+        return Err("You are banned from this server.".to_string());
+    }
+    if !accepting_joins {
+        return Err("The server is shutting down and not accepting new players.".to_string());
+    }
+    Ok(())
+}
+
+// Helper function to check whether another player can join
+fn is_server_full(player_count: usize, max_players: u32) -> bool {
+    player_count >= max_players as usize
+}
+
+// Helper function to check an announcement from `sender_is_admin`, returning the trimmed
+// text and the duration clamped to 1..=MAX_ANNOUNCEMENT_DURATION_SECS
+fn validate_announcement(sender_is_admin: bool, text: &str, duration_secs: u32) -> Result<(String, i64), String> {
+    if !sender_is_admin {
+        return Err("Only admins can broadcast announcements.".to_string());
+    }
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Err("Announcement text cannot be empty.".to_string());
+    }
+    if text.chars().count() > MAX_ANNOUNCEMENT_LENGTH {
+        return Err(format!("Announcement text cannot exceed {} characters.", MAX_ANNOUNCEMENT_LENGTH));
+    }
+    Ok((text, duration_secs.clamp(1, MAX_ANNOUNCEMENT_DURATION_SECS) as i64))
+}
+
+// Helper function to check whether the caller is an admin
+fn is_admin(ctx: &ReducerContext) -> bool {
+    ctx.db.admin().identity().find(ctx.sender).is_some()
+}
+
+#[spacetimedb::reducer]
+pub fn cast_gravity_well(ctx: &ReducerContext, center: Vector3) -> Result<(), String> {
+    let Some(caster) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can cast a gravity well.".to_string());
+    };
+    if caster.health <= 0 {
+        return Err("Dead players cannot cast a gravity well.".to_string());
+    }
+    if calculate_distance(&caster.position, &center) > GRAVITY_WELL_CAST_RANGE {
+        return Err("Gravity well target is out of range.".to_string());
+    }
+
+    let expires_at = Timestamp::from_micros_since_unix_epoch(
+        ctx.timestamp.to_micros_since_unix_epoch() + GRAVITY_WELL_DURATION_MICROS
+    );
+    ctx.db.gravity_well().try_insert(GravityWell {
+        id: 0, // auto_inc will set this
+        owner_identity: caster.identity,
+        owner_team: caster.team,
+        center,
+        radius: GRAVITY_WELL_RADIUS,
+        strength: GRAVITY_WELL_STRENGTH,
+        expires_at,
+    }).map_err(|e| format!("Failed to create gravity well: {}", e))?;
+
+    spacetimedb::log::info!("Player {} cast a gravity well.", caster.identity);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn create_portal(ctx: &ReducerContext, position_a: Vector3, position_b: Vector3) -> Result<(), String> {
+    let Some(caster) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can create a portal.".to_string());
+    };
+    if caster.health <= 0 {
+        return Err("Dead players cannot create a portal.".to_string());
+    }
+    for position in [&position_a, &position_b] {
+        if calculate_distance(&caster.position, position) > PORTAL_CAST_RANGE {
+            return Err("Portal end is out of range.".to_string());
+        }
+        if player_logic::is_out_of_bounds(position) {
+            return Err("Portal end is out of bounds.".to_string());
+        }
+    }
+    if calculate_distance(&position_a, &position_b) <= PORTAL_RADIUS * 2.0 {
+        return Err("Portal ends are too close together.".to_string());
+    }
+
+    // One portal per player, a new one replaces the old
+    let previous: Vec<u64> = ctx.db.portal().iter()
+        .filter(|portal| portal.owner_identity == caster.identity)
+        .map(|portal| portal.id)
+        .collect();
+    for id in previous {
+        ctx.db.portal().id().delete(id);
+    }
+
+    let expires_at = Timestamp::from_micros_since_unix_epoch(
+        ctx.timestamp.to_micros_since_unix_epoch() + PORTAL_DURATION_MICROS
+    );
+    ctx.db.portal().try_insert(Portal {
+        id: 0, // auto_inc will set this
+        owner_identity: caster.identity,
+        position_a,
+        position_b,
+        radius: PORTAL_RADIUS,
+        expires_at,
+    }).map_err(|e| format!("Failed to create portal: {}", e))?;
+
+    spacetimedb::log::info!("Player {} created a portal.", caster.identity);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn place_totem(ctx: &ReducerContext, position: Vector3) -> Result<(), String> {
+    totem_logic::place_totem(ctx, position)
+}
+
+#[spacetimedb::reducer]
+pub fn place_mine(ctx: &ReducerContext, position: Vector3) -> Result<(), String> {
+    mine_logic::place_mine(ctx, position)
+}
+
+#[spacetimedb::reducer]
+pub fn request_duel(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    duel_logic::request_duel(ctx, target)
+}
+
+#[spacetimedb::reducer]
+pub fn accept_duel(ctx: &ReducerContext, challenger: Identity) -> Result<(), String> {
+    duel_logic::accept_duel(ctx, challenger)
+}
+
+#[spacetimedb::reducer]
+pub fn start_votekick(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    votekick_logic::start_votekick(ctx, target)
+}
+
+#[spacetimedb::reducer]
+pub fn cast_votekick_vote(ctx: &ReducerContext, vote_id: u64, yes: bool) -> Result<(), String> {
+    votekick_logic::cast_votekick_vote(ctx, vote_id, yes)
+}
+
+// Helper function to calculate distance between two points
+fn calculate_distance(pos1: &Vector3, pos2: &Vector3) -> f32 {
+    let dx = pos1.x - pos2.x;
+    let dy = pos1.y - pos2.y;
+    let dz = pos1.z - pos2.z;
+    (dx * dx + dy * dy + dz * dz).sqrt()
+}
+
+#[spacetimedb::reducer(update)]
+pub fn game_tick(ctx: &ReducerContext, _tick_info: GameTickSchedule) -> Result<(), String> {
+    // Each call applies a full tick of burns, heals, regen and beams, so clients must not drive it
+    if ctx.sender != ctx.identity() {
+        return Err("game_tick may only be invoked by the scheduler.".to_string());
+    }
+    // Just use a simple log message without timestamp conversion
+    let delta_time = GAME_TICK_MICROS as f64 / 1_000_000.0; // Fixed tick length for simplicity
+    // Under heavy load, low-priority systems (marked below) sit out this tick
+    let shed_low_priority = tick_budget_logic::should_shed_low_priority(ctx, tick_budget_logic::tick_load(ctx));
+    
+    player_logic::update_players_logic(ctx, delta_time);
+
+    // Projectiles are updated separately in projectile_tick
+
+    // Damage-over-time and other timed effects
+    status_logic::update_status_effects(ctx);
+    totem_logic::update_totems(ctx);
+    ground_effect_logic::update_ground_effects(ctx);
+    mine_logic::update_mines(ctx);
+    duel_logic::update_duels(ctx);
+    reveal_logic::update_reveals(ctx);
+    loot_logic::update_loot_drops(ctx);
+    flag_logic::update_flags(ctx);
+    bot_logic::update_bots(ctx);
+    regenerate_training_dummies(ctx);
+    channel_logic::update_channels(ctx);
+
+    // Drop follow-cam targets that are no longer active
+    spectator_logic::clear_stale_spectate_targets(ctx);
+
+    // Low priority
+    if !shed_low_priority {
+        voice_logic::update_voice_proximity(ctx);
+        look_target_logic::refresh_look_targets(ctx);
+    }
+    player_view_logic::refresh_player_views(ctx);
+
+    // Low priority
+    if !shed_low_priority {
+        autosave_logic::autosave_players(ctx);
+        high_score_logic::update_high_scores(ctx);
+    }
+    // Before admission, so a kick frees its slot this tick
+    votekick_logic::update_votekicks(ctx);
+    join_queue_logic::admit_queued_players(ctx);
+
+    // Low priority
+    if !shed_low_priority {
+        prune_expired_announcements(ctx);
+        prune_old_username_checks(ctx);
+        projectile_logic::prune_impact_effects(ctx);
+    }
+    
+    spacetimedb::log::debug!("Game tick completed");
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn projectile_tick(ctx: &ReducerContext, _tick_info: ProjectileTickSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("projectile_tick may only be invoked by the scheduler.".to_string());
+    }
+    // Jumps last well under a second, so they're integrated on this schedule rather than game_tick
+    let delta_time = PROJECTILE_TICK_MICROS as f32 / 1_000_000.0;
+    player_logic::update_vertical_physics(ctx, delta_time);
+    // Record where everyone is before projectiles move, so rewinds see this tick too
+    lag_compensation_logic::record_position_history(ctx);
+    // Projectiles measure their own elapsed time, so a late tick doesn't slow them down
+    projectile_logic::update_projectiles(ctx);
+    Ok(())
+}
+
+// Dummies left alone for DUMMY_REGEN_DELAY_MICROS go back to full health
+fn regenerate_training_dummies(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let idle: Vec<TrainingDummy> = ctx.db.training_dummy().iter()
+        .filter(|dummy| dummy.health < dummy.max_health)
+        .filter(|dummy| now_micros - dummy.last_hit_at.to_micros_since_unix_epoch() >= DUMMY_REGEN_DELAY_MICROS)
+        .collect();
+    for mut dummy in idle {
+        dummy.health = dummy.max_health;
+        ctx.db.training_dummy().id().update(dummy);
+    }
+}
+
+// Remove announcements whose banner time is over
+fn prune_expired_announcements(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let expired: Vec<u64> = ctx.db.announcement().iter()
+        .filter(|announcement| now_micros >= announcement.expires_at.to_micros_since_unix_epoch())
+        .map(|announcement| announcement.id)
+        .collect();
+    for announcement_id in expired {
+        ctx.db.announcement().id().delete(announcement_id);
+    }
+}
+
+// Whether a check result made at `checked_at` has outlived USERNAME_CHECK_TTL_MICROS
+fn is_stale_username_check(checked_at: Timestamp, now_micros: i64) -> bool {
+    now_micros - checked_at.to_micros_since_unix_epoch() >= USERNAME_CHECK_TTL_MICROS
+}
+
+// Remove username check results once the client has had time to read them
+fn prune_old_username_checks(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let stale: Vec<Identity> = ctx.db.username_check().iter()
+        .filter(|check| is_stale_username_check(check.checked_at, now_micros))
+        .map(|check| check.identity)
+        .collect();
+    for identity in stale {
+        ctx.db.username_check().identity().delete(identity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at_micros, identity};
+
+    #[test]
+    fn colors_must_be_in_the_palette_or_hex() {
+        assert!(is_valid_color("cyan"));
+        assert!(is_valid_color("#1a2B3c"));
+        assert!(!is_valid_color("purple"));
+        assert!(!is_valid_color("#12345"));
+        assert!(!is_valid_color("#12345g"));
+        assert!(!is_valid_color("1234567"));
+    }
+
+    #[test]
+    fn new_players_get_the_first_unused_palette_color() {
+        let used = vec!["cyan".to_string(), "yellow".to_string()];
+        assert_eq!(first_free_color(&used, 2), "magenta");
+
+        let all: Vec<String> = PLAYER_COLORS.iter().map(|c| c.to_string()).collect();
+        assert_eq!(first_free_color(&all, 7), PLAYER_COLORS[1]);
+    }
+
+    #[test]
+    fn the_server_is_full_at_max_players() {
+        assert!(!is_server_full(0, 1));
+        assert!(!is_server_full(7, 8));
+        assert!(is_server_full(8, 8));
+        // Lowering the cap below the active count never kicks anyone, it just blocks joins
+        assert!(is_server_full(10, 8));
+    }
+
+    #[test]
+    fn only_recent_disconnects_are_restored_automatically() {
+        let last_seen = at_micros(1_000_000);
+        assert!(within_rejoin_window(last_seen, at_micros(1_000_000)));
+        assert!(within_rejoin_window(last_seen, at_micros(1_000_000 + AUTO_REJOIN_WINDOW_MICROS)));
+        assert!(!within_rejoin_window(last_seen, at_micros(1_000_001 + AUTO_REJOIN_WINDOW_MICROS)));
+    }
+
+    #[test]
+    fn titles_are_trimmed_and_checked() {
+        assert_eq!(validate_title("  Champion "), Ok("Champion".to_string()));
+        assert_eq!(validate_title(""), Ok(String::new()));
+        assert!(validate_title(&"x".repeat(MAX_TITLE_LENGTH)).is_ok());
+        assert!(validate_title(&"x".repeat(MAX_TITLE_LENGTH + 1)).is_err());
+        assert!(validate_title("bad\ttitle").is_err());
+    }
+
+    #[test]
+    fn admins_can_broadcast_trimmed_announcements() {
+        let (text, duration_secs) = validate_announcement(true, "  Restart in 5 minutes  ", 30).unwrap();
+        assert_eq!(text, "Restart in 5 minutes");
+        assert_eq!(duration_secs, 30);
+    }
+
+    #[test]
+    fn non_admins_cannot_broadcast() {
+        assert!(validate_announcement(false, "Restart in 5 minutes", 30).is_err());
+    }
+
+    #[test]
+    fn announcements_need_text_within_the_length_limit() {
+        assert!(validate_announcement(true, "   ", 30).is_err());
+        assert!(validate_announcement(true, &"a".repeat(MAX_ANNOUNCEMENT_LENGTH), 30).is_ok());
+        assert!(validate_announcement(true, &"a".repeat(MAX_ANNOUNCEMENT_LENGTH + 1), 30).is_err());
+    }
+
+    #[test]
+    fn announcement_duration_is_clamped() {
+        assert_eq!(validate_announcement(true, "hi", 0).unwrap().1, 1);
+        assert_eq!(validate_announcement(true, "hi", u32::MAX).unwrap().1, MAX_ANNOUNCEMENT_DURATION_SECS as i64);
+    }
+
+    #[test]
+    fn usernames_are_taken_case_insensitively_by_others() {
+        assert!(name_blocks(identity(1), "Merlin", "merlin", identity(2)));
+        assert!(!name_blocks(identity(1), "Merlin", "Morgana", identity(2)));
+        // Re-checking your own name reports it as available
+        assert!(!name_blocks(identity(1), "Merlin", "Merlin", identity(1)));
+    }
+
+    #[test]
+    fn username_checks_go_stale_after_the_ttl() {
+        let checked_at = at_micros(1_000_000);
+        assert!(!is_stale_username_check(checked_at, 1_000_000 + USERNAME_CHECK_TTL_MICROS - 1));
+        assert!(is_stale_username_check(checked_at, 1_000_000 + USERNAME_CHECK_TTL_MICROS));
+    }
+
+    #[test]
+    fn banned_identities_cannot_rejoin() {
+        assert!(check_can_join(false, true).is_ok());
+        assert_eq!(check_can_join(true, true), Err("You are banned from this server.".to_string()));
+        // The ban is reported even while the server is closed
+        assert_eq!(check_can_join(true, false), Err("You are banned from this server.".to_string()));
+        assert!(check_can_join(false, false).is_err());
     }
 }
@@ -0,0 +1,106 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - look_target_logic.rs
+ *
+ * This file maintains the LookTarget table that powers client target frames
+ * ("you are looking at PlayerX: 80/100 HP, level 5") without subscribing to every player.
+ *
+ * Key components:
+ *
+ * 1. Snapshots:
+ *    - snapshot_for: Builds a LookTarget row from the target's public stats
+ *    - set_look_target: Creates/replaces the caller's row (None removes it)
+ *
+ * 2. Game Tick:
+ *    - refresh_look_targets: Re-snapshots live targets, removes rows whose target left
+ *
+ * Related files:
+ *    - lib.rs: LookTarget table and set_look_target reducer
+ */
+
+use spacetimedb::{ReducerContext, Identity, Table};
+use crate::{LookTarget, PlayerData};
+// Import table traits
+use crate::{look_target, player};
+
+fn snapshot_for(ctx: &ReducerContext, observer: Identity, target: &PlayerData) -> LookTarget {
+    LookTarget {
+        observer,
+        target_identity: target.identity,
+        username: target.username.clone(),
+        character_class: target.character_class.clone(),
+        health: target.health,
+        max_health: target.max_health,
+        level: target.level,
+        updated_at: ctx.timestamp,
+    }
+}
+
+fn differs(a: &LookTarget, b: &LookTarget) -> bool {
+    a.target_identity != b.target_identity
+        || a.username != b.username
+        || a.character_class != b.character_class
+        || a.health != b.health
+        || a.max_health != b.max_health
+        || a.level != b.level
+}
+
+pub fn set_look_target(ctx: &ReducerContext, observer: Identity, target_identity: Option<Identity>) -> Result<(), String> {
+    let Some(target_identity) = target_identity else {
+        ctx.db.look_target().observer().delete(observer);
+        return Ok(());
+    };
+    let Some(target) = ctx.db.player().identity().find(target_identity) else {
+        return Err(format!("Player {} is not active.", target_identity));
+    };
+
+    let snapshot = snapshot_for(ctx, observer, &target);
+    if ctx.db.look_target().observer().find(observer).is_some() {
+        ctx.db.look_target().observer().update(snapshot);
+    } else {
+        ctx.db.look_target().insert(snapshot);
+    }
+    Ok(())
+}
+
+// Keep target frames current; only rows whose stats changed are written
+pub fn refresh_look_targets(ctx: &ReducerContext) {
+    for row in ctx.db.look_target().iter() {
+        match ctx.db.player().identity().find(row.target_identity) {
+            Some(target) => {
+                let snapshot = snapshot_for(ctx, row.observer, &target);
+                if differs(&row, &snapshot) {
+                    ctx.db.look_target().observer().update(snapshot);
+                }
+            }
+            None => {
+                ctx.db.look_target().observer().delete(row.observer);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at_micros, identity};
+
+    fn frame(health: i32, updated_at: i64) -> LookTarget {
+        LookTarget {
+            observer: identity(1),
+            target_identity: identity(2),
+            username: "target".to_string(),
+            character_class: "wizard".to_string(),
+            health,
+            max_health: 100,
+            level: 1,
+            updated_at: at_micros(updated_at),
+        }
+    }
+
+    #[test]
+    fn only_visible_changes_count_as_different() {
+        assert!(!differs(&frame(80, 0), &frame(80, 1_000_000)));
+        assert!(differs(&frame(80, 0), &frame(70, 0)));
+        assert!(differs(&frame(80, 0), &LookTarget { target_identity: identity(3), ..frame(80, 0) }));
+    }
+}
@@ -0,0 +1,125 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - loot_logic.rs
+ *
+ * This file contains loot dropped by dying players and admin-placed speed pickups.
+ *
+ * Key components:
+ *
+ * 1. Dropping:
+ *    - drop_loot: Called from combat_logic's death handling, leaves a LootDrop at
+ *      the death position that expires after LOOT_LIFETIME_MICROS
+ *    - place_speed_pickup: Called by the place_speed_pickup admin reducer, leaves a
+ *      LOOT_KIND_SPEED drop (no health, mana or score) with the same lifetime
+ *
+ * 2. Game Tick:
+ *    - update_loot_drops: Removes expired drops and gives each remaining drop to the
+ *      nearest living player within LOOT_PICKUP_RADIUS (health and mana are clamped
+ *      to their maxes, score is added). Speed pickups also haste the collector through
+ *      status_logic::apply_haste, which wears off like any other status effect
+ *
+ * Related files:
+ *    - combat_logic.rs: Death handling and the clamped heal helper
+ *    - lib.rs: LootDrop table definition
+ */
+
+use spacetimedb::{ReducerContext, Identity, Table, Timestamp};
+use crate::common::{Vector3, LOOT_HEALTH, LOOT_MANA, LOOT_SCORE, LOOT_LIFETIME_MICROS, LOOT_PICKUP_RADIUS};
+use crate::{calculate_distance, combat_logic, status_logic, LootDrop, PlayerData};
+// Import table traits
+use crate::{loot_drop, player};
+
+pub const LOOT_KIND_DEATH: &str = "death";
+pub const LOOT_KIND_SPEED: &str = "speed";
+
+pub fn drop_loot(ctx: &ReducerContext, position: &Vector3) {
+    if let Err(e) = ctx.db.loot_drop().try_insert(LootDrop {
+        id: 0, // auto_inc will set this
+        position: position.clone(),
+        kind: LOOT_KIND_DEATH.to_string(),
+        health: LOOT_HEALTH,
+        mana: LOOT_MANA,
+        score: LOOT_SCORE,
+        expires_at: loot_expires_at(ctx),
+    }) {
+        spacetimedb::log::error!("Failed to create loot drop: {}", e);
+    }
+}
+
+pub fn place_speed_pickup(ctx: &ReducerContext, position: &Vector3) -> Result<(), String> {
+    ctx.db.loot_drop().try_insert(LootDrop {
+        id: 0, // auto_inc will set this
+        position: position.clone(),
+        kind: LOOT_KIND_SPEED.to_string(),
+        health: 0,
+        mana: 0,
+        score: 0,
+        expires_at: loot_expires_at(ctx),
+    }).map_err(|e| format!("Failed to create speed pickup: {}", e))?;
+    Ok(())
+}
+
+fn loot_expires_at(ctx: &ReducerContext) -> Timestamp {
+    Timestamp::from_micros_since_unix_epoch(ctx.timestamp.to_micros_since_unix_epoch() + LOOT_LIFETIME_MICROS)
+}
+
+pub fn update_loot_drops(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    for loot in ctx.db.loot_drop().iter() {
+        if now_micros >= loot.expires_at.to_micros_since_unix_epoch() {
+            ctx.db.loot_drop().id().delete(loot.id);
+            continue;
+        }
+
+        let Some(collector_identity) = nearest_collector(ctx.db.player().iter(), &loot.position) else {
+            continue;
+        };
+
+        ctx.db.loot_drop().id().delete(loot.id);
+        combat_logic::apply_heal(ctx, collector_identity, loot.health);
+        if let Some(mut collector) = ctx.db.player().identity().find(collector_identity) {
+            collector.mana = (collector.mana + loot.mana).min(collector.max_mana);
+            collector.score += loot.score;
+            ctx.db.player().identity().update(collector);
+        }
+        if loot.kind == LOOT_KIND_SPEED {
+            status_logic::apply_haste(ctx, collector_identity);
+        }
+        spacetimedb::log::info!("Player {} picked up loot {}", collector_identity, loot.id);
+    }
+}
+
+// The nearest living player within LOOT_PICKUP_RADIUS of a drop, if any
+fn nearest_collector(players: impl Iterator<Item = PlayerData>, position: &Vector3) -> Option<Identity> {
+    players
+        .filter(|p| p.health > 0)
+        .map(|p| (calculate_distance(&p.position, position), p))
+        .filter(|(distance, _)| *distance <= LOOT_PICKUP_RADIUS)
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(_, p)| p.identity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::GROUND_HEIGHT;
+    use crate::test_support::{identity, player, vec3};
+
+    fn standing_at(n: u8, x: f32) -> PlayerData {
+        PlayerData { position: vec3(x, GROUND_HEIGHT, 0.0), ..player(n) }
+    }
+
+    #[test]
+    fn loot_goes_to_the_nearest_living_player_in_range() {
+        let drop = vec3(0.0, GROUND_HEIGHT, 0.0);
+        let near = LOOT_PICKUP_RADIUS * 0.5;
+        let players = vec![
+            standing_at(1, near),
+            PlayerData { health: 0, ..standing_at(2, 0.0) },
+            standing_at(3, near * 0.5),
+        ];
+        assert_eq!(nearest_collector(players.into_iter(), &drop), Some(identity(3)));
+
+        let out_of_range = vec![standing_at(4, LOOT_PICKUP_RADIUS + 1.0)];
+        assert_eq!(nearest_collector(out_of_range.into_iter(), &drop), None);
+    }
+}
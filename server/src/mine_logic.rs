@@ -0,0 +1,143 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - mine_logic.rs
+ *
+ * This file contains proximity mines: placed area-denial traps that explode
+ * when an enemy of their owner walks into them.
+ *
+ * Key components:
+ *
+ * 1. Placement:
+ *    - place_mine: Validates range and stores a Mine row carrying the owner's team.
+ *      The mine is harmless until armed_at (MINE_ARM_DELAY_MICROS after placement)
+ *
+ * 2. Game Tick:
+ *    - update_mines: Removes expired mines, and detonates armed mines once a living
+ *      enemy (not the owner, not an ally, both sides with PvP on) is within
+ *      trigger_radius. Detonation damages every player within MINE_AOE_RADIUS through
+ *      combat_logic::apply_damage (so allies, i-frames and PvP rules still apply),
+ *      knocks back everyone it hurt, then removes the mine
+ *
+ * Related files:
+ *    - combat_logic.rs: Damage helper and PvP rule
+ *    - team_logic.rs: Ally checks (in free-for-all every other player is an enemy)
+ *    - lib.rs: Mine table and place_mine reducer
+ */
+
+use spacetimedb::{ReducerContext, Table, Timestamp};
+use crate::common::{
+    Vector3, MINE_ARM_DELAY_MICROS, MINE_AOE_RADIUS, MINE_CAST_RANGE, MINE_DAMAGE, MINE_DURATION_MICROS,
+    MINE_TRIGGER_RADIUS, EXPLOSION_KNOCKBACK_DISTANCE,
+};
+use crate::projectile_logic::spawn_impact_effect;
+use crate::{calculate_distance, combat_logic, config_logic, player_logic, team_logic, Mine, PlayerData};
+// Import table traits
+use crate::{mine, player};
+
+pub fn place_mine(ctx: &ReducerContext, position: Vector3) -> Result<(), String> {
+    let Some(owner) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can place a mine.".to_string());
+    };
+    if owner.health <= 0 {
+        return Err("Dead players cannot place mines.".to_string());
+    }
+    if calculate_distance(&owner.position, &position) > MINE_CAST_RANGE {
+        return Err("Mine position is out of range.".to_string());
+    }
+
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    ctx.db.mine().try_insert(Mine {
+        id: 0, // auto_inc will set this
+        owner_identity: owner.identity,
+        owner_team: owner.team,
+        position,
+        armed_at: Timestamp::from_micros_since_unix_epoch(now_micros + MINE_ARM_DELAY_MICROS),
+        trigger_radius: MINE_TRIGGER_RADIUS,
+        damage: MINE_DAMAGE,
+        expires_at: Timestamp::from_micros_since_unix_epoch(now_micros + MINE_DURATION_MICROS),
+    }).map_err(|e| format!("Failed to place mine: {}", e))?;
+
+    spacetimedb::log::info!("Player {} placed a mine.", owner.identity);
+    Ok(())
+}
+
+// Whether `player` sets off a mine owned by `mine.owner_identity`
+fn triggers(mine: &Mine, player: &PlayerData, owner_pvp: bool, team_count: u32) -> bool {
+    player.identity != mine.owner_identity
+        && player.health > 0
+        && !team_logic::is_ally(team_count, mine.owner_team, player.team)
+        && owner_pvp
+        && player.pvp_enabled
+        && calculate_distance(&player.position, &mine.position) <= mine.trigger_radius
+}
+
+// Expire old mines and detonate armed ones an enemy has walked into
+pub fn update_mines(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let team_count = config_logic::get_config(ctx).team_count;
+
+    for mine in ctx.db.mine().iter() {
+        if now_micros >= mine.expires_at.to_micros_since_unix_epoch() {
+            ctx.db.mine().id().delete(mine.id);
+            continue;
+        }
+        if now_micros < mine.armed_at.to_micros_since_unix_epoch() {
+            continue;
+        }
+
+        // A mine whose owner left stays live for everyone
+        let owner_pvp = ctx.db.player().identity().find(mine.owner_identity)
+            .map(|owner| owner.pvp_enabled)
+            .unwrap_or(true);
+        if !ctx.db.player().iter().any(|p| triggers(&mine, &p, owner_pvp, team_count)) {
+            continue;
+        }
+
+        spacetimedb::log::info!("Mine {} detonated", mine.id);
+        let targets: Vec<_> = ctx.db.player().iter()
+            .filter(|p| calculate_distance(&p.position, &mine.position) <= MINE_AOE_RADIUS)
+            .filter(|p| p.identity == mine.owner_identity || !team_logic::is_ally(team_count, mine.owner_team, p.team))
+            .map(|p| p.identity)
+            .collect();
+        for target in targets {
+            let outcome = combat_logic::apply_damage(ctx, target, mine.damage, Some(mine.owner_identity), Some(&mine.position));
+            if outcome.applied > 0 {
+                player_logic::apply_knockback(ctx, target, &mine.position, EXPLOSION_KNOCKBACK_DISTANCE);
+            }
+        }
+        spawn_impact_effect(ctx, &mine.position, "explosion");
+        ctx.db.mine().id().delete(mine.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at_micros, player};
+
+    fn mine(owner: &PlayerData) -> Mine {
+        Mine {
+            id: 1,
+            owner_identity: owner.identity,
+            owner_team: owner.team,
+            position: owner.position.clone(),
+            armed_at: at_micros(MINE_ARM_DELAY_MICROS),
+            trigger_radius: MINE_TRIGGER_RADIUS,
+            damage: MINE_DAMAGE,
+            expires_at: at_micros(MINE_DURATION_MICROS),
+        }
+    }
+
+    #[test]
+    fn mines_are_triggered_by_living_enemies_only() {
+        let owner = PlayerData { team: 1, ..player(1) };
+        let mine = mine(&owner);
+        let enemy = PlayerData { team: 2, ..player(2) };
+
+        assert!(triggers(&mine, &enemy, true, 2));
+        assert!(!triggers(&mine, &owner, true, 2));
+        assert!(!triggers(&mine, &PlayerData { team: 1, ..player(3) }, true, 2));
+        assert!(!triggers(&mine, &PlayerData { health: 0, ..enemy.clone() }, true, 2));
+        assert!(!triggers(&mine, &PlayerData { pvp_enabled: false, ..enemy.clone() }, true, 2));
+        assert!(!triggers(&mine, &enemy, false, 2));
+    }
+}
@@ -8,22 +8,65 @@
  * 
  * 1. Movement Calculation:
  *    - calculate_new_position: Computes player movement based on input and rotation
+ *      at the player's current_speed
  *    - Vector math for converting input to movement direction
  *    - Direction normalization and speed application
  * 
  * 2. State Management:
  *    - update_input_state: Updates player state based on client input
  *    - Handles position, animation, and derived state (is_moving, is_running)
+ *    - update_movement_speed: Ramps current_speed from 0 to walk to run speed
+ *      (MOVEMENT_ACCELERATION/DECELERATION), scaled by speed_multiplier while hasted;
+ *      is_running means above (unhasted) walk speed. Runs per input, over the same step
+ *      the input moves the player, so it matches client prediction
+ *    - determine_animation: Server-side logical animation state from input
+ *    - is_known_animation: Validates logical animation state names
+ *    - try_dodge_roll: Dash burst with i-frames (invulnerable_until) and a cooldown
+ *    - charge: Dash-attack along the facing direction that stops at and damages the
+ *      first enemy in its path
+ *    - blood_sacrifice: Trades health for mana and an empower buff, never lethally
+ *    - share_resource: Gives some of the caller's health or mana to a nearby ally; only what the
+ *      ally has room for is moved, and the giver keeps SHARE_RESOURCE_MIN_HEALTH health
+ *    - swap_positions: Trades places with a living ally in SWAP_POSITIONS_RANGE; both are then
+ *      pushed clear of other players and kept in bounds
+ *    - separate_from_players: After moving, pushes the mover out of any living player
+ *      closer than the sum of their class collision_radius values
+ *    - clamp_turn: Limits how fast the stored facing (rotation.y) can turn
+ *      (GameConfig.max_turn_rate), so players can't spin instantly for melee
+ *    - apply_knockback/knockback_offset: Pushes a player away from an explosion,
+ *      reduced by their class's knockback_resistance
+ *    - try_block/blocks_hit_from: Block input raises a guard (block_until) that
+ *      reduces frontal hits in combat_logic::apply_damage
+ *    - is_active_participant: AFK check (last_input_at vs GameConfig.afk_timeout_micros)
+ *      gating score and xp rewards
+ *    - set_max_health/set_max_mana: Only way to change maxes; floors them at 1
+ *      and rescales the current value to the new max
  *    - Translates raw input to game state
  * 
  * 3. Game Tick:
- *    - update_players_logic: Placeholder for periodic player updates
- *    - Currently empty as players are updated directly through input
+ *    - update_players_logic: Periodic player updates
+ *    - Expires timed state such as attacks (attack_until) and in_combat
+ *    - respawn: Dead players (health 0) come back once respawn_at passes, at their
+ *      bound_position (bind_location) if it's in bounds, otherwise at a spawn point
+ *    - choose_spawn_position: Prefers spawn points with no enemy within the configured
+ *      spawn_protection_radius (used for joins and respawns)
+ *    - apply_vertical_physics: Jump and gravity using GameConfig values scaled by
+ *      the player's jump_scale/gravity_scale. Driven by update_vertical_physics from
+ *      projectile_tick, since a 1s game_tick step would land a jump in the tick it starts
+ *    - apply_gravity_wells: Pulls enemies of a well's owner toward its center
+ *    - apply_jump_pads: Gives grounded players on an armed JumpPad its upward boost,
+ *      the vertical physics on the projectile schedule then carry them
+ *    - apply_portals: A player within one end of a Portal (horizontally) is moved to the
+ *      other end, then ignored by portals for PORTAL_COOLDOWN_MICROS so standing on the
+ *      exit doesn't send them straight back
+ *    - update_smoothed_position: Eases the published smoothed_position toward the
+ *      authoritative position so other clients see motion without rubber-banding
+ *    - World bounds: players outside WORLD_HALF_EXTENT (or below GROUND_HEIGHT) are clamped, damaged or
+ *      teleported to a spawn depending on GameConfig.out_of_bounds_mode
  *    - Can be extended for server-side simulation (AI, physics, etc.)
  * 
  * Extension points:
  *    - Add terrain logic for realistic height adjustments
- *    - Implement server-side animation determination (commented example provided)
  *    - Add collision detection in calculate_new_position
  *    - Expand update_players_logic for server-side gameplay mechanics
  * 
@@ -32,134 +75,1438 @@
  *    - lib.rs: Calls into this module's functions from reducers
  */
 
-use spacetimedb::ReducerContext;
+use spacetimedb::{ReducerContext, Identity, Table, Timestamp};
 // Import common structs and constants
-use crate::common::{Vector3, InputState, PLAYER_SPEED, SPRINT_MULTIPLIER};
+use crate::common::{
+    Vector3, InputState, ATTACK_DURATION_MICROS, DODGE_ROLL_DISTANCE, DODGE_ROLL_IFRAME_MICROS,
+    DODGE_ROLL_COOLDOWN_MICROS, BLOCK_WINDOW_MICROS, MIN_MAX_STAT, XP_PER_LEVEL, MOVEMENT_ACCELERATION, MOVEMENT_DECELERATION,
+    SPAWN_POINT_COUNT, WORLD_HALF_EXTENT, OUT_OF_BOUNDS_DAMAGE_PER_TICK, MOVEMENT_SMOOTHING_FACTOR,
+    MOVEMENT_SMOOTHING_SNAP_DISTANCE, KNOWN_ANIMATIONS, CHARGE_DISTANCE, CHARGE_HIT_RADIUS,
+    CHARGE_STOP_DISTANCE, CHARGE_DAMAGE, COLLISION_LAYER_ENEMY, BLOOD_SACRIFICE_HEALTH_COST, BLOOD_SACRIFICE_MANA_GAIN,
+    GROUND_HEIGHT, SHARE_RESOURCE_RANGE, SHARE_RESOURCE_MIN_HEALTH, PORTAL_COOLDOWN_MICROS, SWAP_POSITIONS_RANGE,
+};
+use crate::projectile_logic::{can_collide, collision_layer_for_player, distance_to_segment};
 // Import the PlayerData struct definition (assuming it's in lib.rs or common.rs)
-use crate::PlayerData;
+use crate::{
+    calculate_distance, class_logic, combat_logic, config_logic, player_view_logic, status_logic, team_logic, PlayerData,
+    GameConfig, GravityWell, JumpPad, Portal,
+};
+// Import table traits
+use crate::{gravity_well, jump_pad, player, portal};
 
-// Corrected movement logic based on reversed feedback
-pub fn calculate_new_position(position: &Vector3, rotation: &Vector3, input: &InputState, delta_time: f32) -> Vector3 {
-    let has_movement_input = input.forward || input.backward || input.left || input.right;
-
-    if has_movement_input {
-        let speed = if input.sprint { PLAYER_SPEED * SPRINT_MULTIPLIER } else { PLAYER_SPEED };
+// GameConfig.out_of_bounds_mode values: what happens to a player outside the world bounds
+pub const OUT_OF_BOUNDS_CLAMP: &str = "clamp"; // Pushed back to the edge
+pub const OUT_OF_BOUNDS_DAMAGE: &str = "damage"; // Take damage every tick until back inside
+pub const OUT_OF_BOUNDS_TELEPORT: &str = "teleport"; // Sent back to a spawn point
 
-        // This approach more directly matches the new client implementation
-        // Create basis vectors for movement (forward/right vectors from camera)
-        // -Z is forward in Three.js coordinates 
-        let yaw = rotation.y;
-        
-        // Forward and right unit vectors (initially along axes)
-        let forward = Vector3 { x: 0.0, y: 0.0, z: -1.0 };
-        let right = Vector3 { x: 1.0, y: 0.0, z: 0.0 };
-        
-        // Rotate these vectors based on player rotation (around Y-axis)
-        // These are the rotation formulas for vectors around Y axis
-        let cos_yaw = yaw.cos();
-        let sin_yaw = yaw.sin();
-        
-        // Apply rotation to forward vector
-        let rotated_forward = Vector3 {
-            x: forward.x * cos_yaw + forward.z * sin_yaw,
-            y: 0.0,
-            z: -forward.x * sin_yaw + forward.z * cos_yaw,
-        };
-        
-        // Apply rotation to right vector
-        let rotated_right = Vector3 {
-            x: right.x * cos_yaw + right.z * sin_yaw,
-            y: 0.0,
-            z: -right.x * sin_yaw + right.z * cos_yaw,
-        };
-        
-        // Accumulate movement along these basis vectors
-        let mut direction = Vector3 { x: 0.0, y: 0.0, z: 0.0 };
-        
-        if input.forward {
-            direction.x -= rotated_forward.x;
-            direction.z -= rotated_forward.z;
-        }
-        if input.backward {
-            direction.x += rotated_forward.x;
-            direction.z += rotated_forward.z;
-        }
-        if input.right {
-            direction.x -= rotated_right.x;
-            direction.z -= rotated_right.z;
-        }
-        if input.left {
-            direction.x += rotated_right.x;
-            direction.z += rotated_right.z;
-        }
-        
-        // Normalize for consistent speed in all directions
-        let magnitude = (direction.x.powi(2) + direction.z.powi(2)).sqrt();
-        if magnitude > 0.01 {
-            direction.x /= magnitude;
-            direction.z /= magnitude;
-        }
-        
+// Corrected movement logic based on reversed feedback
+// `speed` is the player's current_speed, which update_input_state ramps toward the walk/run speed
+pub fn calculate_new_position(position: &Vector3, rotation: &Vector3, input: &InputState, delta_time: f32, speed: f32) -> Vector3 {
+    if let Some(direction) = movement_direction(rotation, input) {
         // Apply speed and delta time
-        direction.x *= speed * delta_time;
-        direction.z *= speed * delta_time;
-        
-        // Create new position
         let mut new_position = position.clone();
-        new_position.x += direction.x;
-        new_position.z += direction.z;
+        new_position.x += direction.x * speed * delta_time;
+        new_position.z += direction.z * speed * delta_time;
         
         // For terrain, you could implement height logic here if needed
         // Example: new_position.y = calculate_terrain_height(new_position.x, new_position.z);
         
-        return new_position;
+        new_position
     } else {
         // No movement input, return current position
         position.clone()
     }
 }
 
-// Note: Animation determination is currently handled client-side
-// You could implement server-side animation logic here if needed
-// For example:
-// pub fn determine_animation(input: &InputState) -> String {
-//     let is_moving = input.forward || input.backward || input.left || input.right;
-//     if input.attack { return "attack1".to_string(); }
-//     if input.jump { return "jump".to_string(); }
-//     if is_moving {
-//         if input.sprint { "run-forward".to_string() }
-//         else { "walk-forward".to_string() }
-//     } else {
-//         "idle".to_string()
-//     }
-// }
+// Normalized horizontal direction the movement input points in, or None if the player isn't moving
+pub fn movement_direction(rotation: &Vector3, input: &InputState) -> Option<Vector3> {
+    let has_movement_input = input.forward || input.backward || input.left || input.right;
+    if !has_movement_input {
+        return None;
+    }
+
+    // This approach more directly matches the new client implementation
+    // Create basis vectors for movement (forward/right vectors from camera)
+    // -Z is forward in Three.js coordinates 
+    let yaw = rotation.y;
+    
+    // Forward and right unit vectors (initially along axes)
+    let forward = Vector3 { x: 0.0, y: 0.0, z: -1.0 };
+    let right = Vector3 { x: 1.0, y: 0.0, z: 0.0 };
+    
+    // Rotate these vectors based on player rotation (around Y-axis)
+    // These are the rotation formulas for vectors around Y axis
+    let cos_yaw = yaw.cos();
+    let sin_yaw = yaw.sin();
+    
+    // Apply rotation to forward vector
+    let rotated_forward = Vector3 {
+        x: forward.x * cos_yaw + forward.z * sin_yaw,
+        y: 0.0,
+        z: -forward.x * sin_yaw + forward.z * cos_yaw,
+    };
+    
+    // Apply rotation to right vector
+    let rotated_right = Vector3 {
+        x: right.x * cos_yaw + right.z * sin_yaw,
+        y: 0.0,
+        z: -right.x * sin_yaw + right.z * cos_yaw,
+    };
+    
+    // Accumulate movement along these basis vectors
+    let mut direction = Vector3 { x: 0.0, y: 0.0, z: 0.0 };
+    
+    if input.forward {
+        direction.x -= rotated_forward.x;
+        direction.z -= rotated_forward.z;
+    }
+    if input.backward {
+        direction.x += rotated_forward.x;
+        direction.z += rotated_forward.z;
+    }
+    if input.right {
+        direction.x -= rotated_right.x;
+        direction.z -= rotated_right.z;
+    }
+    if input.left {
+        direction.x += rotated_right.x;
+        direction.z += rotated_right.z;
+    }
+    
+    // Normalize for consistent speed in all directions
+    let magnitude = (direction.x.powi(2) + direction.z.powi(2)).sqrt();
+    if magnitude > 0.01 {
+        direction.x /= magnitude;
+        direction.z /= magnitude;
+        Some(direction)
+    } else {
+        // Opposing keys cancel out
+        None
+    }
+}
+
+// Speed the player's input asks for: 0 when idle, walk speed, or run speed when sprinting
+pub fn target_speed(input: &InputState, config: &GameConfig) -> f32 {
+    let has_movement_input = input.forward || input.backward || input.left || input.right;
+    if !has_movement_input {
+        0.0
+    } else if input.sprint {
+        config.player_speed * config.sprint_multiplier
+    } else {
+        config.player_speed
+    }
+}
+
+// Above walk speed counts as running (animation and anything that drains while running).
+// Callers pass the unhasted speed (divided by speed_multiplier), so a hasted walk isn't a run
+pub fn is_run_speed(speed: f32, config: &GameConfig) -> bool {
+    speed > config.player_speed
+}
+
+// Ramp current_speed toward the input's target speed instead of jumping straight to it.
+// Returns true if the player's speed or running state changed.
+pub fn update_movement_speed(player: &mut PlayerData, config: &GameConfig, delta_time: f32) -> bool {
+    let target = target_speed(&player.input, config) * player.speed_multiplier;
+    let old_speed = player.current_speed;
+    let old_running = player.is_running;
+
+    player.current_speed = if old_speed < target {
+        (old_speed + MOVEMENT_ACCELERATION * delta_time).min(target)
+    } else {
+        (old_speed - MOVEMENT_DECELERATION * delta_time).max(target)
+    };
+    player.is_running = player.is_moving && is_run_speed(player.current_speed / player.speed_multiplier, config);
+
+    player.current_speed != old_speed || player.is_running != old_running
+}
+
+// Unit vector the player is facing, matching the direction forward input moves them
+pub fn facing_direction(rotation: &Vector3) -> Vector3 {
+    Vector3 { x: rotation.y.sin(), y: 0.0, z: rotation.y.cos() }
+}
+
+// Whether a (trimmed, lowercase) name is one of the logical animation states
+pub fn is_known_animation(state: &str) -> bool {
+    KNOWN_ANIMATIONS.contains(&state)
+}
+
+// Logical animation state (one of KNOWN_ANIMATIONS) for the given input, using the same
+// priorities as the client: death, attack, cast, jump, then directional walk/run
+// (forward/back win over strafing).
+// class_logic::class_animation turns it into the class's actual animation name.
+pub fn determine_animation(input: &InputState, alive: bool) -> &'static str {
+    if !alive {
+        return "death";
+    }
+    if input.attack {
+        return "attack1";
+    }
+    if input.cast_spell {
+        return "cast";
+    }
+    if input.jump {
+        return "jump";
+    }
+    let (forward, backward, left, right) = (input.forward, input.backward, input.left, input.right);
+    if !(forward || backward || left || right) {
+        return "idle";
+    }
+    let direction = if forward && !backward {
+        "forward"
+    } else if backward && !forward {
+        "back"
+    } else if left && !right {
+        "left"
+    } else if right && !left {
+        "right"
+    } else {
+        "forward"
+    };
+    match (input.sprint, direction) {
+        (true, "left") => "run-left",
+        (true, "right") => "run-right",
+        (true, "back") => "run-back",
+        (true, _) => "run-forward",
+        (false, "left") => "walk-left",
+        (false, "right") => "walk-right",
+        (false, "back") => "walk-back",
+        (false, _) => "walk-forward",
+    }
+}
+
+// Reconciliation invariant: after update_input_state, the public row's horizontal position
+// is exactly the result of applying every accepted input up to and including
+// last_input_seq. Clients can drop predicted inputs <= last_input_seq and replay the rest
+// on top of the server position. Vertical position is server-simulated in projectile_tick
+// and should be taken from the server as-is. smoothed_position is for display only
+// and must never be used for reconciliation.
+//
+// Inputs that arrive out of order (sequence not newer than last_input_seq) are stale and
+// must be dropped, otherwise position and last_input_seq would drift apart.
+pub fn is_stale_input(player: &PlayerData, input: &InputState) -> bool {
+    // last_input_seq is 0 on join, so the client's first input is always accepted
+    player.last_input_seq != 0 && input.sequence <= player.last_input_seq
+}
 
 // Update player state based on input
-pub fn update_input_state(player: &mut PlayerData, input: InputState, client_rot: Vector3, client_animation: String) {
-    // Calculate movement & animation based on RECEIVED input
+pub fn update_input_state(player: &mut PlayerData, input: InputState, client_rot: Vector3, config: &GameConfig, now: Timestamp) {
+    // Calculate movement based on RECEIVED input
     let delta_time_estimate: f32 = 1.0 / 60.0; // Estimate client frame delta
+    // Ramp speed over the same step the input moves the player, like client prediction does
+    player.input = input.clone(); // Store the input that caused this state
+    player.is_moving = input.forward || input.backward || input.left || input.right;
+    update_movement_speed(player, config, delta_time_estimate);
     let new_position = calculate_new_position(
         &player.position,
         &client_rot, // Use client rotation for direction calc
         &input,
-        delta_time_estimate
+        delta_time_estimate,
+        player.current_speed
     );
 
     // Update player state
     player.position = new_position;
-    player.rotation = client_rot;
-    player.current_animation = client_animation;
-    player.input = input.clone(); // Store the input that caused this state
+    // Movement follows the client's camera yaw (so prediction matches), but the stored
+    // facing that melee and beams use can only turn at max_turn_rate
+    let elapsed_secs = (now.to_micros_since_unix_epoch() - player.last_input_at.to_micros_since_unix_epoch()).max(0) as f32 / 1_000_000.0;
+    player.rotation = clamp_turn(&player.rotation, client_rot, config.max_turn_rate, elapsed_secs);
+    player.last_input_at = now;
     player.last_input_seq = input.sequence;
-    player.is_moving = input.forward || input.backward || input.left || input.right;
-    player.is_running = player.is_moving && input.sprint;
-    player.is_attacking = input.attack;
+    if !input.jump {
+        // Key released, the next press may jump again
+        player.jump_consumed = false;
+    }
+    // Start the jump as soon as the press arrives, projectile_tick then simulates the arc
+    try_jump(player, config);
+    update_attack_state(player, input.attack, now);
     player.is_casting = input.cast_spell;
+    if input.dash {
+        try_dodge_roll(player, &input, now);
+    }
+    if input.block {
+        try_block(player, now);
+    }
+}
+
+// Raise the guard for BLOCK_WINDOW_MICROS. Like attacks, presses during an active
+// guard are ignored, so holding block doesn't keep it up forever without re-timing it.
+pub fn try_block(player: &mut PlayerData, now: Timestamp) -> bool {
+    let now_micros = now.to_micros_since_unix_epoch();
+    if now_micros < player.block_until.to_micros_since_unix_epoch() {
+        return false;
+    }
+    player.block_until = Timestamp::from_micros_since_unix_epoch(now_micros + BLOCK_WINDOW_MICROS);
+    true
+}
+
+// Whether a hit coming from `origin` is stopped by the player's guard:
+// the guard must be up and the origin in front of the player (within 90 degrees of facing)
+pub fn blocks_hit_from(player: &PlayerData, origin: &Vector3, now: Timestamp) -> bool {
+    if now.to_micros_since_unix_epoch() >= player.block_until.to_micros_since_unix_epoch() {
+        return false;
+    }
+    let facing = facing_direction(&player.rotation);
+    let dx = origin.x - player.position.x;
+    let dz = origin.z - player.position.z;
+    facing.x * dx + facing.z * dz > 0.0
+}
+
+// Turn from `current` toward `requested` by at most max_turn_rate * elapsed_secs around Y,
+// taking the short way round. A max_turn_rate of 0 disables the clamp.
+pub fn clamp_turn(current: &Vector3, requested: Vector3, max_turn_rate: f32, elapsed_secs: f32) -> Vector3 {
+    if max_turn_rate <= 0.0 {
+        return requested;
+    }
+    let full_turn = std::f32::consts::TAU;
+    let mut diff = (requested.y - current.y) % full_turn;
+    if diff > std::f32::consts::PI {
+        diff -= full_turn;
+    } else if diff < -std::f32::consts::PI {
+        diff += full_turn;
+    }
+    let max_step = max_turn_rate * elapsed_secs;
+    Vector3 { y: current.y + diff.clamp(-max_step, max_step), ..requested }
+}
+
+// Dodge-roll: a burst along the movement input (or facing, if standing still)
+// that makes the player invulnerable for the roll. Ignored while on cooldown.
+pub fn try_dodge_roll(player: &mut PlayerData, input: &InputState, now: Timestamp) -> bool {
+    let now_micros = now.to_micros_since_unix_epoch();
+    if now_micros < player.dash_cooldown_until.to_micros_since_unix_epoch() {
+        return false;
+    }
+
+    let direction = movement_direction(&player.rotation, input)
+        .unwrap_or_else(|| facing_direction(&player.rotation));
+    player.position.x += direction.x * DODGE_ROLL_DISTANCE;
+    player.position.z += direction.z * DODGE_ROLL_DISTANCE;
+    player.invulnerable_until = Timestamp::from_micros_since_unix_epoch(now_micros + DODGE_ROLL_IFRAME_MICROS);
+    player.dash_cooldown_until = Timestamp::from_micros_since_unix_epoch(now_micros + DODGE_ROLL_COOLDOWN_MICROS);
+    true
+}
+
+// How far a charge carries the player: the full CHARGE_DISTANCE, or up to
+// CHARGE_STOP_DISTANCE short of an enemy `along` (0..1) the path
+fn charge_travel(hit_along: Option<f32>) -> f32 {
+    match hit_along {
+        Some(along) => (along * CHARGE_DISTANCE - CHARGE_STOP_DISTANCE).max(0.0),
+        None => CHARGE_DISTANCE,
+    }
+}
+
+// Warrior charge: rush CHARGE_DISTANCE along the facing direction, stopping just short of
+// the first enemy in the way and hitting them for CHARGE_DAMAGE. Allies, invulnerable
+// players and players PvP rules protect don't stop the charge. Shares the dash cooldown.
+pub fn charge(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(mut charger) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can charge.".to_string());
+    };
+    if charger.health <= 0 {
+        return Err("Dead players cannot charge.".to_string());
+    }
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    if now_micros < charger.dash_cooldown_until.to_micros_since_unix_epoch() {
+        return Err("Charge is on cooldown.".to_string());
+    }
+
+    let team_count = config_logic::get_config(ctx).team_count;
+    let direction = facing_direction(&charger.rotation);
+    let start = charger.position.clone();
+    let end = Vector3 {
+        x: start.x + direction.x * CHARGE_DISTANCE,
+        y: start.y,
+        z: start.z + direction.z * CHARGE_DISTANCE,
+    };
+    // First enemy along the path, by how far along it they are
+    let hit = ctx.db.player().iter()
+        .filter(|p| p.identity != charger.identity && p.health > 0 && !is_invulnerable(p, ctx.timestamp))
+        .filter(|p| can_collide(COLLISION_LAYER_ENEMY, collision_layer_for_player(charger.identity, charger.team, team_count, p)))
+        .filter(|p| combat_logic::pvp_allowed(ctx, Some(charger.identity), p))
+        .filter_map(|p| {
+            let (distance, along) = distance_to_segment(&p.position, &start, &end);
+            (distance <= CHARGE_HIT_RADIUS).then_some((along, p.identity))
+        })
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let travel = charge_travel(hit.as_ref().map(|(along, _)| *along));
+    charger.position.x += direction.x * travel;
+    charger.position.z += direction.z * travel;
+    charger.dash_cooldown_until = Timestamp::from_micros_since_unix_epoch(now_micros + DODGE_ROLL_COOLDOWN_MICROS);
+    let charger_identity = charger.identity;
+    // Save the charger first, apply_damage re-reads rows itself
+    ctx.db.player().identity().update(charger);
+
+    if let Some((_, target)) = hit {
+        spacetimedb::log::info!("Player {} charged into {}", charger_identity, target);
+        combat_logic::apply_damage(ctx, target, CHARGE_DAMAGE, Some(charger_identity), Some(&start));
+    }
+    Ok(())
+}
+
+// Trade BLOOD_SACRIFICE_HEALTH_COST health for mana (up to max_mana) and a short damage buff.
+// Refused when the cost would kill the caster.
+pub fn blood_sacrifice(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can sacrifice health.".to_string());
+    };
+    if player.health <= 0 {
+        return Err("Dead players cannot sacrifice health.".to_string());
+    }
+    if player.health <= BLOOD_SACRIFICE_HEALTH_COST {
+        return Err("Not enough health to sacrifice.".to_string());
+    }
+
+    player.health -= BLOOD_SACRIFICE_HEALTH_COST;
+    player.mana = (player.mana + BLOOD_SACRIFICE_MANA_GAIN).min(player.max_mana);
+    let identity = player.identity;
+    ctx.db.player().identity().update(player);
+    status_logic::apply_empower(ctx, identity);
+
+    spacetimedb::log::info!("Player {} sacrificed {} health", identity, BLOOD_SACRIFICE_HEALTH_COST);
+    Ok(())
+}
+
+pub const RESOURCE_HEALTH: &str = "health";
+pub const RESOURCE_MANA: &str = "mana";
+
+// Move up to `amount` of a (normalized) resource without overfilling the recipient. Givers
+// always keep SHARE_RESOURCE_MIN_HEALTH. Returns how much actually moved.
+fn transfer_resource(giver: &mut PlayerData, recipient: &mut PlayerData, resource: &str, amount: i32) -> Result<i32, String> {
+    match resource {
+        RESOURCE_HEALTH => {
+            let available = (giver.health - SHARE_RESOURCE_MIN_HEALTH).max(0);
+            let transferred = amount.min(available).min(recipient.max_health - recipient.health);
+            giver.health -= transferred;
+            recipient.health += transferred;
+            Ok(transferred)
+        }
+        RESOURCE_MANA => {
+            let transferred = amount.min(giver.mana).min(recipient.max_mana - recipient.mana);
+            giver.mana -= transferred;
+            recipient.mana += transferred;
+            Ok(transferred)
+        }
+        _ => Err(format!("Unknown resource '{}', expected health or mana.", resource)),
+    }
+}
+
+// Move up to `amount` health or mana from the caller to an ally within SHARE_RESOURCE_RANGE
+pub fn share_resource(ctx: &ReducerContext, target_identity: Identity, resource: &str, amount: i32) -> Result<(), String> {
+    let Some(mut giver) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can share resources.".to_string());
+    };
+    let Some(mut recipient) = ctx.db.player().identity().find(target_identity) else {
+        return Err("Target is not an active player.".to_string());
+    };
+    if amount <= 0 {
+        return Err("Amount must be positive.".to_string());
+    }
+    if giver.identity == recipient.identity {
+        return Err("Cannot share with yourself.".to_string());
+    }
+    if giver.health <= 0 || recipient.health <= 0 {
+        return Err("Dead players cannot share or receive resources.".to_string());
+    }
+    let team_count = config_logic::get_config(ctx).team_count;
+    if !team_logic::is_ally(team_count, giver.team, recipient.team) {
+        return Err("Resources can only be shared with allies.".to_string());
+    }
+    if calculate_distance(&giver.position, &recipient.position) > SHARE_RESOURCE_RANGE {
+        return Err("Ally is out of range.".to_string());
+    }
+
+    let resource = resource.trim().to_lowercase();
+    let transferred = transfer_resource(&mut giver, &mut recipient, &resource, amount)?;
+    if transferred <= 0 {
+        return Err(format!("No {} could be shared.", resource));
+    }
+
+    spacetimedb::log::info!("Player {} gave {} {} to {}", giver.identity, transferred, resource, recipient.identity);
+    ctx.db.player().identity().update(giver);
+    ctx.db.player().identity().update(recipient);
+    Ok(())
+}
+
+// Swaps need two different living allies within SWAP_POSITIONS_RANGE
+fn can_swap_positions(caster: &PlayerData, ally: &PlayerData, team_count: u32) -> Result<(), String> {
+    if caster.identity == ally.identity {
+        return Err("Cannot swap with yourself.".to_string());
+    }
+    if caster.health <= 0 || ally.health <= 0 {
+        return Err("Dead players cannot swap positions.".to_string());
+    }
+    if !team_logic::is_ally(team_count, caster.team, ally.team) {
+        return Err("You can only swap positions with allies.".to_string());
+    }
+    if calculate_distance(&caster.position, &ally.position) > SWAP_POSITIONS_RANGE {
+        return Err("Ally is out of range.".to_string());
+    }
+    Ok(())
+}
+
+// Trade places with a living ally within SWAP_POSITIONS_RANGE
+pub fn swap_positions(ctx: &ReducerContext, target_identity: Identity) -> Result<(), String> {
+    let Some(mut caster) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can swap positions.".to_string());
+    };
+    let Some(mut ally) = ctx.db.player().identity().find(target_identity) else {
+        return Err("Target is not an active player.".to_string());
+    };
+    can_swap_positions(&caster, &ally, config_logic::get_config(ctx).team_count)?;
+
+    std::mem::swap(&mut caster.position, &mut ally.position);
+    spacetimedb::log::info!("Player {} swapped positions with {}", caster.identity, ally.identity);
+    // Each one is saved before the other is separated, so separation sees their new spot
+    ctx.db.player().identity().update(ally.clone());
+    settle_after_teleport(ctx, &mut caster);
+    ctx.db.player().identity().update(caster);
+    settle_after_teleport(ctx, &mut ally);
+    ctx.db.player().identity().update(ally);
+    Ok(())
+}
+
+// Push a teleported player clear of others and keep them in bounds, snapping the smoothed position
+fn settle_after_teleport(ctx: &ReducerContext, player: &mut PlayerData) {
+    separate_from_players(ctx, player);
+    clamp_to_bounds(&mut player.position);
+    player.smoothed_position = player.position.clone();
+    player.vertical_velocity = 0.0;
+}
+
+// Set max health, never below MIN_MAX_STAT, keeping the same health fraction.
+// A living player stays alive (at least 1 health) when their max shrinks.
+pub fn set_max_health(player: &mut PlayerData, new_max: i32) {
+    let new_max = new_max.max(MIN_MAX_STAT);
+    player.health = rescale_current(player.health, player.max_health, new_max);
+    player.max_health = new_max;
+}
+
+// Set max mana, never below MIN_MAX_STAT, keeping the same mana fraction
+pub fn set_max_mana(player: &mut PlayerData, new_max: i32) {
+    let new_max = new_max.max(MIN_MAX_STAT);
+    player.mana = rescale_current(player.mana, player.max_mana, new_max);
+    player.max_mana = new_max;
+}
+
+fn rescale_current(current: i32, old_max: i32, new_max: i32) -> i32 {
+    if current <= 0 {
+        return 0;
+    }
+    // Guard against bad legacy data with a zero/negative max
+    let old_max = old_max.max(MIN_MAX_STAT);
+    let scaled = ((current as i64 * new_max as i64) / old_max as i64) as i32;
+    scaled.clamp(1, new_max)
+}
+
+// Whether the player has sent input within GameConfig.afk_timeout_micros (always true when it's 0).
+// Rewards (kills, captures) are only given to active participants, so idle players can't farm.
+pub fn is_active_participant(player: &PlayerData, config: &GameConfig, now: Timestamp) -> bool {
+    config.afk_timeout_micros <= 0
+        || now.to_micros_since_unix_epoch() - player.last_input_at.to_micros_since_unix_epoch() < config.afk_timeout_micros
+}
+
+// Add xp and level up for every XP_PER_LEVEL earned
+pub fn award_xp(player: &mut PlayerData, amount: u32) {
+    player.xp = player.xp.saturating_add(amount);
+    let level = 1 + player.xp / XP_PER_LEVEL;
+    if level > player.level {
+        spacetimedb::log::info!("Player {} reached level {}", player.identity, level);
+        player.level = level;
+    }
+}
+
+// Whether the player is inside an i-frame window (dodge-roll etc.) and should ignore hits
+pub fn is_invulnerable(player: &PlayerData, now: Timestamp) -> bool {
+    now.to_micros_since_unix_epoch() < player.invulnerable_until.to_micros_since_unix_epoch()
+}
+
+// Attacks are timed by the server: pressing attack starts a fixed-length attack,
+// and further presses are ignored until attack_until has passed
+pub fn update_attack_state(player: &mut PlayerData, attack_pressed: bool, now: Timestamp) {
+    let now_micros = now.to_micros_since_unix_epoch();
+    if now_micros < player.attack_until.to_micros_since_unix_epoch() {
+        // Attack still in progress, ignore re-triggers
+        return;
+    }
+
+    if attack_pressed {
+        player.is_attacking = true;
+        player.attack_until = Timestamp::from_micros_since_unix_epoch(now_micros + ATTACK_DURATION_MICROS);
+    } else {
+        player.is_attacking = false;
+    }
 }
 
 // Update players logic (called from game_tick)
-pub fn update_players_logic(_ctx: &ReducerContext, _delta_time: f64) {
-    // In the simplified starter pack, we don't need to do anything in the game tick
-    // for players as they're updated directly through the update_player_input reducer
-    // This function is a placeholder for future expansion
+pub fn update_players_logic(ctx: &ReducerContext, delta_time: f64) {
+    // Horizontal movement is applied directly through the update_player_input reducer,
+    // the tick handles vertical physics and timed state that must expire without client input
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let config = config_logic::get_config(ctx);
+    let delta_time = delta_time as f32;
+    let gravity_wells = active_gravity_wells(ctx);
+    let jump_pads = active_jump_pads(ctx);
+    let portals = active_portals(ctx);
+
+    for mut player in ctx.db.player().iter() {
+        let mut changed = false;
+
+        if player.health <= 0 {
+            if now_micros >= player.respawn_at.to_micros_since_unix_epoch() {
+                // A bound respawn point wins; otherwise vary the preferred spawn per death so
+                // respawns don't always start at slot 0
+                let position = match usable_bind_point(&player) {
+                    Some(bound) => bound,
+                    None => choose_spawn_position(ctx, player.identity, player.team, player.deaths as usize, &config),
+                };
+                respawn(&mut player, position, ctx.timestamp);
+                player.current_animation = class_logic::class_animation(ctx, &player.character_class, "idle");
+                ctx.db.player().identity().update(player);
+            }
+            continue;
+        }
+
+        // End finished attacks even if the client stopped sending input
+        if player.is_attacking && now_micros >= player.attack_until.to_micros_since_unix_epoch() {
+            player.is_attacking = false;
+            changed = true;
+        }
+
+        changed |= combat_logic::update_combat_state(&mut player, ctx.timestamp);
+        changed |= apply_jump_pads(&mut player, &jump_pads);
+        changed |= apply_gravity_wells(&mut player, &gravity_wells, config.team_count, delta_time);
+        changed |= apply_portals(&mut player, &portals, ctx.timestamp);
+
+        let out_of_bounds = is_out_of_bounds(&player.position);
+        if out_of_bounds {
+            match config.out_of_bounds_mode.as_str() {
+                OUT_OF_BOUNDS_TELEPORT => {
+                    player.position = choose_spawn_position(ctx, player.identity, player.team, 0, &config);
+                    player.smoothed_position = player.position.clone();
+                    player.vertical_velocity = 0.0;
+                    changed = true;
+                }
+                OUT_OF_BOUNDS_DAMAGE => {} // Applied below, once this row is saved
+                _ => {
+                    clamp_to_bounds(&mut player.position);
+                    changed = true;
+                }
+            }
+        }
+
+        changed |= update_smoothed_position(&mut player);
+
+        let identity = player.identity;
+        if changed {
+            ctx.db.player().identity().update(player);
+        }
+        if out_of_bounds && config.out_of_bounds_mode == OUT_OF_BOUNDS_DAMAGE {
+            // Storm-style damage, no one is credited if it kills
+            combat_logic::apply_damage(ctx, identity, OUT_OF_BOUNDS_DAMAGE_PER_TICK, None, None);
+        }
+    }
+}
+
+// Push `player` (horizontally) out of every living player they overlap, so the two end up
+// exactly the sum of their collision radii apart. Only the mover is pushed; the others
+// keep their authoritative positions. Players standing on the same point are left alone.
+pub fn separate_from_players(ctx: &ReducerContext, player: &mut PlayerData) {
+    if player.health <= 0 {
+        return;
+    }
+    for other in ctx.db.player().iter() {
+        if other.identity == player.identity || other.health <= 0 {
+            continue;
+        }
+        push_out_of(player, &other);
+    }
+}
+
+fn push_out_of(player: &mut PlayerData, other: &PlayerData) {
+    let min_distance = player.collision_radius + other.collision_radius;
+    let dx = player.position.x - other.position.x;
+    let dz = player.position.z - other.position.z;
+    let distance = (dx * dx + dz * dz).sqrt();
+    if distance >= min_distance || distance < 0.0001 {
+        return;
+    }
+    player.position.x = other.position.x + dx / distance * min_distance;
+    player.position.z = other.position.z + dz / distance * min_distance;
+}
+
+// Ease smoothed_position toward the authoritative position by MOVEMENT_SMOOTHING_FACTOR of the
+// remaining distance. Large jumps (teleports, rolls) snap, height is always copied as-is.
+// Returns true if the smoothed position changed.
+pub fn update_smoothed_position(player: &mut PlayerData) -> bool {
+    let old = player.smoothed_position.clone();
+    let dx = player.position.x - old.x;
+    let dz = player.position.z - old.z;
+    let distance = (dx * dx + dz * dz).sqrt();
+
+    if !(0.01..=MOVEMENT_SMOOTHING_SNAP_DISTANCE).contains(&distance) {
+        player.smoothed_position = player.position.clone();
+    } else {
+        player.smoothed_position.x += dx * MOVEMENT_SMOOTHING_FACTOR;
+        player.smoothed_position.z += dz * MOVEMENT_SMOOTHING_FACTOR;
+        player.smoothed_position.y = player.position.y;
+    }
+    player.smoothed_position != old
+}
+
+// The playable area is a square of WORLD_HALF_EXTENT around the origin (x/z only)
+// Outside the playable area, or below the floor
+pub fn is_out_of_bounds(position: &Vector3) -> bool {
+    position.x.abs() > WORLD_HALF_EXTENT || position.z.abs() > WORLD_HALF_EXTENT || position.y < GROUND_HEIGHT
+}
+
+pub fn clamp_to_bounds(position: &mut Vector3) {
+    position.x = position.x.clamp(-WORLD_HALF_EXTENT, WORLD_HALF_EXTENT);
+    position.z = position.z.clamp(-WORLD_HALF_EXTENT, WORLD_HALF_EXTENT);
+    position.y = position.y.max(GROUND_HEIGHT);
+}
+
+pub fn is_valid_out_of_bounds_mode(mode: &str) -> bool {
+    matches!(mode, OUT_OF_BOUNDS_CLAMP | OUT_OF_BOUNDS_DAMAGE | OUT_OF_BOUNDS_TELEPORT)
+}
+
+// Simple horizontal offset per spawn slot, standing on the ground
+pub fn spawn_position(slot: usize) -> Vector3 {
+    Vector3 { x: (slot as f32 * 5.0) - 2.5, y: GROUND_HEIGHT, z: 0.0 }
+}
+
+// Pick a spawn point for a player, trying slots in order starting at `preferred_slot`.
+// The first slot with no living enemy within GameConfig.spawn_protection_radius wins;
+// if every slot is camped, the one whose nearest enemy is furthest away is used.
+pub fn choose_spawn_position(ctx: &ReducerContext, identity: Identity, team: u32, preferred_slot: usize, config: &GameConfig) -> Vector3 {
+    let enemies: Vec<Vector3> = ctx.db.player().iter()
+        .filter(|p| p.identity != identity && p.health > 0 && !team_logic::is_ally(config.team_count, team, p.team))
+        .map(|p| p.position)
+        .collect();
+    safest_spawn_position(&enemies, preferred_slot, config.spawn_protection_radius)
+}
+
+// The spawn choice itself, given where the living enemies stand
+fn safest_spawn_position(enemies: &[Vector3], preferred_slot: usize, protection_radius: f32) -> Vector3 {
+    let mut safest = spawn_position(preferred_slot % SPAWN_POINT_COUNT);
+    let mut safest_distance = f32::MIN;
+    for offset in 0..SPAWN_POINT_COUNT {
+        let candidate = spawn_position((preferred_slot + offset) % SPAWN_POINT_COUNT);
+        let nearest_enemy = enemies.iter()
+            .map(|enemy| calculate_distance(enemy, &candidate))
+            .fold(f32::MAX, f32::min);
+        if nearest_enemy > protection_radius {
+            return candidate;
+        }
+        if nearest_enemy > safest_distance {
+            safest_distance = nearest_enemy;
+            safest = candidate;
+        }
+    }
+    safest
+}
+
+// Horizontal offset pushing a player at `position` away from `origin` by `distance`,
+// shortened by their knockback_resistance (0..1). Zero when they stand on the origin.
+pub fn knockback_offset(position: &Vector3, origin: &Vector3, distance: f32, resistance: f32) -> Vector3 {
+    let dx = position.x - origin.x;
+    let dz = position.z - origin.z;
+    let length = (dx * dx + dz * dz).sqrt();
+    if length < 0.001 || distance <= 0.0 {
+        return Vector3 { x: 0.0, y: 0.0, z: 0.0 };
+    }
+    let scaled = distance * (1.0 - resistance.clamp(0.0, 1.0));
+    Vector3 { x: dx / length * scaled, y: 0.0, z: dz / length * scaled }
+}
+
+// Knock a living player away from `origin`, e.g. from an explosion. Re-reads the row
+// so it can follow apply_damage in the same tick.
+pub fn apply_knockback(ctx: &ReducerContext, identity: Identity, origin: &Vector3, distance: f32) {
+    let Some(mut player) = ctx.db.player().identity().find(identity) else {
+        return;
+    };
+    if player.health <= 0 {
+        return;
+    }
+    let offset = knockback_offset(&player.position, origin, distance, player.knockback_resistance);
+    if offset.x == 0.0 && offset.z == 0.0 {
+        return;
+    }
+    player.position.x += offset.x;
+    player.position.z += offset.z;
+    ctx.db.player().identity().update(player);
+}
+
+// The player's bound respawn point, unless it's since fallen outside the playable area
+fn usable_bind_point(player: &PlayerData) -> Option<Vector3> {
+    player.bound_position.clone().filter(|bound| !is_out_of_bounds(bound))
+}
+
+// Bring a dead player back at `position` with full health and mana.
+// respawn_at is set to now so it records when they last respawned.
+pub fn respawn(player: &mut PlayerData, position: Vector3, now: Timestamp) {
+    spacetimedb::log::info!("Player {} respawned", player.identity);
+    player.health = player.max_health;
+    player.mana = player.max_mana;
+    player.position = position.clone();
+    player.smoothed_position = position.clone();
+    player.last_spawn_position = position;
+    player.vertical_velocity = 0.0;
+    player.is_grounded = true;
+    player.current_speed = 0.0;
+    player.in_combat = false;
+    player.respawn_at = now;
+}
+
+// Remove expired gravity wells and return the ones still pulling
+fn active_gravity_wells(ctx: &ReducerContext) -> Vec<GravityWell> {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let mut active = Vec::new();
+    for well in ctx.db.gravity_well().iter() {
+        if now_micros >= well.expires_at.to_micros_since_unix_epoch() {
+            ctx.db.gravity_well().id().delete(well.id);
+        } else {
+            active.push(well);
+        }
+    }
+    active
+}
+
+// Pull the player horizontally toward every enemy gravity well they're inside.
+// The pull never overshoots the center. Returns true if the player moved.
+pub fn apply_gravity_wells(player: &mut PlayerData, wells: &[GravityWell], team_count: u32, delta_time: f32) -> bool {
+    let mut moved = false;
+    for well in wells {
+        if well.owner_identity == player.identity || team_logic::is_ally(team_count, well.owner_team, player.team) {
+            continue;
+        }
+        let dx = well.center.x - player.position.x;
+        let dz = well.center.z - player.position.z;
+        let distance = (dx * dx + dz * dz).sqrt();
+        if distance > well.radius || distance < 0.01 {
+            continue;
+        }
+
+        let pull = (well.strength * delta_time).min(distance);
+        player.position.x += dx / distance * pull;
+        player.position.z += dz / distance * pull;
+        moved = true;
+    }
+    moved
+}
+
+// Remove expired jump pads and return the armed ones
+fn active_jump_pads(ctx: &ReducerContext) -> Vec<JumpPad> {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let mut active = Vec::new();
+    for pad in ctx.db.jump_pad().iter() {
+        if pad.expires_at.is_some_and(|expires_at| now_micros >= expires_at.to_micros_since_unix_epoch()) {
+            ctx.db.jump_pad().id().delete(pad.id);
+        } else if now_micros >= pad.armed_at.to_micros_since_unix_epoch() {
+            active.push(pad);
+        }
+    }
+    active
+}
+
+// Remove expired portals and return the rest
+fn active_portals(ctx: &ReducerContext) -> Vec<Portal> {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let mut active = Vec::new();
+    for portal in ctx.db.portal().iter() {
+        if now_micros >= portal.expires_at.to_micros_since_unix_epoch() {
+            ctx.db.portal().id().delete(portal.id);
+        } else {
+            active.push(portal);
+        }
+    }
+    active
+}
+
+// Teleport a player standing in either end of a portal to the other end.
+// Returns true if the player was moved.
+pub fn apply_portals(player: &mut PlayerData, portals: &[Portal], now: Timestamp) -> bool {
+    let now_micros = now.to_micros_since_unix_epoch();
+    if now_micros < player.portal_cooldown_until.to_micros_since_unix_epoch() {
+        return false;
+    }
+    let within = |end: &Vector3, radius: f32| {
+        let dx = end.x - player.position.x;
+        let dz = end.z - player.position.z;
+        (dx * dx + dz * dz).sqrt() <= radius
+    };
+    let Some((portal, exit)) = portals.iter().find_map(|portal| {
+        if within(&portal.position_a, portal.radius) {
+            Some((portal, portal.position_b.clone()))
+        } else if within(&portal.position_b, portal.radius) {
+            Some((portal, portal.position_a.clone()))
+        } else {
+            None
+        }
+    }) else {
+        return false;
+    };
+
+    player.position = exit;
+    clamp_to_bounds(&mut player.position);
+    player.smoothed_position = player.position.clone();
+    player.vertical_velocity = 0.0;
+    player.portal_cooldown_until = Timestamp::from_micros_since_unix_epoch(now_micros + PORTAL_COOLDOWN_MICROS);
+    spacetimedb::log::info!("Player {} went through portal {}", player.identity, portal.id);
+    true
+}
+
+// Launch a grounded player standing on a pad (horizontal distance within its radius).
+// Returns true if the player was launched.
+pub fn apply_jump_pads(player: &mut PlayerData, pads: &[JumpPad]) -> bool {
+    if !player.is_grounded {
+        return false;
+    }
+    let Some(pad) = pads.iter().find(|pad| {
+        let dx = pad.position.x - player.position.x;
+        let dz = pad.position.z - player.position.z;
+        (dx * dx + dz * dz).sqrt() <= pad.radius
+    }) else {
+        return false;
+    };
+    player.vertical_velocity = pad.boost;
+    player.is_grounded = false;
+    spacetimedb::log::info!("Player {} launched by jump pad {}", player.identity, pad.id);
+    true
+}
+
+// Only a fresh press while grounded jumps. The press is consumed, so holding jump
+// (or spamming it mid-air) can't re-trigger until the client releases the key.
+pub fn try_jump(player: &mut PlayerData, config: &GameConfig) -> bool {
+    if !(player.input.jump && player.is_grounded && !player.jump_consumed) {
+        return false;
+    }
+    player.vertical_velocity = config.jump_force * player.jump_scale;
+    player.is_grounded = false;
+    player.jump_consumed = true;
+    true
+}
+
+// Vertical physics for every living airborne player (called from projectile_tick).
+// Views are synced right away, game_tick only refreshes them once a second.
+pub fn update_vertical_physics(ctx: &ReducerContext, delta_time: f32) {
+    let config = config_logic::get_config(ctx);
+    let airborne: Vec<PlayerData> = ctx.db.player().iter()
+        .filter(|p| p.health > 0 && !p.is_grounded)
+        .collect();
+    for mut player in airborne {
+        if apply_vertical_physics(&mut player, &config, delta_time) {
+            player.smoothed_position.y = player.position.y;
+            player_view_logic::sync_player_view(ctx, &player);
+            ctx.db.player().identity().update(player);
+        }
+    }
+}
+
+// Jumping and gravity, scaled per player by jump_scale/gravity_scale (e.g. moon-gravity powerups).
+// Returns true if the player's vertical state changed.
+pub fn apply_vertical_physics(player: &mut PlayerData, config: &GameConfig, delta_time: f32) -> bool {
+    try_jump(player, config);
+    if player.is_grounded {
+        return false;
+    }
+
+    let gravity = config.gravity * player.gravity_scale;
+    player.position.y += player.vertical_velocity * delta_time - 0.5 * gravity * delta_time * delta_time;
+    player.vertical_velocity -= gravity * delta_time;
+
+    // Ground level matches the spawn height
+    if player.position.y <= GROUND_HEIGHT {
+        player.position.y = GROUND_HEIGHT;
+        player.vertical_velocity = 0.0;
+        player.is_grounded = true;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at_micros, identity, idle_input, player, vec3};
+
+    #[test]
+    fn attack_press_starts_a_timed_attack_that_ignores_retriggers() {
+        let mut attacker = player(1);
+        update_attack_state(&mut attacker, true, at_micros(1_000));
+        assert!(attacker.is_attacking);
+        assert_eq!(attacker.attack_until, at_micros(1_000 + ATTACK_DURATION_MICROS));
+
+        // Releasing or pressing again mid-attack changes nothing
+        update_attack_state(&mut attacker, false, at_micros(2_000));
+        assert!(attacker.is_attacking);
+        update_attack_state(&mut attacker, true, at_micros(3_000));
+        assert_eq!(attacker.attack_until, at_micros(1_000 + ATTACK_DURATION_MICROS));
+
+        update_attack_state(&mut attacker, false, at_micros(1_000 + ATTACK_DURATION_MICROS));
+        assert!(!attacker.is_attacking);
+    }
+
+    #[test]
+    fn facing_direction_matches_forward_movement() {
+        let forward = InputState { forward: true, ..idle_input() };
+        for yaw in [0.0, 0.7, -2.0] {
+            let rotation = vec3(0.0, yaw, 0.0);
+            let facing = facing_direction(&rotation);
+            let moved = movement_direction(&rotation, &forward).unwrap();
+            assert!((facing.x - moved.x).abs() < 1e-5 && (facing.z - moved.z).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn dodge_roll_moves_the_player_and_grants_iframes_until_they_expire() {
+        let mut roller = player(1);
+        let right = InputState { right: true, ..idle_input() };
+        assert!(try_dodge_roll(&mut roller, &right, at_micros(1_000)));
+        assert!((roller.position.x + DODGE_ROLL_DISTANCE).abs() < 1e-5);
+
+        assert!(is_invulnerable(&roller, at_micros(1_000 + DODGE_ROLL_IFRAME_MICROS - 1)));
+        assert!(!is_invulnerable(&roller, at_micros(1_000 + DODGE_ROLL_IFRAME_MICROS)));
+    }
+
+    #[test]
+    fn dodge_roll_is_ignored_on_cooldown() {
+        let mut roller = player(1);
+        assert!(try_dodge_roll(&mut roller, &idle_input(), at_micros(0)));
+        let position = roller.position.clone();
+        assert!(!try_dodge_roll(&mut roller, &idle_input(), at_micros(DODGE_ROLL_COOLDOWN_MICROS - 1)));
+        assert_eq!(roller.position, position);
+        assert!(try_dodge_roll(&mut roller, &idle_input(), at_micros(DODGE_ROLL_COOLDOWN_MICROS)));
+    }
+
+    #[test]
+    fn changing_max_health_keeps_the_health_fraction() {
+        let mut target = PlayerData { health: 50, max_health: 100, ..player(1) };
+        set_max_health(&mut target, 200);
+        assert_eq!((target.health, target.max_health), (100, 200));
+
+        set_max_mana(&mut target, 50);
+        assert_eq!((target.mana, target.max_mana), (50, 50));
+    }
+
+    #[test]
+    fn max_stats_are_floored_and_living_players_stay_alive() {
+        let mut target = PlayerData { health: 1, max_health: 100, ..player(1) };
+        set_max_health(&mut target, -20);
+        assert_eq!(target.max_health, MIN_MAX_STAT);
+        assert_eq!(target.health, 1);
+
+        // Dead stays dead, bad legacy max values don't divide by zero
+        assert_eq!(rescale_current(0, 100, 50), 0);
+        assert_eq!(rescale_current(10, 0, 50), 50);
+    }
+
+    #[test]
+    fn jump_and_gravity_are_scaled_per_player() {
+        let config = config_logic::default_game_config();
+        let jumping = InputState { jump: true, ..idle_input() };
+        let mut normal = PlayerData { input: jumping.clone(), ..player(1) };
+        let mut floaty = PlayerData { input: jumping, jump_scale: 2.0, gravity_scale: 0.5, ..player(2) };
+
+        assert!(apply_vertical_physics(&mut normal, &config, 0.05));
+        assert!(apply_vertical_physics(&mut floaty, &config, 0.05));
+        assert!(floaty.position.y > normal.position.y);
+        assert!((floaty.vertical_velocity - (2.0 * config.jump_force - 0.5 * config.gravity * 0.05)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn falling_players_land_on_the_ground() {
+        let config = config_logic::default_game_config();
+        let mut faller = PlayerData { is_grounded: false, position: vec3(0.0, GROUND_HEIGHT + 0.1, 0.0), ..player(1) };
+        assert!(apply_vertical_physics(&mut faller, &config, 0.5));
+        assert_eq!(faller.position.y, GROUND_HEIGHT);
+        assert_eq!(faller.vertical_velocity, 0.0);
+        assert!(faller.is_grounded);
+
+        // Grounded players without a jump press are left alone
+        assert!(!apply_vertical_physics(&mut faller, &config, 0.5));
+    }
+
+    #[test]
+    fn inputs_not_newer_than_the_last_accepted_one_are_stale() {
+        let fresh_join = player(1);
+        assert!(!is_stale_input(&fresh_join, &InputState { sequence: 0, ..idle_input() }));
+
+        let moving = PlayerData { last_input_seq: 10, ..player(1) };
+        assert!(is_stale_input(&moving, &InputState { sequence: 9, ..idle_input() }));
+        assert!(is_stale_input(&moving, &InputState { sequence: 10, ..idle_input() }));
+        assert!(!is_stale_input(&moving, &InputState { sequence: 11, ..idle_input() }));
+    }
+
+    #[test]
+    fn only_a_fresh_grounded_press_jumps() {
+        let config = config_logic::default_game_config();
+        let mut jumper = PlayerData { input: InputState { jump: true, ..idle_input() }, ..player(1) };
+        assert!(try_jump(&mut jumper, &config));
+        assert!(jumper.jump_consumed && !jumper.is_grounded);
+        assert_eq!(jumper.vertical_velocity, config.jump_force);
+
+        // Holding jump after landing doesn't jump again until the key is released
+        jumper.is_grounded = true;
+        assert!(!try_jump(&mut jumper, &config));
+        jumper.jump_consumed = false;
+        assert!(try_jump(&mut jumper, &config));
+
+        // No jumping mid-air
+        let mut airborne = PlayerData { is_grounded: false, input: InputState { jump: true, ..idle_input() }, ..player(2) };
+        assert!(!try_jump(&mut airborne, &config));
+    }
+
+    fn gravity_well(owner: u8, owner_team: u32) -> GravityWell {
+        GravityWell {
+            id: 1,
+            owner_identity: identity(owner),
+            owner_team,
+            center: vec3(10.0, GROUND_HEIGHT, 0.0),
+            radius: 8.0,
+            strength: 4.0,
+            expires_at: at_micros(1_000_000),
+        }
+    }
+
+    #[test]
+    fn gravity_wells_pull_enemies_without_overshooting() {
+        let wells = [gravity_well(9, 0)];
+        let mut enemy = PlayerData { position: vec3(5.0, GROUND_HEIGHT, 0.0), ..player(1) };
+        assert!(apply_gravity_wells(&mut enemy, &wells, 1, 0.5));
+        assert!((enemy.position.x - 7.0).abs() < 1e-5);
+
+        // A long step stops at the center
+        assert!(apply_gravity_wells(&mut enemy, &wells, 1, 10.0));
+        assert!((enemy.position.x - 10.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn gravity_wells_ignore_the_owner_allies_and_players_outside() {
+        let wells = [gravity_well(1, 0)];
+        let mut owner = PlayerData { position: vec3(5.0, GROUND_HEIGHT, 0.0), ..player(1) };
+        assert!(!apply_gravity_wells(&mut owner, &wells, 2, 0.5));
+        let mut ally = PlayerData { position: vec3(5.0, GROUND_HEIGHT, 0.0), team: 0, ..player(2) };
+        assert!(!apply_gravity_wells(&mut ally, &wells, 2, 0.5));
+        let mut far_enemy = PlayerData { position: vec3(-5.0, GROUND_HEIGHT, 0.0), team: 1, ..player(3) };
+        assert!(!apply_gravity_wells(&mut far_enemy, &wells, 2, 0.5));
+    }
+
+    #[test]
+    fn xp_levels_the_player_up_every_xp_per_level() {
+        let mut earner = player(1);
+        award_xp(&mut earner, XP_PER_LEVEL - 1);
+        assert_eq!(earner.level, 1);
+        award_xp(&mut earner, 1);
+        assert_eq!(earner.level, 2);
+        award_xp(&mut earner, 2 * XP_PER_LEVEL);
+        assert_eq!((earner.xp, earner.level), (3 * XP_PER_LEVEL, 4));
+    }
+
+    #[test]
+    fn sprinting_targets_run_speed_and_walking_does_not() {
+        let config = config_logic::default_game_config();
+        let walk = InputState { forward: true, ..idle_input() };
+        let sprint = InputState { sprint: true, ..walk.clone() };
+
+        assert_eq!(target_speed(&idle_input(), &config), 0.0);
+        assert_eq!(target_speed(&walk, &config), config.player_speed);
+        assert!(!is_run_speed(target_speed(&walk, &config), &config));
+        assert!(is_run_speed(target_speed(&sprint, &config), &config));
+    }
+
+    #[test]
+    fn movement_speed_ramps_toward_the_target() {
+        let config = config_logic::default_game_config();
+        let mut runner = PlayerData {
+            input: InputState { forward: true, sprint: true, ..idle_input() },
+            is_moving: true,
+            ..player(1)
+        };
+
+        assert!(update_movement_speed(&mut runner, &config, 0.1));
+        assert!((runner.current_speed - MOVEMENT_ACCELERATION * 0.1).abs() < 1e-5);
+
+        runner.current_speed = config.player_speed * config.sprint_multiplier;
+        update_movement_speed(&mut runner, &config, 0.1);
+        assert!(runner.is_running);
+
+        runner.input = idle_input();
+        update_movement_speed(&mut runner, &config, 0.1);
+        let expected = config.player_speed * config.sprint_multiplier - MOVEMENT_DECELERATION * 0.1;
+        assert!((runner.current_speed - expected.max(0.0)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn respawning_restores_a_fresh_player_at_the_spawn() {
+        let mut fallen = PlayerData {
+            health: 0, mana: 5, vertical_velocity: -8.0, is_grounded: false, current_speed: 6.0, in_combat: true,
+            ..player(1)
+        };
+        let spawn = spawn_position(2);
+        respawn(&mut fallen, spawn.clone(), at_micros(3_000_000));
+
+        assert_eq!((fallen.health, fallen.mana), (fallen.max_health, fallen.max_mana));
+        assert!((fallen.position.x - spawn.x).abs() < 1e-5);
+        assert!((fallen.last_spawn_position.x - spawn.x).abs() < 1e-5);
+        assert!(fallen.is_grounded && !fallen.in_combat);
+        assert_eq!((fallen.vertical_velocity, fallen.current_speed), (0.0, 0.0));
+        assert_eq!(fallen.respawn_at, at_micros(3_000_000));
+    }
+
+    #[test]
+    fn spawns_skip_slots_camped_by_enemies() {
+        assert!((safest_spawn_position(&[], 1, 10.0).x - spawn_position(1).x).abs() < 1e-5);
+
+        let camper = spawn_position(1);
+        let chosen = safest_spawn_position(std::slice::from_ref(&camper), 1, 3.0);
+        assert!((chosen.x - spawn_position(2).x).abs() < 1e-5);
+
+        // Every slot is camped, so the one furthest from the enemy is used
+        let chosen = safest_spawn_position(&[camper], 1, 1000.0);
+        assert!((chosen.x - spawn_position(SPAWN_POINT_COUNT - 1).x).abs() < 1e-5);
+    }
+
+    #[test]
+    fn blocks_stop_hits_from_the_front_while_the_guard_is_up() {
+        let mut guard = player(1);
+        assert!(try_block(&mut guard, at_micros(0)));
+        assert!(!try_block(&mut guard, at_micros(BLOCK_WINDOW_MICROS - 1)));
+
+        let in_front = vec3(0.0, GROUND_HEIGHT, 5.0);
+        let behind = vec3(0.0, GROUND_HEIGHT, -5.0);
+        assert!(blocks_hit_from(&guard, &in_front, at_micros(BLOCK_WINDOW_MICROS - 1)));
+        assert!(!blocks_hit_from(&guard, &behind, at_micros(BLOCK_WINDOW_MICROS - 1)));
+        assert!(!blocks_hit_from(&guard, &in_front, at_micros(BLOCK_WINDOW_MICROS)));
+    }
+
+    #[test]
+    fn out_of_bounds_positions_are_clamped_back_to_the_edge() {
+        let mut stray = vec3(WORLD_HALF_EXTENT + 3.0, GROUND_HEIGHT - 1.0, -WORLD_HALF_EXTENT - 2.0);
+        assert!(is_out_of_bounds(&stray));
+        clamp_to_bounds(&mut stray);
+        assert!(!is_out_of_bounds(&stray));
+        assert_eq!((stray.x, stray.y, stray.z), (WORLD_HALF_EXTENT, GROUND_HEIGHT, -WORLD_HALF_EXTENT));
+    }
+
+    #[test]
+    fn only_known_out_of_bounds_modes_are_valid() {
+        assert!(is_valid_out_of_bounds_mode(OUT_OF_BOUNDS_CLAMP));
+        assert!(is_valid_out_of_bounds_mode(OUT_OF_BOUNDS_DAMAGE));
+        assert!(is_valid_out_of_bounds_mode(OUT_OF_BOUNDS_TELEPORT));
+        assert!(!is_valid_out_of_bounds_mode("kill"));
+    }
+
+    #[test]
+    fn smoothed_position_eases_small_moves_and_snaps_large_ones() {
+        let mut walker = PlayerData { position: vec3(1.0, GROUND_HEIGHT, 0.0), ..player(1) };
+        assert!(update_smoothed_position(&mut walker));
+        assert!((walker.smoothed_position.x - MOVEMENT_SMOOTHING_FACTOR).abs() < 1e-5);
+
+        let mut teleported = PlayerData {
+            position: vec3(MOVEMENT_SMOOTHING_SNAP_DISTANCE + 1.0, GROUND_HEIGHT, 0.0),
+            ..player(2)
+        };
+        assert!(update_smoothed_position(&mut teleported));
+        assert!((teleported.smoothed_position.x - teleported.position.x).abs() < 1e-5);
+
+        assert!(!update_smoothed_position(&mut player(3)));
+    }
+
+    #[test]
+    fn turns_are_capped_by_the_turn_rate_and_go_the_short_way() {
+        let facing = vec3(0.0, 0.0, 0.0);
+        assert!((clamp_turn(&facing, vec3(0.0, 3.0, 0.0), 0.0, 0.1).y - 3.0).abs() < 1e-5);
+        assert!((clamp_turn(&facing, vec3(0.0, 1.0, 0.0), 2.0, 0.1).y - 0.2).abs() < 1e-5);
+        assert!((clamp_turn(&facing, vec3(0.0, 0.1, 0.0), 2.0, 0.1).y - 0.1).abs() < 1e-5);
+        // 350 degrees to the left is 10 degrees to the right
+        let requested = std::f32::consts::TAU - 0.1;
+        assert!((clamp_turn(&facing, vec3(0.0, requested, 0.0), 2.0, 1.0).y + 0.1).abs() < 1e-5);
+    }
+
+    #[test]
+    fn knockback_pushes_away_from_the_origin_less_for_resistant_classes() {
+        let origin = vec3(0.0, 0.0, 0.0);
+        let position = vec3(3.0, 0.0, 4.0);
+
+        let offset = knockback_offset(&position, &origin, 5.0, 0.0);
+        assert!((offset.x - 3.0).abs() < 1e-5 && (offset.z - 4.0).abs() < 1e-5);
+
+        let paladin = class_logic::class_stats("paladin").knockback_resistance;
+        let resisted = knockback_offset(&position, &origin, 5.0, paladin);
+        assert!((resisted.z - 4.0 * (1.0 - paladin)).abs() < 1e-5);
+
+        assert_eq!(knockback_offset(&position, &origin, 5.0, 2.0).x, 0.0);
+        assert_eq!(knockback_offset(&origin, &origin, 5.0, 0.0).x, 0.0);
+    }
+
+    #[test]
+    fn only_logical_animation_states_are_known() {
+        assert!(is_known_animation("idle"));
+        assert!(is_known_animation("run-left"));
+        assert!(!is_known_animation("Idle"));
+        assert!(!is_known_animation("dance"));
+    }
+
+    #[test]
+    fn bind_points_are_used_only_while_in_bounds() {
+        assert!(usable_bind_point(&player(1)).is_none());
+
+        let bound = PlayerData { bound_position: Some(vec3(4.0, GROUND_HEIGHT, -2.0)), ..player(2) };
+        assert!(usable_bind_point(&bound).is_some_and(|p| (p.x - 4.0).abs() < 1e-5));
+
+        let outside = PlayerData { bound_position: Some(vec3(WORLD_HALF_EXTENT + 1.0, GROUND_HEIGHT, 0.0)), ..player(3) };
+        assert!(usable_bind_point(&outside).is_none());
+    }
+
+    #[test]
+    fn charges_stop_short_of_the_first_enemy() {
+        assert_eq!(charge_travel(None), CHARGE_DISTANCE);
+        assert!((charge_travel(Some(0.5)) - (CHARGE_DISTANCE * 0.5 - CHARGE_STOP_DISTANCE)).abs() < 1e-5);
+        // An enemy right in front means no movement, never a step backwards
+        assert_eq!(charge_travel(Some(0.0)), 0.0);
+    }
+
+    #[test]
+    fn animation_follows_the_client_priorities() {
+        let walk = InputState { forward: true, ..idle_input() };
+        assert_eq!(determine_animation(&idle_input(), true), "idle");
+        assert_eq!(determine_animation(&walk, false), "death");
+        assert_eq!(determine_animation(&InputState { attack: true, cast_spell: true, ..walk.clone() }, true), "attack1");
+        assert_eq!(determine_animation(&InputState { cast_spell: true, jump: true, ..walk.clone() }, true), "cast");
+        assert_eq!(determine_animation(&InputState { jump: true, ..walk.clone() }, true), "jump");
+        assert_eq!(determine_animation(&walk, true), "walk-forward");
+        assert_eq!(determine_animation(&InputState { sprint: true, backward: true, forward: false, ..walk.clone() }, true), "run-back");
+        // Forward/back win over strafing
+        assert_eq!(determine_animation(&InputState { left: true, ..walk.clone() }, true), "walk-forward");
+        assert_eq!(determine_animation(&InputState { forward: false, right: true, ..walk }, true), "walk-right");
+    }
+
+    #[test]
+    fn shared_resources_never_overfill_or_drain_the_giver() {
+        let mut giver = player(1);
+        let mut recipient = PlayerData { health: 70, mana: 95, ..player(2) };
+
+        assert_eq!(transfer_resource(&mut giver, &mut recipient, RESOURCE_MANA, 20), Ok(5));
+        assert_eq!((giver.mana, recipient.mana), (95, 100));
+
+        recipient.health = 10;
+        let available = giver.health - SHARE_RESOURCE_MIN_HEALTH;
+        assert_eq!(transfer_resource(&mut giver, &mut recipient, RESOURCE_HEALTH, 1_000), Ok(available.min(90)));
+        assert!(giver.health >= SHARE_RESOURCE_MIN_HEALTH);
+
+        assert!(transfer_resource(&mut giver, &mut recipient, "xp", 5).is_err());
+    }
+
+    #[test]
+    fn overlapping_players_are_pushed_apart_by_their_class_radii() {
+        let wizard = class_logic::class_stats("wizard").collision_radius;
+        let paladin = class_logic::class_stats("paladin").collision_radius;
+        let tank = PlayerData { collision_radius: paladin, ..player(2) };
+
+        let mut mover = PlayerData { collision_radius: wizard, position: vec3(0.2, GROUND_HEIGHT, 0.0), ..player(1) };
+        push_out_of(&mut mover, &tank);
+        assert!((mover.position.x - (wizard + paladin)).abs() < 1e-5);
+
+        // Already far enough apart, or exactly on top of each other: left alone
+        let mut clear = PlayerData { position: vec3(5.0, GROUND_HEIGHT, 0.0), ..player(3) };
+        push_out_of(&mut clear, &tank);
+        assert_eq!(clear.position.x, 5.0);
+        let mut stacked = player(4);
+        push_out_of(&mut stacked, &tank);
+        assert_eq!(stacked.position.x, 0.0);
+    }
+
+    #[test]
+    fn jump_pads_launch_grounded_players_standing_on_them() {
+        let pads = [JumpPad {
+            id: 1,
+            position: vec3(0.0, GROUND_HEIGHT, 0.0),
+            radius: 1.5,
+            boost: 12.0,
+            armed_at: at_micros(0),
+            expires_at: None,
+        }];
+
+        let mut on_pad = player(1);
+        assert!(apply_jump_pads(&mut on_pad, &pads));
+        assert_eq!(on_pad.vertical_velocity, 12.0);
+        assert!(!on_pad.is_grounded);
+        // Already airborne, so no second launch
+        assert!(!apply_jump_pads(&mut on_pad, &pads));
+
+        let mut beside = PlayerData { position: vec3(2.0, GROUND_HEIGHT, 0.0), ..player(2) };
+        assert!(!apply_jump_pads(&mut beside, &pads));
+    }
+
+    #[test]
+    fn idle_players_stop_counting_as_active_participants() {
+        let mut config = config_logic::default_game_config();
+        let idler = PlayerData { last_input_at: at_micros(1_000_000), ..player(1) };
+
+        config.afk_timeout_micros = 0;
+        assert!(is_active_participant(&idler, &config, at_micros(1_000_000_000)));
+
+        config.afk_timeout_micros = 30_000_000;
+        assert!(is_active_participant(&idler, &config, at_micros(30_999_999)));
+        assert!(!is_active_participant(&idler, &config, at_micros(31_000_000)));
+    }
+
+    #[test]
+    fn spawns_and_the_duel_arena_stand_on_the_floor() {
+        for slot in 0..SPAWN_POINT_COUNT {
+            assert_eq!(spawn_position(slot).y, GROUND_HEIGHT);
+        }
+        assert_eq!(crate::common::DUEL_ARENA_CENTER.y, GROUND_HEIGHT);
+        assert!(is_out_of_bounds(&vec3(0.0, GROUND_HEIGHT - 0.01, 0.0)));
+        assert!(!is_out_of_bounds(&vec3(0.0, GROUND_HEIGHT, 0.0)));
+    }
+
+    #[test]
+    fn haste_raises_speed_without_turning_a_walk_into_a_run() {
+        let config = config_logic::default_game_config();
+        let mut hasted = PlayerData {
+            input: InputState { forward: true, ..idle_input() },
+            is_moving: true,
+            speed_multiplier: 1.5,
+            current_speed: config.player_speed * 1.5,
+            ..player(1)
+        };
+        update_movement_speed(&mut hasted, &config, 0.1);
+        assert!((hasted.current_speed - config.player_speed * 1.5).abs() < 1e-5);
+        assert!(!hasted.is_running);
+    }
+
+    #[test]
+    fn portals_carry_players_to_the_other_end_then_cool_down() {
+        let portals = [Portal {
+            id: 1,
+            owner_identity: identity(9),
+            position_a: vec3(0.0, GROUND_HEIGHT, 0.0),
+            position_b: vec3(20.0, GROUND_HEIGHT, 0.0),
+            radius: 1.0,
+            expires_at: at_micros(60_000_000),
+        }];
+
+        let mut traveler = player(1);
+        assert!(apply_portals(&mut traveler, &portals, at_micros(0)));
+        assert!((traveler.position.x - 20.0).abs() < 1e-5);
+        assert!((traveler.smoothed_position.x - 20.0).abs() < 1e-5);
+
+        // Standing on the exit doesn't bounce them straight back
+        assert!(!apply_portals(&mut traveler, &portals, at_micros(PORTAL_COOLDOWN_MICROS - 1)));
+        assert!(apply_portals(&mut traveler, &portals, at_micros(PORTAL_COOLDOWN_MICROS)));
+        assert!(traveler.position.x.abs() < 1e-5);
+    }
+
+    #[test]
+    fn swap_requires_a_living_ally_in_range() {
+        let caster = player(1);
+        let ally = PlayerData { position: vec3(SWAP_POSITIONS_RANGE, GROUND_HEIGHT, 0.0), ..player(2) };
+        assert!(can_swap_positions(&caster, &ally, 2).is_ok());
+        assert!(can_swap_positions(&caster, &caster, 2).is_err());
+        assert!(can_swap_positions(&caster, &ally, 1).is_err());
+        assert!(can_swap_positions(&caster, &PlayerData { team: 1, ..ally.clone() }, 2).is_err());
+        assert!(can_swap_positions(&caster, &PlayerData { health: 0, ..ally.clone() }, 2).is_err());
+        let far = PlayerData { position: vec3(SWAP_POSITIONS_RANGE + 1.0, GROUND_HEIGHT, 0.0), ..ally };
+        assert!(can_swap_positions(&caster, &far, 2).is_err());
+    }
 }
@@ -0,0 +1,157 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - player_view_logic.rs
+ *
+ * This file maintains PlayerView, the public read-only projection of PlayerData.
+ * PlayerData itself is private: it holds internal state (input buffer, sequence
+ * numbers, cooldown timestamps) that clients don't need and that would otherwise be
+ * sent to every subscriber on every change.
+ *
+ * Key components:
+ *
+ * 1. Views:
+ *    - view_for: Builds a PlayerView row from the fields clients render
+ *    - Spawn privacy: with GameConfig.spawn_privacy_micros > 0, a player who (re)spawned
+ *      less than that long ago and is still within SPAWN_PRIVACY_RADIUS of their spawn
+ *      point is published at a position snapped to SPAWN_PRIVACY_GRID_SIZE, so enemies
+ *      can't camp the exact spawn. PlayerView is shared by every subscriber, so allies
+ *      see the coarse position too until the window ends or the player moves away
+ *    - sync_player_view: Upserts one player's view; skipped when nothing visible changed
+ *
+ * 2. Game Tick:
+ *    - refresh_player_views: Syncs every active player's view (picking up damage,
+ *      respawns and other server-side changes) and removes views of players who left
+ *
+ * When modifying:
+ *    - Only add fields to PlayerView that every client may see
+ *    - update_player_input syncs the mover's view immediately so movement isn't
+ *      held back until the next game tick
+ *
+ * Related files:
+ *    - lib.rs: PlayerView table; update_player_input and identity_disconnected
+ */
+
+use spacetimedb::{ReducerContext, Table, Timestamp};
+use crate::common::{Vector3, GROUND_HEIGHT, SPAWN_PRIVACY_RADIUS, SPAWN_PRIVACY_GRID_SIZE};
+use crate::{calculate_distance, config_logic, GameConfig, PlayerData, PlayerView};
+// Import table traits
+use crate::{player, player_view};
+
+fn view_for(player: &PlayerData, config: &GameConfig, now: Timestamp) -> PlayerView {
+    let position = if in_spawn_privacy(player, config, now) {
+        coarse_position(&player.position)
+    } else {
+        player.position.clone()
+    };
+    PlayerView {
+        identity: player.identity,
+        username: player.username.clone(),
+        character_class: player.character_class.clone(),
+        position,
+        rotation: player.rotation.clone(),
+        current_animation: player.current_animation.clone(),
+        health: player.health,
+        max_health: player.max_health,
+        mana: player.mana,
+        max_mana: player.max_mana,
+        color: player.color.clone(),
+        team: player.team,
+        title: player.title.clone(),
+    }
+}
+
+// Freshly spawned and still near the spawn point
+fn in_spawn_privacy(player: &PlayerData, config: &GameConfig, now: Timestamp) -> bool {
+    config.spawn_privacy_micros > 0
+        && player.health > 0
+        && now.to_micros_since_unix_epoch() - player.respawn_at.to_micros_since_unix_epoch() < config.spawn_privacy_micros
+        && calculate_distance(&player.position, &player.last_spawn_position) <= SPAWN_PRIVACY_RADIUS
+}
+
+fn coarse_position(position: &Vector3) -> Vector3 {
+    Vector3 {
+        x: (position.x / SPAWN_PRIVACY_GRID_SIZE).round() * SPAWN_PRIVACY_GRID_SIZE,
+        y: GROUND_HEIGHT,
+        z: (position.z / SPAWN_PRIVACY_GRID_SIZE).round() * SPAWN_PRIVACY_GRID_SIZE,
+    }
+}
+
+pub fn sync_player_view(ctx: &ReducerContext, player: &PlayerData) {
+    let config = config_logic::get_config(ctx);
+    upsert_view(ctx, view_for(player, &config, ctx.timestamp));
+}
+
+fn upsert_view(ctx: &ReducerContext, view: PlayerView) {
+    match ctx.db.player_view().identity().find(view.identity) {
+        Some(existing) if existing == view => {}
+        Some(_) => {
+            ctx.db.player_view().identity().update(view);
+        }
+        None => {
+            ctx.db.player_view().insert(view);
+        }
+    }
+}
+
+pub fn refresh_player_views(ctx: &ReducerContext) {
+    let config = config_logic::get_config(ctx);
+    for player in ctx.db.player().iter() {
+        upsert_view(ctx, view_for(&player, &config, ctx.timestamp));
+    }
+    let orphaned: Vec<_> = ctx.db.player_view().iter()
+        .filter(|view| ctx.db.player().identity().find(view.identity).is_none())
+        .map(|view| view.identity)
+        .collect();
+    for identity in orphaned {
+        ctx.db.player_view().identity().delete(identity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at_micros, player, vec3};
+
+    #[test]
+    fn views_copy_the_fields_clients_render() {
+        let mut config = config_logic::default_game_config();
+        config.spawn_privacy_micros = 0;
+        let fighter = PlayerData {
+            position: vec3(3.3, GROUND_HEIGHT, -1.7),
+            health: 42,
+            mana: 17,
+            title: "Champion".to_string(),
+            ..player(1)
+        };
+        let view = view_for(&fighter, &config, at_micros(0));
+
+        assert_eq!(view.identity, fighter.identity);
+        assert_eq!(view.position, fighter.position);
+        assert_eq!((view.health, view.max_health), (42, fighter.max_health));
+        assert_eq!((view.mana, view.max_mana), (17, fighter.max_mana));
+        assert_eq!(view.title, "Champion");
+    }
+
+    #[test]
+    fn fresh_spawns_are_published_on_a_coarse_grid() {
+        let mut config = config_logic::default_game_config();
+        config.spawn_privacy_micros = 3_000_000;
+        let spawned = PlayerData {
+            position: vec3(7.0, GROUND_HEIGHT + 0.5, -12.0),
+            last_spawn_position: vec3(7.0, GROUND_HEIGHT, -12.0),
+            respawn_at: at_micros(1_000_000),
+            ..player(1)
+        };
+
+        assert!(in_spawn_privacy(&spawned, &config, at_micros(3_999_999)));
+        let view = view_for(&spawned, &config, at_micros(2_000_000));
+        assert_eq!(view.position, vec3(0.0, GROUND_HEIGHT, -SPAWN_PRIVACY_GRID_SIZE));
+
+        // The window ends with time, or early once the player walks away from the spawn
+        assert!(!in_spawn_privacy(&spawned, &config, at_micros(4_000_000)));
+        let wandered = PlayerData { position: vec3(7.0 + SPAWN_PRIVACY_RADIUS + 1.0, GROUND_HEIGHT, -12.0), ..spawned.clone() };
+        assert!(!in_spawn_privacy(&wandered, &config, at_micros(2_000_000)));
+
+        config.spawn_privacy_micros = 0;
+        assert!(!in_spawn_privacy(&spawned, &config, at_micros(2_000_000)));
+    }
+}
@@ -0,0 +1,1034 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - projectile_logic.rs
+ *
+ * This file contains the projectile simulation run from projectile_tick.
+ * It's separated from lib.rs so combat systems can grow without bloating the reducers.
+ *
+ * Key components:
+ *
+ * 1. Projectile Update:
+ *    - update_projectiles: Moves projectiles toward their targets, handles expiry and hits.
+ *      Each branch (expire_projectile, update_homing/piercing/arcing_projectile) returns a
+ *      ProjectileStep instead of writing the table; deletes are applied first and a
+ *      projectile marked for deletion is never updated in the same tick.
+ *      Movement uses the real time since each projectile's last_moved_at, so `speed`
+ *      is always units per second regardless of PROJECTILE_TICK_MICROS
+ *    - lifetime_fraction: Share of a projectile's lifetime left (1.0 -> 0.0), stored on
+ *      the row whenever it moves so clients can fade projectiles without recomputing it
+ *    - update_piercing_projectile: Sweeps a piercing projectile's path, hitting each
+ *      player once (tracked in hit_identities) until max_pierce is reached
+ *    - apply_projectile_gravity: Every projectile falls under its own `gravity` (from
+ *      the spell's projectile_gravity, 0 = flat). Non-arcing projectiles that reach the
+ *      ground fizzle
+ *    - Direct hits are checked against lag_compensation_logic::hit_position, which
+ *      rewinds targets by the projectile's rewind_micros (0 unless compensation is on)
+ *    - update_homing_projectile/steer_toward: Homing projectiles turn toward their
+ *      target by homing_strength each update (1.0 = perfect tracking, lower = dodgeable)
+ *
+ *    - projectile_spawn_position: Spawn point offset ahead of and above the caster
+ *
+ *    - dummy_on_path/hit_dummy: Training dummies on a projectile's path absorb it;
+ *      detonations also damage dummies in their radius
+ *
+ *    - record_shot_hit: Counts a landed shot (once per projectile, and only when it actually
+ *      removed health) for the caster's accuracy; shots_fired is counted where projectiles
+ *      are created. Hits that PvP, duels, the damage cooldown or a dead target reject don't count
+ *
+ *    - throw_grenade/update_arcing_projectile: Thrown projectiles launched on an arc that
+ *      lands on the aimed point, falling under the GameConfig gravity at the time of the throw. They never hit directly,
+ *      they detonate (detonate: damage and knock back everyone in aoe_radius) on ground contact or when
+ *      the fuse (expires_at) runs out. GameConfig.self_aoe_damage decides whether the
+ *      caster is hurt by their own blast. Throws count against MAX_PROJECTILES_PER_CASTER
+ *      (projectiles_in_flight) like casts do
+ *
+ *    - caster_alive: With GameConfig.cull_dead_caster_projectiles on, projectiles whose
+ *      caster died or left are removed (no expiry effects or fragments). Off, they keep
+ *      flying and still credit the caster for hits and kills
+ *
+ *    - expire_projectile: When expires_at passes, projectiles with on_expire "explode"
+ *      detonate in their aoe_radius at their current position, "fizzle" ones just vanish
+ *
+ *    - fan_directions: Evenly spread directions around a heading, used for cluster
+ *      fragments and multi-projectile spells (SpellDefinition.projectile_count/spread_angle)
+ *
+ *    - cluster_fragments: Cluster projectiles (fragment_count > 0) burst into a fan of
+ *      short-lived fragments on hit or expiry. Fragments have fragment_count 0, so a
+ *      split never chains past one generation
+ *
+ *    - spawn_impact_effect/prune_impact_effects: ImpactEffect rows for client particles
+ *
+ * 2. Collision Layers:
+ *    - collision_layer_for_player: Classifies a player relative to the projectile's caster
+ *      (self, ally on the caster's team, or enemy)
+ *    - can_collide: Checks an entity's layer against a projectile's collision_mask
+ *    - Projectiles only interact with entities whose layer is set in their mask,
+ *      anything else is passed through
+ *
+ * Extension points:
+ *    - Add new entity kinds (structures, NPCs) by giving them a COLLISION_LAYER_* bit
+ *    - Spells can choose a mask at spawn, e.g. a heal that only collides with allies
+ *
+ * Related files:
+ *    - common.rs: Collision layer constants and Vector3
+ *    - lib.rs: ProjectileData table definition and cast_spell reducer
+ */
+
+use spacetimedb::{ReducerContext, Identity, Table, Timestamp};
+use std::collections::HashSet;
+use crate::common::{
+    Vector3, COLLISION_LAYER_SELF, COLLISION_LAYER_ALLY, COLLISION_LAYER_ENEMY, PROJECTILE_SPAWN_FORWARD_OFFSET,
+    PROJECTILE_SPAWN_HEIGHT_OFFSET, IMPACT_EFFECT_LIFETIME_MICROS, CLUSTER_FRAGMENT_SPREAD_RADIANS,
+    CLUSTER_FRAGMENT_SPEED, CLUSTER_FRAGMENT_LIFETIME_SECS, CLUSTER_FRAGMENT_DAMAGE, GRENADE_MAX_THROW_RANGE,
+    GRENADE_HORIZONTAL_SPEED, GRENADE_FUSE_SECS, GRENADE_AOE_RADIUS, GRENADE_DAMAGE, MAX_PROJECTILE_STEP_SECS,
+    EXPLOSION_KNOCKBACK_DISTANCE, GROUND_HEIGHT, MAX_PROJECTILES_PER_CASTER,
+};
+use crate::{
+    calculate_distance, combat_logic, config_logic, lag_compensation_logic, player_logic, spell_logic, status_logic,
+    team_logic, GameConfig, ImpactEffect, PlayerData, ProjectileData, TrainingDummy,
+};
+use crate::combat_logic::DamageOutcome;
+use crate::player_logic::{facing_direction, is_invulnerable};
+// Import table traits
+use crate::{impact_effect, player, projectile, training_dummy};
+
+pub const CLUSTER_FRAGMENT_TYPE: &str = "cluster_fragment";
+
+// Determine which collision layer a player belongs to from the caster's point of view
+pub fn collision_layer_for_player(caster_identity: Identity, caster_team: u32, team_count: u32, player: &PlayerData) -> u32 {
+    if player.identity == caster_identity {
+        COLLISION_LAYER_SELF
+    } else if team_logic::is_ally(team_count, caster_team, player.team) {
+        COLLISION_LAYER_ALLY
+    } else {
+        COLLISION_LAYER_ENEMY
+    }
+}
+
+// Check whether an entity on `layer` is part of the projectile's collision mask
+pub fn can_collide(collision_mask: u32, layer: u32) -> bool {
+    collision_mask & layer != 0
+}
+
+// Where a caster's projectile appears: ahead along their facing and slightly up,
+// so it doesn't start inside the caster or a wall they're touching
+pub fn projectile_spawn_position(caster: &PlayerData) -> Vector3 {
+    let facing = facing_direction(&caster.rotation);
+    Vector3 {
+        x: caster.position.x + facing.x * PROJECTILE_SPAWN_FORWARD_OFFSET,
+        y: caster.position.y + PROJECTILE_SPAWN_HEIGHT_OFFSET,
+        z: caster.position.z + facing.z * PROJECTILE_SPAWN_FORWARD_OFFSET,
+    }
+}
+
+// Seconds since the projectile last moved, capped at MAX_PROJECTILE_STEP_SECS so a stalled
+// schedule can't teleport projectiles through everything in one step
+fn projectile_step_secs(projectile: &ProjectileData, now: Timestamp) -> f32 {
+    let elapsed_micros = now.to_micros_since_unix_epoch() - projectile.last_moved_at.to_micros_since_unix_epoch();
+    (elapsed_micros.max(0) as f64 / 1_000_000.0).min(MAX_PROJECTILE_STEP_SECS as f64) as f32
+}
+
+// What a projectile's update decided. Branches never write the projectile table
+// themselves, update_projectiles applies every step once the loop is done.
+enum ProjectileStep {
+    Move(Box<ProjectileData>), // Still in flight, store the updated row
+    Remove, // Expired, hit or lost its target
+    Keep, // Nothing changed this tick
+}
+
+// Update all projectiles - move them toward targets and handle expiration
+pub fn update_projectiles(ctx: &ReducerContext) {
+    let current_time = ctx.timestamp;
+    // Deletes win: a projectile in this set is never updated again this tick,
+    // however many branches touched it
+    let mut projectiles_to_delete: HashSet<u64> = HashSet::new();
+    let mut projectiles_to_update: Vec<ProjectileData> = Vec::new();
+    let config = config_logic::get_config(ctx);
+    // Inserted after the loop so new fragments don't move in the tick they spawn
+    let mut fragments_to_spawn: Vec<ProjectileData> = Vec::new();
+
+    for projectile in ctx.db.projectile().iter() {
+        if projectiles_to_delete.contains(&projectile.id) {
+            continue;
+        }
+        let projectile_id = projectile.id;
+
+        // Debug: Log projectile lifetime info
+        let time_alive = (current_time.to_micros_since_unix_epoch() - projectile.created_at.to_micros_since_unix_epoch()) as f64 / 1_000_000.0;
+        let lifetime_fraction = lifetime_fraction(&projectile, current_time);
+
+        spacetimedb::log::info!(
+            "🚀 Projectile {} - Alive: {:.1}s, Remaining: {:.0}%",
+            projectile.id,
+            time_alive,
+            lifetime_fraction * 100.0
+        );
+
+        // Each projectile moves by the real time since it last moved, so speed stays in
+        // units per second whatever the tick interval is (or however late a tick fires)
+        let delta_time = projectile_step_secs(&projectile, current_time);
+
+        let cull = config.cull_dead_caster_projectiles
+            && !caster_alive(ctx.db.player().identity().find(projectile.caster_identity));
+        let step = if cull {
+            spacetimedb::log::info!("Projectile {} culled, its caster is dead", projectile.id);
+            ProjectileStep::Remove
+        } else if has_expired(&projectile, current_time) {
+            expire_projectile(ctx, &projectile, &config, &mut fragments_to_spawn);
+            spacetimedb::log::info!("⏰ Projectile {} EXPIRED after {:.1}s", projectile.id, time_alive);
+            ProjectileStep::Remove
+        } else if projectile.arcing {
+            update_arcing_projectile(ctx, projectile, delta_time, &config)
+        } else if projectile.piercing {
+            update_piercing_projectile(ctx, projectile, delta_time, config.team_count)
+        } else {
+            update_homing_projectile(ctx, projectile, delta_time, config.team_count, &mut fragments_to_spawn)
+        };
+
+        match step {
+            ProjectileStep::Move(mut updated) => {
+                // Arcing projectiles handle their own fall, anything else with gravity drops
+                // here and fizzles on reaching the ground
+                if !updated.arcing && apply_projectile_gravity(&mut updated, delta_time) {
+                    spawn_impact_effect(ctx, &updated.position, impact_kind(&updated.projectile_type, false));
+                    projectiles_to_delete.insert(projectile_id);
+                } else {
+                    updated.last_moved_at = current_time;
+                    updated.lifetime_fraction = lifetime_fraction;
+                    projectiles_to_update.push(*updated);
+                }
+            }
+            ProjectileStep::Remove => {
+                projectiles_to_delete.insert(projectile_id);
+            }
+            ProjectileStep::Keep => {}
+        }
+    }
+
+    // Clean up expired/hit projectiles, then store the ones still flying
+    for projectile_id in &projectiles_to_delete {
+        ctx.db.projectile().id().delete(*projectile_id);
+    }
+    for projectile in surviving_updates(projectiles_to_update, &projectiles_to_delete) {
+        ctx.db.projectile().id().update(projectile);
+    }
+
+    for fragment in fragments_to_spawn {
+        if let Err(e) = ctx.db.projectile().try_insert(fragment) {
+            spacetimedb::log::error!("Failed to create cluster fragment: {}", e);
+        }
+    }
+}
+
+// Updates for projectiles that weren't deleted this tick, whichever branch deleted them
+fn surviving_updates(updates: Vec<ProjectileData>, deleted: &HashSet<u64>) -> Vec<ProjectileData> {
+    updates.into_iter().filter(|projectile| !deleted.contains(&projectile.id)).collect()
+}
+
+// Projectiles (of any kind) this caster currently has in the air, for MAX_PROJECTILES_PER_CASTER
+pub fn projectiles_in_flight(ctx: &ReducerContext, caster_identity: Identity) -> usize {
+    ctx.db.projectile().iter().filter(|p| p.caster_identity == caster_identity).count()
+}
+
+// Whether the caster is still an active, living player
+fn caster_alive(caster: Option<PlayerData>) -> bool {
+    caster.is_some_and(|caster| caster.health > 0)
+}
+
+// Expiry is checked before any movement, so an expired projectile is removed without moving
+fn has_expired(projectile: &ProjectileData, now: Timestamp) -> bool {
+    now.to_micros_since_unix_epoch() >= projectile.expires_at.to_micros_since_unix_epoch()
+}
+
+// Share of the projectile's lifetime still left at `now`: 1.0 when created, 0.0 once expired
+pub fn lifetime_fraction(projectile: &ProjectileData, now: Timestamp) -> f32 {
+    let created = projectile.created_at.to_micros_since_unix_epoch();
+    let lifetime = projectile.expires_at.to_micros_since_unix_epoch() - created;
+    if lifetime <= 0 {
+        return 0.0;
+    }
+    let remaining = projectile.expires_at.to_micros_since_unix_epoch() - now.to_micros_since_unix_epoch();
+    (remaining as f64 / lifetime as f64).clamp(0.0, 1.0) as f32
+}
+
+// Lob a grenade from the caster toward `target`. The launch speed is picked so the arc
+// lands on the target point (within GRENADE_MAX_THROW_RANGE) under the current gravity.
+pub fn throw_grenade(ctx: &ReducerContext, target: Vector3) -> Result<(), String> {
+    let Some(caster) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can throw.".to_string());
+    };
+    if caster.health <= 0 {
+        return Err("Dead players cannot throw.".to_string());
+    }
+    // Grenades share the per-caster cap with spells
+    if projectiles_in_flight(ctx, caster.identity) >= MAX_PROJECTILES_PER_CASTER {
+        return Err("Too many projectiles in flight.".to_string());
+    }
+    let start = projectile_spawn_position(&caster);
+    let dx = target.x - start.x;
+    let dz = target.z - start.z;
+    let horizontal_distance = (dx * dx + dz * dz).sqrt();
+    if horizontal_distance > GRENADE_MAX_THROW_RANGE {
+        return Err("Throw target is out of range.".to_string());
+    }
+
+    let direction = if horizontal_distance > 0.01 {
+        Vector3 { x: dx / horizontal_distance, y: 0.0, z: dz / horizontal_distance }
+    } else {
+        facing_direction(&caster.rotation)
+    };
+    let gravity = config_logic::get_config(ctx).gravity;
+    let vertical_velocity = lob_vertical_velocity(start.y, target.y, horizontal_distance, gravity);
+
+    ctx.db.projectile().try_insert(ProjectileData {
+        id: 0, // auto_inc will set this
+        caster_identity: caster.identity,
+        position: start,
+        target_identity: caster.identity,
+        speed: spell_logic::clamp_projectile_speed(GRENADE_HORIZONTAL_SPEED),
+        created_at: ctx.timestamp,
+        last_moved_at: ctx.timestamp,
+        expires_at: spell_logic::projectile_expires_at(ctx.timestamp, GRENADE_FUSE_SECS),
+        projectile_type: "grenade".to_string(),
+        collision_mask: COLLISION_LAYER_ENEMY,
+        direction,
+        piercing: false,
+        max_pierce: 0,
+        hit_identities: Vec::new(),
+        hit_counted: false,
+        damage: GRENADE_DAMAGE,
+        status_effect: String::new(),
+        caster_team: caster.team,
+        caster_color: caster.color.clone(),
+        fragment_count: 0,
+        arcing: true,
+        vertical_velocity,
+        aoe_radius: GRENADE_AOE_RADIUS,
+        homing_strength: 0.0,
+        gravity,
+        on_expire: spell_logic::ON_EXPIRE_EXPLODE.to_string(),
+        rewind_micros: 0,
+        lifetime_fraction: 1.0,
+    }).map_err(|e| format!("Failed to throw grenade: {}", e))?;
+    spacetimedb::log::info!("Player {} threw a grenade.", caster.identity);
+    let mut caster = caster;
+    caster.shots_fired += 1;
+    ctx.db.player().identity().update(caster);
+    Ok(())
+}
+
+// On expiry "explode" projectiles detonate where they are (grenade fuses run out this way),
+// everything else fizzles. Cluster projectiles split either way.
+fn expire_projectile(ctx: &ReducerContext, projectile: &ProjectileData, config: &GameConfig, fragments_to_spawn: &mut Vec<ProjectileData>) {
+    if projectile.on_expire == spell_logic::ON_EXPIRE_EXPLODE && projectile.aoe_radius > 0.0 {
+        detonate(ctx, projectile, &projectile.position, config);
+    } else {
+        spawn_impact_effect(ctx, &projectile.position, impact_kind(&projectile.projectile_type, false));
+    }
+    fragments_to_spawn.extend(cluster_fragments(projectile, &projectile.position, ctx.timestamp));
+}
+
+// Homing projectiles chase their target and hit it once within 1 unit
+fn update_homing_projectile(
+    ctx: &ReducerContext,
+    projectile: ProjectileData,
+    delta_time: f32,
+    team_count: u32,
+    fragments_to_spawn: &mut Vec<ProjectileData>,
+) -> ProjectileStep {
+    let Some(target) = ctx.db.player().identity().find(projectile.target_identity) else {
+        spacetimedb::log::info!("👻 Projectile {} TARGET NO LONGER EXISTS (target_identity: {})", projectile.id, projectile.target_identity);
+        return ProjectileStep::Remove;
+    };
+
+    // Calculate distance to target, as the caster saw it when lag compensation is on
+    let hit_position = lag_compensation_logic::hit_position(ctx, &target, projectile.rewind_micros);
+    let distance = calculate_distance(&projectile.position, &hit_position);
+    let target_layer = collision_layer_for_player(projectile.caster_identity, projectile.caster_team, team_count, &target);
+
+    // Check if projectile reached target (within 1 unit) and is allowed to interact with it.
+    // Invulnerable targets are passed through, so a homing projectile can still hit after the roll.
+    if distance <= 1.0
+        && can_collide(projectile.collision_mask, target_layer)
+        && !is_invulnerable(&target, ctx.timestamp)
+    {
+        spacetimedb::log::info!("🎯 Projectile {} HIT target {} at distance {:.2}", projectile.id, target.identity, distance);
+        if apply_projectile_damage(ctx, &projectile, &target).applied > 0 {
+            record_shot_hit(ctx, &projectile);
+        }
+        spawn_impact_effect(ctx, &target.position, impact_kind(&projectile.projectile_type, true));
+        fragments_to_spawn.extend(cluster_fragments(&projectile, &target.position, ctx.timestamp));
+        return ProjectileStep::Remove;
+    }
+
+    // Turn toward the target by homing_strength, then move along the new direction
+    match direction_to(&projectile.position, &target.position) {
+        Some(target_direction) => {
+            let normalized_direction = steer_toward(&projectile.direction, &target_direction, projectile.homing_strength);
+            let movement_distance = projectile.speed * delta_time;
+            let end = advance(&projectile.position, &normalized_direction, movement_distance);
+            // A training dummy in the way absorbs the projectile
+            if let Some((_, dummy)) = dummy_on_path(ctx, &projectile, &projectile.position, &end) {
+                hit_dummy(ctx, &projectile, &dummy);
+                return ProjectileStep::Remove;
+            }
+            let mut updated_projectile = projectile;
+            updated_projectile.position = end;
+            updated_projectile.direction = normalized_direction;
+            ProjectileStep::Move(Box::new(updated_projectile))
+        }
+        None => ProjectileStep::Keep,
+    }
+}
+
+// Launch speed for a grenade thrown `horizontal_distance` away to land at `target_y`.
+// Solves y(t) = target_y for the flight time t at a fixed horizontal speed.
+fn lob_vertical_velocity(start_y: f32, target_y: f32, horizontal_distance: f32, gravity: f32) -> f32 {
+    let flight_time = (horizontal_distance / GRENADE_HORIZONTAL_SPEED).max(0.1);
+    (target_y - start_y + 0.5 * gravity * flight_time * flight_time) / flight_time
+}
+
+// Thrown projectiles fly along their arc and detonate on touching the ground
+fn update_arcing_projectile(
+    ctx: &ReducerContext,
+    mut projectile: ProjectileData,
+    delta_time: f32,
+    config: &GameConfig,
+) -> ProjectileStep {
+    projectile.position.x += projectile.direction.x * projectile.speed * delta_time;
+    projectile.position.z += projectile.direction.z * projectile.speed * delta_time;
+    if apply_projectile_gravity(&mut projectile, delta_time) {
+        detonate(ctx, &projectile, &projectile.position, config);
+        return ProjectileStep::Remove;
+    }
+    ProjectileStep::Move(Box::new(projectile))
+}
+
+// Vertical motion under the projectile's own gravity. Returns true once it reaches the
+// ground (matching the player ground height), where it's left resting.
+fn apply_projectile_gravity(projectile: &mut ProjectileData, delta_time: f32) -> bool {
+    if projectile.gravity <= 0.0 && projectile.vertical_velocity == 0.0 {
+        return false;
+    }
+    projectile.position.y += projectile.vertical_velocity * delta_time;
+    projectile.vertical_velocity -= projectile.gravity * delta_time;
+    if projectile.position.y <= GROUND_HEIGHT {
+        projectile.position.y = GROUND_HEIGHT;
+        return true;
+    }
+    false
+}
+
+// Whether a player takes a detonation's damage: inside aoe_radius and collidable, with
+// the caster only hurt when GameConfig.self_aoe_damage is on
+fn caught_in_blast(projectile: &ProjectileData, player: &PlayerData, position: &Vector3, config: &GameConfig) -> bool {
+    if calculate_distance(&player.position, position) > projectile.aoe_radius {
+        return false;
+    }
+    if player.identity == projectile.caster_identity {
+        return config.self_aoe_damage;
+    }
+    let layer = collision_layer_for_player(projectile.caster_identity, projectile.caster_team, config.team_count, player);
+    can_collide(projectile.collision_mask, layer)
+}
+
+// Area damage: every collidable player within aoe_radius of `position` takes the projectile's damage.
+// The caster is caught in their own blast only when GameConfig.self_aoe_damage is on.
+fn detonate(ctx: &ReducerContext, projectile: &ProjectileData, position: &Vector3, config: &GameConfig) {
+    spacetimedb::log::info!("💥 Projectile {} DETONATED", projectile.id);
+    let targets: Vec<PlayerData> = ctx.db.player().iter()
+        .filter(|p| caught_in_blast(projectile, p, position, config))
+        .collect();
+    if can_collide(projectile.collision_mask, COLLISION_LAYER_ENEMY) {
+        let dummies: Vec<u64> = ctx.db.training_dummy().iter()
+            .filter(|dummy| calculate_distance(&dummy.position, position) <= projectile.aoe_radius)
+            .map(|dummy| dummy.id)
+            .collect();
+        for dummy_id in dummies {
+            combat_logic::apply_dummy_damage(ctx, dummy_id, projectile.damage);
+        }
+    }
+    let mut hit_anyone = false;
+    for target in targets {
+        if target.identity == projectile.caster_identity {
+            let outcome = combat_logic::apply_damage(ctx, target.identity, projectile.damage, None, Some(position));
+            spacetimedb::log::info!("Projectile {} dealt {} self-damage to its caster", projectile.id, outcome.applied);
+        } else if apply_projectile_damage(ctx, projectile, &target).applied > 0 {
+            hit_anyone = true;
+            player_logic::apply_knockback(ctx, target.identity, position, EXPLOSION_KNOCKBACK_DISTANCE);
+        }
+    }
+    // One blast is one shot, however many it damaged
+    if hit_anyone {
+        record_shot_hit(ctx, projectile);
+    }
+    spawn_impact_effect(ctx, position, "explosion");
+}
+
+// Count a landed shot for the caster's accuracy. Cluster fragments aren't shots of
+// their own, their parent was already counted when it was cast.
+fn record_shot_hit(ctx: &ReducerContext, projectile: &ProjectileData) {
+    if !is_counted_shot(projectile) {
+        return;
+    }
+    if let Some(mut caster) = ctx.db.player().identity().find(projectile.caster_identity) {
+        caster.shots_hit += 1;
+        ctx.db.player().identity().update(caster);
+    }
+}
+
+fn is_counted_shot(projectile: &ProjectileData) -> bool {
+    projectile.projectile_type != CLUSTER_FRAGMENT_TYPE
+}
+
+// Fragments for a cluster projectile bursting at `position`, fanned evenly across
+// CLUSTER_FRAGMENT_SPREAD_RADIANS around its travel direction. Empty for projectiles
+// that don't split, which includes the fragments themselves.
+pub fn cluster_fragments(parent: &ProjectileData, position: &Vector3, now: Timestamp) -> Vec<ProjectileData> {
+    let count = parent.fragment_count;
+    if count == 0 {
+        return Vec::new();
+    }
+
+    let expires_at = spell_logic::projectile_expires_at(now, CLUSTER_FRAGMENT_LIFETIME_SECS);
+
+    fan_directions(&parent.direction, count, CLUSTER_FRAGMENT_SPREAD_RADIANS)
+        .into_iter()
+        .map(|direction| {
+            ProjectileData {
+                id: 0, // auto_inc will set this
+                caster_identity: parent.caster_identity,
+                position: position.clone(),
+                // Targeting the caster makes a piercing projectile fly straight along `direction`
+                target_identity: parent.caster_identity,
+                speed: spell_logic::clamp_projectile_speed(CLUSTER_FRAGMENT_SPEED),
+                created_at: now,
+                last_moved_at: now,
+                expires_at,
+                projectile_type: CLUSTER_FRAGMENT_TYPE.to_string(),
+                collision_mask: COLLISION_LAYER_ENEMY,
+                direction,
+                piercing: true,
+                max_pierce: 1, // Stops at the first enemy hit
+                hit_identities: Vec::new(),
+                hit_counted: false,
+                damage: CLUSTER_FRAGMENT_DAMAGE,
+                status_effect: String::new(),
+                caster_team: parent.caster_team,
+                caster_color: parent.caster_color.clone(),
+                fragment_count: 0,
+                arcing: false,
+                vertical_velocity: 0.0,
+                aoe_radius: 0.0,
+                homing_strength: 0.0,
+                gravity: 0.0,
+                on_expire: spell_logic::ON_EXPIRE_FIZZLE.to_string(),
+                rewind_micros: 0,
+                lifetime_fraction: 1.0,
+            }
+        })
+        .collect()
+}
+
+// `count` horizontal directions evenly fanned `spread` radians wide, centered on `direction`
+pub fn fan_directions(direction: &Vector3, count: u32, spread: f32) -> Vec<Vector3> {
+    let base_yaw = direction.x.atan2(direction.z);
+    let step = if count > 1 { spread / (count - 1) as f32 } else { 0.0 };
+    let first_yaw = base_yaw - step * count.saturating_sub(1) as f32 / 2.0;
+    (0..count)
+        .map(|i| {
+            let yaw = first_yaw + step * i as f32;
+            Vector3 { x: yaw.sin(), y: 0.0, z: yaw.cos() }
+        })
+        .collect()
+}
+
+// Piercing projectiles home on their target until they have passed through it, then fly straight.
+// Every player swept along this tick's path is hit once, front to back, until max_pierce is reached.
+fn update_piercing_projectile(
+    ctx: &ReducerContext,
+    mut projectile: ProjectileData,
+    delta_time: f32,
+    team_count: u32,
+) -> ProjectileStep {
+    let has_target = projectile.target_identity != projectile.caster_identity;
+    if has_target && !projectile.hit_identities.contains(&projectile.target_identity) {
+        if let Some(target) = ctx.db.player().identity().find(projectile.target_identity) {
+            if let Some(direction) = direction_to(&projectile.position, &target.position) {
+                projectile.direction = direction;
+            }
+        }
+    }
+
+    let start = projectile.position.clone();
+    let end = advance(&start, &projectile.direction, projectile.speed * delta_time);
+
+    // Collect the players on the path, ordered by how far along the path they are
+    let mut hits: Vec<(f32, PlayerData)> = Vec::new();
+    for player in ctx.db.player().iter() {
+        if projectile.hit_identities.contains(&player.identity) {
+            continue;
+        }
+        let layer = collision_layer_for_player(projectile.caster_identity, projectile.caster_team, team_count, &player);
+        if !can_collide(projectile.collision_mask, layer) || is_invulnerable(&player, ctx.timestamp) {
+            continue;
+        }
+        let hit_position = lag_compensation_logic::hit_position(ctx, &player, projectile.rewind_micros);
+        let (distance, along) = distance_to_segment(&hit_position, &start, &end);
+        if distance <= 1.0 {
+            hits.push((along, player));
+        }
+    }
+    hits.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    // A training dummy stops the bolt, so only players in front of it are reached
+    let dummy = dummy_on_path(ctx, &projectile, &start, &end);
+    if let Some((dummy_along, _)) = &dummy {
+        hits.retain(|(along, _)| along < dummy_along);
+    }
+
+    for (_, target) in hits {
+        spacetimedb::log::info!("🎯 Piercing projectile {} HIT target {}", projectile.id, target.identity);
+        let outcome = apply_projectile_damage(ctx, &projectile, &target);
+        if outcome.applied > 0 && !projectile.hit_counted {
+            // Only the first damaging hit counts, a shot hits or misses once however many it pierces
+            record_shot_hit(ctx, &projectile);
+            projectile.hit_counted = true;
+        }
+        projectile.hit_identities.push(target.identity);
+
+        if projectile.hit_identities.len() as u32 >= projectile.max_pierce {
+            // Out of pierces, stop at this target
+            spacetimedb::log::info!("Piercing projectile {} exhausted after {} hits", projectile.id, projectile.hit_identities.len());
+            spawn_impact_effect(ctx, &target.position, impact_kind(&projectile.projectile_type, true));
+            return ProjectileStep::Remove;
+        }
+    }
+
+    if let Some((_, dummy)) = dummy {
+        hit_dummy(ctx, &projectile, &dummy);
+        return ProjectileStep::Remove;
+    }
+
+    projectile.position = end;
+    ProjectileStep::Move(Box::new(projectile))
+}
+
+// Closest training dummy within 1 unit of the path start..end (and how far along the path it is),
+// if the projectile can hit enemies. Dummies count as enemies for everyone.
+fn dummy_on_path(ctx: &ReducerContext, projectile: &ProjectileData, start: &Vector3, end: &Vector3) -> Option<(f32, TrainingDummy)> {
+    if !can_collide(projectile.collision_mask, COLLISION_LAYER_ENEMY) {
+        return None;
+    }
+    ctx.db.training_dummy().iter()
+        .filter_map(|dummy| {
+            let (distance, along) = distance_to_segment(&dummy.position, start, end);
+            (distance <= 1.0).then_some((along, dummy))
+        })
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+}
+
+// Dummy hits never count towards accuracy, kills or score
+fn hit_dummy(ctx: &ReducerContext, projectile: &ProjectileData, dummy: &TrainingDummy) {
+    spacetimedb::log::info!("🎯 Projectile {} HIT training dummy {}", projectile.id, dummy.id);
+    combat_logic::apply_dummy_damage(ctx, dummy.id, projectile.damage);
+    spawn_impact_effect(ctx, &dummy.position, impact_kind(&projectile.projectile_type, true));
+}
+
+// Which client effect to play when a projectile of this type is removed
+pub fn impact_kind(projectile_type: &str, hit: bool) -> &'static str {
+    if !hit {
+        return "fizzle";
+    }
+    match projectile_type {
+        "piercing_bolt" => "spark",
+        _ => "explosion",
+    }
+}
+
+pub fn spawn_impact_effect(ctx: &ReducerContext, position: &Vector3, kind: &str) {
+    // Effects are cosmetic, a failed insert must never abort the projectile update
+    if let Err(e) = ctx.db.impact_effect().try_insert(ImpactEffect {
+        id: 0, // auto_inc will set this
+        position: position.clone(),
+        kind: kind.to_string(),
+        at: ctx.timestamp,
+    }) {
+        spacetimedb::log::error!("Failed to create impact effect: {}", e);
+    }
+}
+
+// Remove impact effects once clients have had time to spawn their particles
+pub fn prune_impact_effects(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let expired: Vec<u64> = ctx.db.impact_effect().iter()
+        .filter(|effect| now_micros - effect.at.to_micros_since_unix_epoch() >= IMPACT_EFFECT_LIFETIME_MICROS)
+        .map(|effect| effect.id)
+        .collect();
+    for effect_id in expired {
+        ctx.db.impact_effect().id().delete(effect_id);
+    }
+}
+
+// Apply a projectile's damage and on-hit status to a target (the caster never damages themselves)
+fn apply_projectile_damage(ctx: &ReducerContext, projectile: &ProjectileData, target: &PlayerData) -> DamageOutcome {
+    if target.identity == projectile.caster_identity {
+        spacetimedb::log::info!("Projectile {} hit caster {} - no self-damage", projectile.id, target.identity);
+        return DamageOutcome::none();
+    }
+
+    let outcome = combat_logic::apply_damage(
+        ctx,
+        target.identity,
+        projectile.damage,
+        Some(projectile.caster_identity),
+        Some(&projectile.position),
+    );
+    spacetimedb::log::info!(
+        "Projectile {} dealt {} damage to player {}",
+        projectile.id,
+        outcome.applied,
+        target.identity
+    );
+    if !outcome.killed {
+        status_logic::apply_status_effect(ctx, &projectile.status_effect, target.identity, projectile.caster_identity);
+    }
+    outcome
+}
+
+// Blend the current travel direction toward the target's by `strength` (0 keeps flying
+// straight, 1 points straight at the target). Falls back to the target direction when
+// there's no usable current direction or the blend cancels out.
+pub fn steer_toward(current: &Vector3, desired: &Vector3, strength: f32) -> Vector3 {
+    let strength = strength.clamp(0.0, 1.0);
+    let blended = Vector3 {
+        x: current.x + (desired.x - current.x) * strength,
+        y: current.y + (desired.y - current.y) * strength,
+        z: current.z + (desired.z - current.z) * strength,
+    };
+    let length = (blended.x * blended.x + blended.y * blended.y + blended.z * blended.z).sqrt();
+    if length < 0.001 {
+        return desired.clone();
+    }
+    Vector3 { x: blended.x / length, y: blended.y / length, z: blended.z / length }
+}
+
+// Normalized direction from one point to another, or None if they're (nearly) the same point
+fn direction_to(from: &Vector3, to: &Vector3) -> Option<Vector3> {
+    let direction = Vector3 {
+        x: to.x - from.x,
+        y: to.y - from.y,
+        z: to.z - from.z,
+    };
+    let magnitude = (direction.x * direction.x + direction.y * direction.y + direction.z * direction.z).sqrt();
+    if magnitude > 0.01 {
+        Some(Vector3 {
+            x: direction.x / magnitude,
+            y: direction.y / magnitude,
+            z: direction.z / magnitude,
+        })
+    } else {
+        None
+    }
+}
+
+// Move a point along a (normalized) direction
+fn advance(position: &Vector3, direction: &Vector3, distance: f32) -> Vector3 {
+    Vector3 {
+        x: position.x + direction.x * distance,
+        y: position.y + direction.y * distance,
+        z: position.z + direction.z * distance,
+    }
+}
+
+// Distance from a point to the segment start..end, and how far along the segment (0..1) the closest point is
+pub fn distance_to_segment(point: &Vector3, start: &Vector3, end: &Vector3) -> (f32, f32) {
+    let seg = Vector3 { x: end.x - start.x, y: end.y - start.y, z: end.z - start.z };
+    let length_sq = seg.x * seg.x + seg.y * seg.y + seg.z * seg.z;
+    if length_sq < 0.0001 {
+        return (calculate_distance(point, start), 0.0);
+    }
+    let t = (((point.x - start.x) * seg.x + (point.y - start.y) * seg.y + (point.z - start.z) * seg.z) / length_sq)
+        .clamp(0.0, 1.0);
+    let closest = Vector3 { x: start.x + seg.x * t, y: start.y + seg.y * t, z: start.z + seg.z * t };
+    (calculate_distance(point, &closest), t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{COLLISION_MASK_DEFAULT, GAME_TICK_MICROS, PROJECTILE_TICK_MICROS};
+    use crate::test_support::{at_micros, player, projectile, vec3};
+
+    #[test]
+    fn players_are_layered_relative_to_the_caster() {
+        let caster = player(1);
+        let teammate = PlayerData { team: 0, ..player(2) };
+        let opponent = PlayerData { team: 1, ..player(3) };
+
+        assert_eq!(collision_layer_for_player(caster.identity, 0, 2, &caster), COLLISION_LAYER_SELF);
+        assert_eq!(collision_layer_for_player(caster.identity, 0, 2, &teammate), COLLISION_LAYER_ALLY);
+        assert_eq!(collision_layer_for_player(caster.identity, 0, 2, &opponent), COLLISION_LAYER_ENEMY);
+        // Free-for-all has no allies, even on the same team id
+        assert_eq!(collision_layer_for_player(caster.identity, 0, 1, &teammate), COLLISION_LAYER_ENEMY);
+    }
+
+    #[test]
+    fn projectiles_only_collide_with_layers_in_their_mask() {
+        assert!(can_collide(COLLISION_MASK_DEFAULT, COLLISION_LAYER_ENEMY));
+        assert!(can_collide(COLLISION_MASK_DEFAULT, COLLISION_LAYER_SELF));
+        assert!(!can_collide(COLLISION_MASK_DEFAULT, COLLISION_LAYER_ALLY));
+
+        let heal_mask = COLLISION_LAYER_ALLY;
+        assert!(can_collide(heal_mask, COLLISION_LAYER_ALLY));
+        assert!(!can_collide(heal_mask, COLLISION_LAYER_ENEMY));
+        assert!(!can_collide(0, COLLISION_LAYER_SELF));
+    }
+
+    #[test]
+    fn segment_distance_uses_the_closest_point_on_the_segment() {
+        let start = vec3(0.0, 0.0, 0.0);
+        let end = vec3(0.0, 0.0, 10.0);
+
+        let (distance, t) = distance_to_segment(&vec3(1.0, 0.0, 4.0), &start, &end);
+        assert!((distance - 1.0).abs() < 1e-5);
+        assert!((t - 0.4).abs() < 1e-5);
+
+        // Past the end the closest point is the end itself
+        let (distance, t) = distance_to_segment(&vec3(0.0, 0.0, 13.0), &start, &end);
+        assert!((distance - 3.0).abs() < 1e-5);
+        assert_eq!(t, 1.0);
+    }
+
+    #[test]
+    fn direction_to_is_normalized_and_none_for_the_same_point() {
+        let direction = direction_to(&vec3(1.0, 0.0, 1.0), &vec3(4.0, 0.0, 5.0)).unwrap();
+        assert!((direction.x - 0.6).abs() < 1e-5);
+        assert!((direction.z - 0.8).abs() < 1e-5);
+        assert!(direction_to(&vec3(1.0, 2.0, 3.0), &vec3(1.0, 2.0, 3.0)).is_none());
+
+        assert_eq!(advance(&vec3(1.0, 0.0, 1.0), &direction, 5.0), vec3(4.0, 0.0, 5.0));
+    }
+
+    #[test]
+    fn projectiles_spawn_ahead_of_and_above_the_caster() {
+        let caster = PlayerData { position: vec3(2.0, GROUND_HEIGHT, 3.0), rotation: vec3(0.0, 0.0, 0.0), ..player(1) };
+        let spawn = projectile_spawn_position(&caster);
+        assert!((spawn.x - 2.0).abs() < 1e-5);
+        assert!((spawn.z - (3.0 + PROJECTILE_SPAWN_FORWARD_OFFSET)).abs() < 1e-5);
+        assert!((spawn.y - (GROUND_HEIGHT + PROJECTILE_SPAWN_HEIGHT_OFFSET)).abs() < 1e-5);
+
+        // Turned around, the offset follows the facing
+        let turned = PlayerData { rotation: vec3(0.0, std::f32::consts::PI, 0.0), ..caster };
+        assert!((projectile_spawn_position(&turned).z - (3.0 - PROJECTILE_SPAWN_FORWARD_OFFSET)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn impact_kind_depends_on_hit_and_projectile_type() {
+        assert_eq!(impact_kind("homing_sphere", true), "explosion");
+        assert_eq!(impact_kind("piercing_bolt", true), "spark");
+        assert_eq!(impact_kind("piercing_bolt", false), "fizzle");
+        assert_eq!(impact_kind("homing_sphere", false), "fizzle");
+    }
+
+    #[test]
+    fn cluster_projectiles_split_into_fragments_that_do_not_split() {
+        assert!(cluster_fragments(&projectile(1, 2), &vec3(0.0, 1.0, 0.0), at_micros(0)).is_empty());
+
+        let cluster = ProjectileData { fragment_count: 3, ..projectile(1, 2) };
+        let fragments = cluster_fragments(&cluster, &vec3(4.0, 1.0, 0.0), at_micros(0));
+        assert_eq!(fragments.len(), 3);
+        for fragment in &fragments {
+            assert_eq!(fragment.fragment_count, 0);
+            assert_eq!(fragment.projectile_type, CLUSTER_FRAGMENT_TYPE);
+            assert!((fragment.position.x - 4.0).abs() < 1e-5);
+            assert!(cluster_fragments(fragment, &fragment.position, at_micros(0)).is_empty());
+        }
+    }
+
+    #[test]
+    fn lobbed_grenades_come_down_at_the_target_height() {
+        let (start_y, target_y, distance, gravity) = (1.5, GROUND_HEIGHT, 12.0, 20.0);
+        let vertical_velocity = lob_vertical_velocity(start_y, target_y, distance, gravity);
+        let flight_time = distance / GRENADE_HORIZONTAL_SPEED;
+        let landing_y = start_y + vertical_velocity * flight_time - 0.5 * gravity * flight_time * flight_time;
+        assert!((landing_y - target_y).abs() < 1e-3);
+        assert!(vertical_velocity > 0.0);
+    }
+
+    #[test]
+    fn blasts_only_hurt_their_caster_when_self_damage_is_on() {
+        let grenade = ProjectileData { aoe_radius: 3.0, collision_mask: COLLISION_LAYER_ENEMY, ..projectile(1, 1) };
+        let caster = player(1);
+        let enemy = player(2);
+        let distant = PlayerData { position: vec3(10.0, GROUND_HEIGHT, 0.0), ..player(3) };
+        let center = vec3(0.0, GROUND_HEIGHT, 0.0);
+        let mut config = config_logic::default_game_config();
+
+        config.self_aoe_damage = false;
+        assert!(!caught_in_blast(&grenade, &caster, &center, &config));
+        assert!(caught_in_blast(&grenade, &enemy, &center, &config));
+        assert!(!caught_in_blast(&grenade, &distant, &center, &config));
+
+        config.self_aoe_damage = true;
+        assert!(caught_in_blast(&grenade, &caster, &center, &config));
+    }
+
+    #[test]
+    fn fragments_do_not_count_as_shots_of_their_own() {
+        let cluster = ProjectileData { fragment_count: 2, ..projectile(1, 2) };
+        assert!(is_counted_shot(&cluster));
+        for fragment in cluster_fragments(&cluster, &cluster.position, at_micros(0)) {
+            assert!(!is_counted_shot(&fragment));
+        }
+    }
+
+    #[test]
+    fn projectile_steps_cover_the_real_time_since_the_last_move() {
+        let bolt = ProjectileData { last_moved_at: at_micros(1_000_000), ..projectile(1, 2) };
+        assert!((projectile_step_secs(&bolt, at_micros(1_050_000)) - 0.05).abs() < 1e-5);
+        assert_eq!(projectile_step_secs(&bolt, at_micros(900_000)), 0.0);
+        assert_eq!(projectile_step_secs(&bolt, at_micros(60_000_000)), MAX_PROJECTILE_STEP_SECS);
+    }
+
+    #[test]
+    fn partial_homing_turns_only_part_of_the_way() {
+        let ahead = vec3(0.0, 0.0, 1.0);
+        let right = vec3(1.0, 0.0, 0.0);
+
+        let straight = steer_toward(&ahead, &right, 0.0);
+        assert!((straight.z - 1.0).abs() < 1e-5);
+        let perfect = steer_toward(&ahead, &right, 1.0);
+        assert!((perfect.x - 1.0).abs() < 1e-5);
+
+        let half = steer_toward(&ahead, &right, 0.5);
+        assert!((half.x - half.z).abs() < 1e-5);
+        assert!((half.x * half.x + half.z * half.z - 1.0).abs() < 1e-5);
+
+        // Turning straight around cancels out, so it snaps to the target direction
+        let behind = vec3(0.0, 0.0, -1.0);
+        assert!((steer_toward(&ahead, &behind, 0.5).z + 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn projectile_gravity_drops_the_projectile_until_it_lands() {
+        let mut flat = projectile(1, 2);
+        assert!(!apply_projectile_gravity(&mut flat, 0.1));
+        assert_eq!(flat.position.y, GROUND_HEIGHT);
+
+        let mut lob = ProjectileData {
+            position: vec3(0.0, GROUND_HEIGHT + 1.0, 0.0),
+            vertical_velocity: 2.0,
+            gravity: 10.0,
+            ..projectile(1, 2)
+        };
+        assert!(!apply_projectile_gravity(&mut lob, 0.1));
+        assert!((lob.position.y - (GROUND_HEIGHT + 1.2)).abs() < 1e-5);
+        assert!((lob.vertical_velocity - 1.0).abs() < 1e-5);
+
+        lob.vertical_velocity = -20.0;
+        assert!(apply_projectile_gravity(&mut lob, 0.1));
+        assert_eq!(lob.position.y, GROUND_HEIGHT);
+    }
+
+    #[test]
+    fn fragments_keep_their_caster_color_and_team() {
+        let cluster = ProjectileData {
+            fragment_count: 2,
+            caster_color: "#ff8800".to_string(),
+            caster_team: 2,
+            ..projectile(1, 2)
+        };
+        for fragment in cluster_fragments(&cluster, &cluster.position, at_micros(0)) {
+            assert_eq!(fragment.caster_color, "#ff8800");
+            assert_eq!(fragment.caster_team, 2);
+        }
+    }
+
+    #[test]
+    fn volleys_fan_evenly_around_the_aim_direction() {
+        let ahead = vec3(0.0, 0.0, 1.0);
+        let single = fan_directions(&ahead, 1, 1.0);
+        assert_eq!(single.len(), 1);
+        assert!((single[0].z - 1.0).abs() < 1e-5);
+
+        let spread = std::f32::consts::FRAC_PI_2;
+        let volley = fan_directions(&ahead, 3, spread);
+        assert_eq!(volley.len(), 3);
+        let yaws: Vec<f32> = volley.iter().map(|d| d.x.atan2(d.z)).collect();
+        assert!((yaws[0] + spread / 2.0).abs() < 1e-5);
+        assert!(yaws[1].abs() < 1e-5);
+        assert!((yaws[2] - spread / 2.0).abs() < 1e-5);
+        assert!(volley.iter().all(|d| d.y == 0.0));
+    }
+
+    #[test]
+    fn lifetime_fraction_runs_from_one_down_to_zero() {
+        let bolt = projectile(1, 2);
+        assert_eq!(lifetime_fraction(&bolt, at_micros(0)), 1.0);
+        assert!((lifetime_fraction(&bolt, at_micros(250_000)) - 0.75).abs() < 1e-5);
+        assert_eq!(lifetime_fraction(&bolt, at_micros(2_000_000)), 0.0);
+
+        let instant = ProjectileData { expires_at: at_micros(0), ..projectile(1, 2) };
+        assert_eq!(lifetime_fraction(&instant, at_micros(0)), 0.0);
+    }
+
+    #[test]
+    fn only_living_casters_keep_their_projectiles() {
+        assert!(caster_alive(Some(player(1))));
+        assert!(!caster_alive(Some(PlayerData { health: 0, ..player(1) })));
+        assert!(!caster_alive(None));
+    }
+
+    #[test]
+    fn projectile_expiring_and_hitting_in_one_tick_is_deleted_once() {
+        let mut deleted = HashSet::new();
+        deleted.insert(1); // Expired
+        deleted.insert(1); // Hit
+        assert_eq!(deleted.len(), 1);
+        let other = ProjectileData { id: 2, ..projectile(1, 2) };
+        let survivors = surviving_updates(vec![projectile(1, 2), other], &deleted);
+        assert_eq!(survivors.iter().map(|p| p.id).collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn expired_projectile_is_removed_instead_of_moved() {
+        let flying = projectile(1, 2);
+        assert!(!has_expired(&flying, at_micros(999_999)));
+        assert!(has_expired(&flying, flying.expires_at));
+
+        // Even if a branch had stored a moved row, the removal wins
+        let mut deleted = HashSet::new();
+        deleted.insert(flying.id);
+        let moved = ProjectileData { position: vec3(0.0, GROUND_HEIGHT, 1.0), ..flying };
+        assert!(surviving_updates(vec![moved], &deleted).is_empty());
+    }
+
+    // The auto_inc sequence lives in SpacetimeDB and can't be advanced from a unit test, so
+    // this checks the tick bookkeeping with ids at the top of the u64 range instead. Ids are
+    // only compared for equality, never ordered or added to.
+    #[test]
+    fn tick_bookkeeping_handles_ids_near_the_sequence_limit() {
+        let mut deleted = HashSet::new();
+        deleted.insert(u64::MAX - 1);
+        let updates = vec![
+            ProjectileData { id: u64::MAX - 1, ..projectile(1, 2) },
+            ProjectileData { id: u64::MAX, ..projectile(1, 2) },
+            ProjectileData { id: 1, ..projectile(1, 2) },
+        ];
+        let survivors = surviving_updates(updates, &deleted);
+        assert_eq!(survivors.iter().map(|p| p.id).collect::<Vec<_>>(), vec![u64::MAX, 1]);
+    }
+
+    #[test]
+    fn projectiles_step_on_the_projectile_tick() {
+        let flying = projectile(1, 2);
+        let start = flying.last_moved_at.to_micros_since_unix_epoch();
+        let step = projectile_step_secs(&flying, at_micros(start + PROJECTILE_TICK_MICROS));
+        assert!((step - PROJECTILE_TICK_MICROS as f32 / 1_000_000.0).abs() < 1e-5);
+
+        // game_tick doesn't move projectiles, so a full game tick's flight is covered by
+        // this many shorter projectile steps
+        let steps_per_game_tick = GAME_TICK_MICROS / PROJECTILE_TICK_MICROS;
+        assert!(steps_per_game_tick > 1);
+        assert_eq!(steps_per_game_tick * PROJECTILE_TICK_MICROS, GAME_TICK_MICROS);
+    }
+}
@@ -0,0 +1,110 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - reveal_logic.rs
+ *
+ * This file contains the taunt emote and the reveals it creates: enemies near the
+ * taunter are marked as revealed to the taunter's side for a few seconds.
+ *
+ * Key components:
+ *
+ * 1. Taunt:
+ *    - taunt: Reveals every living enemy within TAUNT_RADIUS of the caller for
+ *      REVEAL_DURATION_MICROS. Taunting again refreshes existing reveals instead of
+ *      stacking rows. Clients play the taunt emote on the taunter when new Reveal
+ *      rows with them as taunter appear
+ *
+ * 2. Game Tick:
+ *    - update_reveals: Removes expired reveals and reveals of players who left
+ *
+ * Related files:
+ *    - lib.rs: Reveal table and taunt reducer
+ *    - team_logic.rs: Ally checks (in free-for-all a reveal is only for the taunter)
+ */
+
+use spacetimedb::{ReducerContext, Table, Timestamp};
+use crate::common::{REVEAL_DURATION_MICROS, TAUNT_RADIUS};
+use crate::{calculate_distance, config_logic, team_logic, PlayerData, Reveal};
+// Import table traits
+use crate::{player, reveal};
+
+pub fn taunt(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(taunter) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can taunt.".to_string());
+    };
+    if taunter.health <= 0 {
+        return Err("Dead players cannot taunt.".to_string());
+    }
+
+    let team_count = config_logic::get_config(ctx).team_count;
+    let expires_at = Timestamp::from_micros_since_unix_epoch(
+        ctx.timestamp.to_micros_since_unix_epoch() + REVEAL_DURATION_MICROS
+    );
+    let enemies: Vec<_> = ctx.db.player().iter()
+        .filter(|p| revealed_by(&taunter, p, team_count))
+        .map(|p| p.identity)
+        .collect();
+
+    for revealed in &enemies {
+        let existing = ctx.db.reveal().iter()
+            .find(|r| r.taunter == taunter.identity && r.revealed == *revealed);
+        match existing {
+            Some(mut reveal) => {
+                reveal.expires_at = expires_at;
+                ctx.db.reveal().id().update(reveal);
+            }
+            None => {
+                ctx.db.reveal().try_insert(Reveal {
+                    id: 0, // auto_inc will set this
+                    revealed: *revealed,
+                    taunter: taunter.identity,
+                    team: taunter.team,
+                    expires_at,
+                }).map_err(|e| format!("Failed to reveal player: {}", e))?;
+            }
+        }
+    }
+
+    spacetimedb::log::info!("Player {} taunted, revealing {} enemies.", taunter.identity, enemies.len());
+    Ok(())
+}
+
+// Living enemies of the taunter within TAUNT_RADIUS get revealed
+fn revealed_by(taunter: &PlayerData, player: &PlayerData, team_count: u32) -> bool {
+    player.identity != taunter.identity
+        && player.health > 0
+        && !team_logic::is_ally(team_count, taunter.team, player.team)
+        && calculate_distance(&player.position, &taunter.position) <= TAUNT_RADIUS
+}
+
+pub fn update_reveals(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let stale: Vec<u64> = ctx.db.reveal().iter()
+        .filter(|r| {
+            now_micros >= r.expires_at.to_micros_since_unix_epoch()
+                || ctx.db.player().identity().find(r.revealed).is_none()
+        })
+        .map(|r| r.id)
+        .collect();
+    for id in stale {
+        ctx.db.reveal().id().delete(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::GROUND_HEIGHT;
+    use crate::test_support::{player, vec3};
+
+    #[test]
+    fn taunts_reveal_nearby_living_enemies() {
+        let taunter = PlayerData { team: 1, ..player(1) };
+        let enemy = PlayerData { team: 2, ..player(2) };
+
+        assert!(revealed_by(&taunter, &enemy, 2));
+        assert!(!revealed_by(&taunter, &taunter, 2));
+        assert!(!revealed_by(&taunter, &PlayerData { team: 1, ..player(3) }, 2));
+        assert!(!revealed_by(&taunter, &PlayerData { health: 0, ..enemy.clone() }, 2));
+        let far = PlayerData { position: vec3(TAUNT_RADIUS + 1.0, GROUND_HEIGHT, 0.0), ..enemy.clone() };
+        assert!(!revealed_by(&taunter, &far, 2));
+    }
+}
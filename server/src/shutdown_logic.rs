@@ -0,0 +1,128 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - shutdown_logic.rs
+ *
+ * This file contains the planned-restart flow: flushing every active player to
+ * persistent storage and closing the server to new joins.
+ *
+ * Key components:
+ *
+ * 1. Shutdown:
+ *    - graceful_shutdown: Logs out every active player through the same path as a
+ *      disconnect (progression lands in LoggedOutPlayerData), clears transient
+ *      tables and sets MatchState.accepting_joins to false
+ *    - shutdown_counts: How many players are persisted and how many bots just removed
+ *    - clear_transient_tables: Removes projectiles, effects, placed objects, duels
+ *      and other per-session rows that make no sense after a restart
+ *
+ * 2. Reopening:
+ *    - reopen_server: Accepts joins again. MatchState lives in the database, so
+ *      a restarted module stays closed until an admin calls this
+ *    - accepting_joins: Checked by registration, auto-rejoin and queue admission
+ *      (joins_open: open unless a MatchState row says otherwise)
+ *
+ * Related files:
+ *    - lib.rs: MatchState table, logout_player, and the reducers wrapping these
+ */
+
+use spacetimedb::{ReducerContext, Table, Timestamp};
+use crate::{logout_player, MatchState, PlayerData};
+// Import table traits
+use crate::{
+    bot_state, duel, gravity_well, ground_effect, impact_effect, join_queue, look_target, loot_drop, match_state, mine,
+    player, portal, position_history, projectile, reveal, status_effect, totem, voice_proximity, vote_kick,
+};
+
+// MatchState is a single-row table keyed by this id
+pub const MATCH_STATE_ID: u32 = 0;
+
+pub fn accepting_joins(ctx: &ReducerContext) -> bool {
+    joins_open(ctx.db.match_state().id().find(MATCH_STATE_ID).as_ref())
+}
+
+// A server that was never shut down has no MatchState row and is open
+fn joins_open(state: Option<&MatchState>) -> bool {
+    state.is_none_or(|state| state.accepting_joins)
+}
+
+fn match_state(accepting_joins: bool, now: Timestamp) -> MatchState {
+    MatchState { id: MATCH_STATE_ID, accepting_joins, changed_at: now }
+}
+
+fn set_accepting_joins(ctx: &ReducerContext, accepting_joins: bool) {
+    let state = match_state(accepting_joins, ctx.timestamp);
+    if ctx.db.match_state().id().find(MATCH_STATE_ID).is_some() {
+        ctx.db.match_state().id().update(state);
+    } else {
+        ctx.db.match_state().insert(state);
+    }
+}
+
+pub fn graceful_shutdown(ctx: &ReducerContext) {
+    set_accepting_joins(ctx, false);
+
+    let players: Vec<PlayerData> = ctx.db.player().iter().collect();
+    let (persisted, bots) = shutdown_counts(&players);
+    for player in players {
+        logout_player(ctx, player);
+    }
+    clear_transient_tables(ctx);
+
+    spacetimedb::log::info!(
+        "Graceful shutdown by {}: persisted {} players, removed {} bots, joins closed.",
+        ctx.sender,
+        persisted,
+        bots
+    );
+}
+
+// Players logout_player saves to LoggedOutPlayerData, and bots, which it only removes
+fn shutdown_counts(players: &[PlayerData]) -> (usize, usize) {
+    let bots = players.iter().filter(|player| player.is_bot).count();
+    (players.len() - bots, bots)
+}
+
+pub fn reopen_server(ctx: &ReducerContext) {
+    set_accepting_joins(ctx, true);
+    spacetimedb::log::info!("Server reopened for joins by {}.", ctx.sender);
+}
+
+// Keys are collected before deleting so no table is modified while it's being iterated
+fn clear_transient_tables(ctx: &ReducerContext) {
+    for key in ctx.db.projectile().iter().map(|row| row.id).collect::<Vec<_>>() { ctx.db.projectile().id().delete(key); }
+    for key in ctx.db.impact_effect().iter().map(|row| row.id).collect::<Vec<_>>() { ctx.db.impact_effect().id().delete(key); }
+    for key in ctx.db.status_effect().iter().map(|row| row.id).collect::<Vec<_>>() { ctx.db.status_effect().id().delete(key); }
+    for key in ctx.db.gravity_well().iter().map(|row| row.id).collect::<Vec<_>>() { ctx.db.gravity_well().id().delete(key); }
+    for key in ctx.db.ground_effect().iter().map(|row| row.id).collect::<Vec<_>>() { ctx.db.ground_effect().id().delete(key); }
+    for key in ctx.db.vote_kick().iter().map(|row| row.id).collect::<Vec<_>>() { ctx.db.vote_kick().id().delete(key); }
+    for key in ctx.db.portal().iter().map(|row| row.id).collect::<Vec<_>>() { ctx.db.portal().id().delete(key); }
+    for key in ctx.db.bot_state().iter().map(|row| row.identity).collect::<Vec<_>>() { ctx.db.bot_state().identity().delete(key); }
+    for key in ctx.db.totem().iter().map(|row| row.id).collect::<Vec<_>>() { ctx.db.totem().id().delete(key); }
+    for key in ctx.db.mine().iter().map(|row| row.id).collect::<Vec<_>>() { ctx.db.mine().id().delete(key); }
+    for key in ctx.db.loot_drop().iter().map(|row| row.id).collect::<Vec<_>>() { ctx.db.loot_drop().id().delete(key); }
+    for key in ctx.db.reveal().iter().map(|row| row.id).collect::<Vec<_>>() { ctx.db.reveal().id().delete(key); }
+    for key in ctx.db.duel().iter().map(|row| row.id).collect::<Vec<_>>() { ctx.db.duel().id().delete(key); }
+    for key in ctx.db.voice_proximity().iter().map(|row| row.id).collect::<Vec<_>>() { ctx.db.voice_proximity().id().delete(key); }
+    for key in ctx.db.position_history().iter().map(|row| row.id).collect::<Vec<_>>() { ctx.db.position_history().id().delete(key); }
+    for key in ctx.db.look_target().iter().map(|row| row.observer).collect::<Vec<_>>() { ctx.db.look_target().observer().delete(key); }
+    for key in ctx.db.join_queue().iter().map(|row| row.identity).collect::<Vec<_>>() { ctx.db.join_queue().identity().delete(key); }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at_micros, player};
+
+    #[test]
+    fn joins_are_open_until_shutdown() {
+        assert!(joins_open(None));
+        assert!(!joins_open(Some(&match_state(false, at_micros(0)))));
+        assert!(joins_open(Some(&match_state(true, at_micros(1_000_000)))));
+    }
+
+    #[test]
+    fn shutdown_persists_every_player_but_bots() {
+        let bot = PlayerData { is_bot: true, ..player(3) };
+        assert_eq!(shutdown_counts(&[player(1), player(2), bot]), (2, 1));
+        assert_eq!(shutdown_counts(&[]), (0, 0));
+    }
+}
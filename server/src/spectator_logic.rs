@@ -0,0 +1,64 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - spectator_logic.rs
+ *
+ * This file contains the follow-cam bookkeeping for spectators.
+ *
+ * Key components:
+ *
+ * 1. Target Cleanup:
+ *    - clear_spectate_targets_for: Clears every spectator following a player who just left
+ *    - clear_stale_spectate_targets: game_tick safety net for targets that are no longer active
+ *    - loses_target: Whether a spectator's target is gone, shared by both
+ *
+ * Related files:
+ *    - lib.rs: Spectator table and the join/leave/set_spectate_target reducers
+ */
+
+use spacetimedb::{ReducerContext, Identity, Table};
+// Import table traits
+use crate::{player, spectator};
+
+// Called when a player leaves the active table so spectators stop following them
+pub fn clear_spectate_targets_for(ctx: &ReducerContext, target_identity: Identity) {
+    for mut spectator in ctx.db.spectator().iter() {
+        if loses_target(spectator.spectate_target, |identity| identity != target_identity) {
+            spectator.spectate_target = None;
+            spacetimedb::log::info!("Spectator {} lost target {}.", spectator.identity, target_identity);
+            ctx.db.spectator().identity().update(spectator);
+        }
+    }
+}
+
+// Clear any spectate target that no longer points at an active player
+pub fn clear_stale_spectate_targets(ctx: &ReducerContext) {
+    for mut spectator in ctx.db.spectator().iter() {
+        if loses_target(spectator.spectate_target, |identity| ctx.db.player().identity().find(identity).is_some()) {
+            spectator.spectate_target = None;
+            ctx.db.spectator().identity().update(spectator);
+        }
+    }
+}
+
+// A spectator loses their target once it's no longer an active player. Free-cam spectators
+// (no target) have nothing to lose.
+fn loses_target(target: Option<Identity>, is_active: impl Fn(Identity) -> bool) -> bool {
+    target.is_some_and(|identity| !is_active(identity))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::identity;
+
+    #[test]
+    fn target_is_lost_when_it_disconnects() {
+        let target = identity(1);
+        assert!(loses_target(Some(target), |active| active != target));
+        assert!(!loses_target(Some(identity(2)), |active| active != target));
+    }
+
+    #[test]
+    fn free_cam_has_no_target_to_lose() {
+        assert!(!loses_target(None, |_| false));
+    }
+}
@@ -0,0 +1,352 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - spell_logic.rs
+ *
+ * This file contains spell definitions and the helpers cast_spell uses to spawn projectiles.
+ *
+ * Key components:
+ *
+ * 1. Spell Definitions:
+ *    - default_spell_definitions: Spells seeded into the SpellDefinition table in init
+ *    - find_spell: Looks up a spell by (case-insensitive) name, unknown spells fall back
+ *      to the default homing sphere
+ *    - Spells with ground_effect_radius > 0 (the "fire patch") place a GroundEffect
+ *      instead of a projectile, see ground_effect_logic.rs
+ *
+ * 2. Projectile Lifetime:
+ *    - projectile_expires_at: Applies the global MAX_PROJECTILE_LIFETIME_SECS clamp so
+ *      a misconfigured spell can never create a projectile that lingers
+ *    - clamp_projectile_speed: Keeps every projectile's speed within 0..MAX_PROJECTILE_SPEED,
+ *      so no definition can make one fast enough to skip through players
+ *    - clamp_homing_strength: Keeps a spell's homing_strength within 0..1
+ *    - clamp_projectile_gravity: Keeps a spell's projectile_gravity non-negative
+ *    - clamp_projectile_count/clamp_spread_angle: Volley size (1..MAX_PROJECTILES_PER_CASTER)
+ *      and fan width (0..PI radians)
+ *    - normalize_on_expire/clamp_aoe_radius: Expiry behavior ("fizzle" or "explode") and
+ *      the explosion radius, unknown behaviors fizzle
+ *
+ * When modifying:
+ *    - Spell names are stored lowercase, matching the names the client sends
+ *    - Any value copied from a definition onto a projectile must be sanity-clamped here
+ *
+ * Related files:
+ *    - lib.rs: SpellDefinition table and cast_spell reducer
+ *    - common.rs: Collision masks and projectile limits
+ */
+
+use spacetimedb::{ReducerContext, Timestamp};
+use crate::common::{
+    COLLISION_MASK_DEFAULT, COLLISION_LAYER_ENEMY, PIERCING_BOLT_MAX_PIERCE, MAX_PROJECTILE_LIFETIME_SECS, MAX_PROJECTILE_SPEED,
+    CLUSTER_FRAGMENT_COUNT, BEAM_DAMAGE_PER_TICK, BEAM_MANA_PER_TICK, MAX_PROJECTILES_PER_CASTER,
+    SHOTGUN_PROJECTILE_COUNT, SHOTGUN_SPREAD_RADIANS, FIRE_PATCH_RADIUS, FIRE_PATCH_DAMAGE_PER_TICK,
+    FIRE_PATCH_DURATION_SECS,
+};
+use crate::channel_logic::BEAM_SPELL_NAME;
+use crate::status_logic::STATUS_BURN;
+use crate::SpellDefinition;
+// Import table traits
+use crate::spell_definition;
+
+pub const DEFAULT_SPELL_NAME: &str = "fireball";
+pub const ON_EXPIRE_FIZZLE: &str = "fizzle";
+pub const ON_EXPIRE_EXPLODE: &str = "explode";
+
+fn homing_sphere(name: &str, status_effect: &str) -> SpellDefinition {
+    SpellDefinition {
+        name: name.to_string(),
+        projectile_type: "homing_sphere".to_string(),
+        speed: 15.0, // units per second
+        lifetime_secs: 8.0,
+        damage: 10,
+        collision_mask: COLLISION_MASK_DEFAULT,
+        piercing: false,
+        max_pierce: 0,
+        status_effect: status_effect.to_string(),
+        fragment_count: 0,
+        homing_strength: 1.0,
+        projectile_gravity: 0.0,
+        channel_mana_per_tick: 0,
+        on_expire: ON_EXPIRE_FIZZLE.to_string(),
+        aoe_radius: 0.0,
+        projectile_count: 1,
+        spread_angle: 0.0,
+        ground_effect_radius: 0.0,
+    }
+}
+
+pub fn default_spell_definitions() -> Vec<SpellDefinition> {
+    vec![
+        // Fire spells set their target burning
+        homing_sphere("fireball", STATUS_BURN),
+        homing_sphere("ice shard", ""),
+        homing_sphere("lightning bolt", ""),
+        // Lazy seeker: only partially corrects toward its target, so it can be outrun or sidestepped
+        SpellDefinition {
+            homing_strength: 0.2,
+            ..homing_sphere("seeker", "")
+        },
+        // Piercing bolts fly through targets instead of stopping at the first hit
+        SpellDefinition {
+            name: "piercing_bolt".to_string(),
+            projectile_type: "piercing_bolt".to_string(),
+            speed: 20.0,
+            lifetime_secs: 3.0,
+            damage: 10,
+            collision_mask: COLLISION_LAYER_ENEMY,
+            piercing: true,
+            max_pierce: PIERCING_BOLT_MAX_PIERCE,
+            status_effect: String::new(),
+            fragment_count: 0,
+            homing_strength: 0.0,
+            projectile_gravity: 0.0,
+            channel_mana_per_tick: 0,
+            on_expire: ON_EXPIRE_FIZZLE.to_string(),
+            aoe_radius: 0.0,
+            projectile_count: 1,
+            spread_angle: 0.0,
+            ground_effect_radius: 0.0,
+        },
+        // Cluster bombs burst into a fan of fragments when they hit or expire
+        SpellDefinition {
+            name: "cluster_bomb".to_string(),
+            projectile_type: "cluster".to_string(),
+            speed: 12.0,
+            lifetime_secs: 2.0,
+            damage: 10,
+            collision_mask: COLLISION_MASK_DEFAULT,
+            piercing: false,
+            max_pierce: 0,
+            status_effect: String::new(),
+            fragment_count: CLUSTER_FRAGMENT_COUNT,
+            homing_strength: 1.0,
+            projectile_gravity: 0.0,
+            channel_mana_per_tick: 0,
+            on_expire: ON_EXPIRE_FIZZLE.to_string(),
+            aoe_radius: 0.0,
+            projectile_count: 1,
+            spread_angle: 0.0,
+            ground_effect_radius: 0.0,
+        },
+        // Shotgun: a fan of short-range pellets, each stopping at the first enemy it hits
+        SpellDefinition {
+            name: "shotgun".to_string(),
+            projectile_type: "pellet".to_string(),
+            speed: 25.0,
+            lifetime_secs: 0.6,
+            damage: 4,
+            collision_mask: COLLISION_LAYER_ENEMY,
+            piercing: true,
+            max_pierce: 1,
+            status_effect: String::new(),
+            fragment_count: 0,
+            homing_strength: 0.0,
+            projectile_gravity: 0.0,
+            channel_mana_per_tick: 0,
+            on_expire: ON_EXPIRE_FIZZLE.to_string(),
+            aoe_radius: 0.0,
+            projectile_count: SHOTGUN_PROJECTILE_COUNT,
+            spread_angle: SHOTGUN_SPREAD_RADIANS,
+            ground_effect_radius: 0.0,
+        },
+        // Fire patch: no projectile, leaves a burning area ahead of the caster that
+        // damages enemies inside every game tick for lifetime_secs
+        SpellDefinition {
+            name: "fire patch".to_string(),
+            projectile_type: "fire_patch".to_string(),
+            speed: 0.0,
+            lifetime_secs: FIRE_PATCH_DURATION_SECS,
+            damage: FIRE_PATCH_DAMAGE_PER_TICK,
+            collision_mask: COLLISION_LAYER_ENEMY,
+            piercing: false,
+            max_pierce: 0,
+            status_effect: String::new(),
+            fragment_count: 0,
+            homing_strength: 0.0,
+            projectile_gravity: 0.0,
+            channel_mana_per_tick: 0,
+            on_expire: ON_EXPIRE_FIZZLE.to_string(),
+            aoe_radius: 0.0,
+            projectile_count: 1,
+            spread_angle: 0.0,
+            ground_effect_radius: FIRE_PATCH_RADIUS,
+        },
+        // Channeled: started with start_channel, costs mana and deals damage every game tick
+        SpellDefinition {
+            name: BEAM_SPELL_NAME.to_string(),
+            projectile_type: "beam".to_string(),
+            speed: 0.0,
+            lifetime_secs: 0.0,
+            damage: BEAM_DAMAGE_PER_TICK,
+            collision_mask: COLLISION_LAYER_ENEMY,
+            piercing: false,
+            max_pierce: 0,
+            status_effect: String::new(),
+            fragment_count: 0,
+            homing_strength: 0.0,
+            projectile_gravity: 0.0,
+            channel_mana_per_tick: BEAM_MANA_PER_TICK,
+            on_expire: ON_EXPIRE_FIZZLE.to_string(),
+            aoe_radius: 0.0,
+            projectile_count: 1,
+            spread_angle: 0.0,
+            ground_effect_radius: 0.0,
+        },
+    ]
+}
+
+// Canonical form of a spell name: trimmed and lowercase
+pub fn normalize_spell_name(spell_name: &str) -> String {
+    spell_name.trim().to_lowercase()
+}
+
+pub fn find_spell(ctx: &ReducerContext, spell_name: &str) -> SpellDefinition {
+    let name = normalize_spell_name(spell_name);
+    ctx.db.spell_definition().name().find(&name)
+        .or_else(|| ctx.db.spell_definition().name().find(DEFAULT_SPELL_NAME.to_string()))
+        .unwrap_or_else(|| homing_sphere(DEFAULT_SPELL_NAME, STATUS_BURN))
+}
+
+// Speed copied onto a projectile: 0..MAX_PROJECTILE_SPEED, with non-finite values treated as the cap
+pub fn clamp_projectile_speed(speed: f32) -> f32 {
+    if speed.is_finite() {
+        speed.clamp(0.0, MAX_PROJECTILE_SPEED)
+    } else {
+        MAX_PROJECTILE_SPEED
+    }
+}
+
+// Homing strength copied onto a projectile: 0..1, with non-finite values treated as perfect homing
+pub fn clamp_homing_strength(homing_strength: f32) -> f32 {
+    if homing_strength.is_finite() {
+        homing_strength.clamp(0.0, 1.0)
+    } else {
+        1.0
+    }
+}
+
+// Expiry behavior copied onto a projectile: anything but "explode" fizzles
+pub fn normalize_on_expire(on_expire: &str) -> String {
+    if on_expire.trim().eq_ignore_ascii_case(ON_EXPIRE_EXPLODE) {
+        ON_EXPIRE_EXPLODE.to_string()
+    } else {
+        ON_EXPIRE_FIZZLE.to_string()
+    }
+}
+
+// Expiry explosion radius copied onto a projectile: never negative, non-finite values disable it
+pub fn clamp_aoe_radius(aoe_radius: f32) -> f32 {
+    if aoe_radius.is_finite() {
+        aoe_radius.max(0.0)
+    } else {
+        0.0
+    }
+}
+
+// Projectiles per cast: at least 1, and never a volley bigger than the per-caster cap
+pub fn clamp_projectile_count(projectile_count: u32) -> u32 {
+    projectile_count.clamp(1, MAX_PROJECTILES_PER_CASTER as u32)
+}
+
+// Volley fan width: 0..PI, non-finite values fire every projectile straight ahead
+pub fn clamp_spread_angle(spread_angle: f32) -> f32 {
+    if spread_angle.is_finite() {
+        spread_angle.clamp(0.0, std::f32::consts::PI)
+    } else {
+        0.0
+    }
+}
+
+// Gravity copied onto a projectile: never negative, non-finite values fly flat
+pub fn clamp_projectile_gravity(projectile_gravity: f32) -> f32 {
+    if projectile_gravity.is_finite() {
+        projectile_gravity.max(0.0)
+    } else {
+        0.0
+    }
+}
+
+// Expiry for a projectile spawned now, never further out than MAX_PROJECTILE_LIFETIME_SECS
+pub fn projectile_expires_at(now: Timestamp, lifetime_secs: f32) -> Timestamp {
+    let lifetime_secs = if lifetime_secs.is_finite() {
+        lifetime_secs.clamp(0.0, MAX_PROJECTILE_LIFETIME_SECS)
+    } else {
+        MAX_PROJECTILE_LIFETIME_SECS
+    };
+    Timestamp::from_micros_since_unix_epoch(
+        now.to_micros_since_unix_epoch() + (lifetime_secs as f64 * 1_000_000.0) as i64
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::at_micros;
+
+    #[test]
+    fn default_spells_are_keyed_by_unique_normalized_names() {
+        let spells = default_spell_definitions();
+        assert!(spells.iter().any(|spell| spell.name == DEFAULT_SPELL_NAME));
+        for (i, spell) in spells.iter().enumerate() {
+            assert_eq!(normalize_spell_name(&spell.name), spell.name);
+            assert!(spells[i + 1..].iter().all(|other| other.name != spell.name), "duplicate {}", spell.name);
+        }
+        assert_eq!(normalize_spell_name("  Ice Shard "), "ice shard");
+    }
+
+    #[test]
+    fn projectile_lifetime_is_capped() {
+        let now = at_micros(5_000_000);
+        assert_eq!(projectile_expires_at(now, 1.5), at_micros(6_500_000));
+        assert_eq!(projectile_expires_at(now, -3.0), now);
+        let cap = at_micros(5_000_000 + (MAX_PROJECTILE_LIFETIME_SECS as f64 * 1_000_000.0) as i64);
+        assert_eq!(projectile_expires_at(now, 1_000.0), cap);
+        assert_eq!(projectile_expires_at(now, f32::NAN), cap);
+    }
+
+    #[test]
+    fn homing_strength_is_clamped_to_a_share_of_the_turn() {
+        assert_eq!(clamp_homing_strength(0.25), 0.25);
+        assert_eq!(clamp_homing_strength(-1.0), 0.0);
+        assert_eq!(clamp_homing_strength(3.0), 1.0);
+        assert_eq!(clamp_homing_strength(f32::NAN), 1.0);
+    }
+
+    #[test]
+    fn projectile_gravity_is_never_negative() {
+        assert_eq!(clamp_projectile_gravity(9.8), 9.8);
+        assert_eq!(clamp_projectile_gravity(-3.0), 0.0);
+        assert_eq!(clamp_projectile_gravity(f32::INFINITY), 0.0);
+    }
+
+    #[test]
+    fn anything_but_explode_fizzles_on_expiry() {
+        assert_eq!(normalize_on_expire(" Explode "), ON_EXPIRE_EXPLODE);
+        assert_eq!(normalize_on_expire("fizzle"), ON_EXPIRE_FIZZLE);
+        assert_eq!(normalize_on_expire("vanish"), ON_EXPIRE_FIZZLE);
+        assert_eq!(normalize_on_expire(""), ON_EXPIRE_FIZZLE);
+    }
+
+    #[test]
+    fn expiry_explosion_radius_is_never_negative() {
+        assert_eq!(clamp_aoe_radius(2.5), 2.5);
+        assert_eq!(clamp_aoe_radius(-1.0), 0.0);
+        assert_eq!(clamp_aoe_radius(f32::NAN), 0.0);
+    }
+
+    #[test]
+    fn volley_size_and_spread_are_clamped() {
+        assert_eq!(clamp_projectile_count(0), 1);
+        assert_eq!(clamp_projectile_count(5), 5);
+        assert_eq!(clamp_projectile_count(1_000), MAX_PROJECTILES_PER_CASTER as u32);
+        assert_eq!(clamp_spread_angle(-1.0), 0.0);
+        assert_eq!(clamp_spread_angle(10.0), std::f32::consts::PI);
+        assert_eq!(clamp_spread_angle(f32::NAN), 0.0);
+    }
+
+    #[test]
+    fn projectile_speed_is_capped() {
+        assert_eq!(clamp_projectile_speed(12.0), 12.0);
+        assert_eq!(clamp_projectile_speed(-4.0), 0.0);
+        assert_eq!(clamp_projectile_speed(1.0e9), MAX_PROJECTILE_SPEED);
+        assert_eq!(clamp_projectile_speed(f32::NAN), MAX_PROJECTILE_SPEED);
+    }
+}
@@ -0,0 +1,449 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - status_logic.rs
+ *
+ * This file contains timed status effects (buffs/debuffs) stored in the StatusEffect table.
+ *
+ * Key components:
+ *
+ * 1. Applying Effects:
+ *    - apply_status_effect: Dispatches on the effect kind carried by a projectile/spell
+ *    - apply_burn: Adds a burn stack, refreshing duration and capping at BURN_MAX_STACKS
+ *    - apply_empower: Damage buff from blood_sacrifice, one per player, refreshed on reapply
+ *    - outgoing_damage: Scales a player's hits on others while they're empowered
+ *    - apply_haste: Speed buff from speed pickups, one per player, refreshed on reapply.
+ *      Sets PlayerData.speed_multiplier, which update_status_effects resets to 1.0 when
+ *      the effect expires
+ *    - toggle_vampiric_aura: Turns the caller's vampiric aura on (for
+ *      VAMPIRIC_AURA_DURATION_MICROS) or off
+ *    - cleanse: For CLEANSE_MANA_COST mana, removes every negative effect (is_negative)
+ *      on the caller, at most once per CLEANSE_COOLDOWN_MICROS. Buffs are kept, and
+ *      derived state such as speed_multiplier is recomputed from what remains
+ *    - clear_negative_effects: The debuff removal behind cleanse, also run on death so
+ *      a burn doesn't outlive the respawn delay
+ *    - toggle_thorns: Turns the caller's thorns on (for THORNS_DURATION_MICROS) or off
+ *    - reflected_damage: Called by combat_logic::apply_damage; while the target's thorns
+ *      are up, THORNS_REFLECT_FRACTION of the damage they took goes back to the attacker
+ *    - distribute_vampiric_heal: Called by combat_logic::apply_damage; while the attacker's
+ *      aura is up, VAMPIRIC_AURA_FRACTION of the damage they dealt is split evenly between
+ *      their living allies within VAMPIRIC_AURA_RADIUS (not the attacker themselves)
+ *
+ * 2. Game Tick:
+ *    - update_status_effects: Removes expired effects and runs periodic effects
+ *      (burn damages the target each tick through combat_logic::apply_damage,
+ *      crediting the effect's source for kills)
+ *
+ * Stacking rules (burn):
+ *    - One burn row per target and source
+ *    - Reapplying refreshes the duration and adds a stack, up to BURN_MAX_STACKS
+ *    - Damage per tick = magnitude * stacks (burn_tick_damage)
+ *
+ * Related files:
+ *    - combat_logic.rs: Damage helper used by periodic effects
+ *    - lib.rs: StatusEffect table definition
+ */
+
+use spacetimedb::{ReducerContext, Identity, Table, Timestamp};
+use crate::{calculate_distance, combat_logic, config_logic, team_logic};
+use crate::common::{
+    BURN_DAMAGE_PER_STACK, BURN_DURATION_MICROS, BURN_MAX_STACKS, EMPOWER_DAMAGE_MULTIPLIER, EMPOWER_DURATION_MICROS,
+    VAMPIRIC_AURA_FRACTION, VAMPIRIC_AURA_RADIUS, VAMPIRIC_AURA_DURATION_MICROS, HASTE_SPEED_MULTIPLIER,
+    HASTE_DURATION_MICROS, CLEANSE_MANA_COST, CLEANSE_COOLDOWN_MICROS, THORNS_REFLECT_FRACTION, THORNS_DURATION_MICROS,
+};
+use crate::StatusEffect;
+// Import table traits
+use crate::{player, status_effect};
+
+pub const STATUS_BURN: &str = "burn";
+pub const STATUS_EMPOWER: &str = "empower";
+pub const STATUS_HASTE: &str = "haste";
+pub const STATUS_THORNS: &str = "thorns";
+pub const STATUS_VAMPIRIC_AURA: &str = "vampiric_aura";
+
+// Debuffs, which cleanse removes
+pub fn is_negative(kind: &str) -> bool {
+    matches!(kind, STATUS_BURN)
+}
+
+// Apply the status named by a spell/projectile. Empty or unknown kinds do nothing.
+pub fn apply_status_effect(ctx: &ReducerContext, kind: &str, target: Identity, source: Identity) {
+    match kind {
+        STATUS_BURN => apply_burn(ctx, target, source),
+        "" => {}
+        other => spacetimedb::log::warn!("Unknown status effect '{}'", other),
+    }
+}
+
+pub fn apply_burn(ctx: &ReducerContext, target: Identity, source: Identity) {
+    let expires_at = Timestamp::from_micros_since_unix_epoch(
+        ctx.timestamp.to_micros_since_unix_epoch() + BURN_DURATION_MICROS
+    );
+
+    let existing = ctx.db.status_effect().iter()
+        .find(|e| e.kind == STATUS_BURN && e.target_identity == target && e.source_identity == source);
+    match existing {
+        Some(mut burn) => {
+            burn.stacks = add_burn_stack(burn.stacks);
+            burn.expires_at = expires_at;
+            ctx.db.status_effect().id().update(burn);
+        }
+        None => {
+            if let Err(e) = ctx.db.status_effect().try_insert(StatusEffect {
+                id: 0, // auto_inc will set this
+                target_identity: target,
+                source_identity: source,
+                kind: STATUS_BURN.to_string(),
+                stacks: 1,
+                magnitude: BURN_DAMAGE_PER_STACK as f32,
+                applied_at: ctx.timestamp,
+                expires_at,
+            }) {
+                spacetimedb::log::error!("Failed to apply burn to {}: {}", target, e);
+            }
+        }
+    }
+}
+
+// Reapplying burn adds a stack, up to BURN_MAX_STACKS
+fn add_burn_stack(stacks: u32) -> u32 {
+    (stacks + 1).min(BURN_MAX_STACKS)
+}
+
+// Damage one burn tick deals: magnitude per stack
+pub fn burn_tick_damage(burn: &StatusEffect) -> i32 {
+    burn.magnitude as i32 * burn.stacks as i32
+}
+
+pub fn apply_empower(ctx: &ReducerContext, target: Identity) {
+    let expires_at = Timestamp::from_micros_since_unix_epoch(
+        ctx.timestamp.to_micros_since_unix_epoch() + EMPOWER_DURATION_MICROS
+    );
+
+    let existing = ctx.db.status_effect().iter()
+        .find(|e| e.kind == STATUS_EMPOWER && e.target_identity == target);
+    match existing {
+        Some(mut empower) => {
+            empower.expires_at = expires_at;
+            ctx.db.status_effect().id().update(empower);
+        }
+        None => {
+            if let Err(e) = ctx.db.status_effect().try_insert(StatusEffect {
+                id: 0, // auto_inc will set this
+                target_identity: target,
+                source_identity: target,
+                kind: STATUS_EMPOWER.to_string(),
+                stacks: 1,
+                magnitude: EMPOWER_DAMAGE_MULTIPLIER,
+                applied_at: ctx.timestamp,
+                expires_at,
+            }) {
+                spacetimedb::log::error!("Failed to empower {}: {}", target, e);
+            }
+        }
+    }
+}
+
+pub fn apply_haste(ctx: &ReducerContext, target: Identity) {
+    let Some(mut player) = ctx.db.player().identity().find(target) else {
+        return;
+    };
+    let expires_at = Timestamp::from_micros_since_unix_epoch(
+        ctx.timestamp.to_micros_since_unix_epoch() + HASTE_DURATION_MICROS
+    );
+
+    let existing = ctx.db.status_effect().iter()
+        .find(|e| e.kind == STATUS_HASTE && e.target_identity == target);
+    match existing {
+        Some(mut haste) => {
+            haste.expires_at = expires_at;
+            ctx.db.status_effect().id().update(haste);
+        }
+        None => {
+            if let Err(e) = ctx.db.status_effect().try_insert(StatusEffect {
+                id: 0, // auto_inc will set this
+                target_identity: target,
+                source_identity: target,
+                kind: STATUS_HASTE.to_string(),
+                stacks: 1,
+                magnitude: HASTE_SPEED_MULTIPLIER,
+                applied_at: ctx.timestamp,
+                expires_at,
+            }) {
+                spacetimedb::log::error!("Failed to haste {}: {}", target, e);
+                return;
+            }
+        }
+    }
+    player.speed_multiplier = HASTE_SPEED_MULTIPLIER;
+    ctx.db.player().identity().update(player);
+}
+
+// Damage a hit from `source` deals before the target's mitigation. Only buffs hits on others,
+// and ignores effects that expired since the last game tick.
+pub fn outgoing_damage(ctx: &ReducerContext, source: Identity, target: Identity, amount: i32) -> i32 {
+    if source == target {
+        return amount;
+    }
+    match active_effect(ctx, STATUS_EMPOWER, source) {
+        Some(empower) => empowered_damage(amount, empower.magnitude),
+        None => amount,
+    }
+}
+
+fn empowered_damage(amount: i32, multiplier: f32) -> i32 {
+    (amount as f32 * multiplier).round() as i32
+}
+
+// Turn the caller's vampiric aura on, or off if it's already up. Returns whether it's now on.
+pub fn toggle_vampiric_aura(ctx: &ReducerContext) -> Result<bool, String> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can use a vampiric aura.".to_string());
+    };
+    if let Some(aura) = active_effect(ctx, STATUS_VAMPIRIC_AURA, player.identity) {
+        ctx.db.status_effect().id().delete(aura.id);
+        return Ok(false);
+    }
+    if player.health <= 0 {
+        return Err("Dead players cannot use a vampiric aura.".to_string());
+    }
+    ctx.db.status_effect().try_insert(StatusEffect {
+        id: 0, // auto_inc will set this
+        target_identity: player.identity,
+        source_identity: player.identity,
+        kind: STATUS_VAMPIRIC_AURA.to_string(),
+        stacks: 1,
+        magnitude: VAMPIRIC_AURA_FRACTION,
+        applied_at: ctx.timestamp,
+        expires_at: Timestamp::from_micros_since_unix_epoch(
+            ctx.timestamp.to_micros_since_unix_epoch() + VAMPIRIC_AURA_DURATION_MICROS
+        ),
+    }).map_err(|e| format!("Failed to start vampiric aura: {}", e))?;
+    Ok(true)
+}
+
+// Turn the caller's thorns on, or off if they're already up. Returns whether they're now on.
+pub fn toggle_thorns(ctx: &ReducerContext) -> Result<bool, String> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can use thorns.".to_string());
+    };
+    if let Some(thorns) = active_effect(ctx, STATUS_THORNS, player.identity) {
+        ctx.db.status_effect().id().delete(thorns.id);
+        return Ok(false);
+    }
+    if player.health <= 0 {
+        return Err("Dead players cannot use thorns.".to_string());
+    }
+    ctx.db.status_effect().try_insert(StatusEffect {
+        id: 0, // auto_inc will set this
+        target_identity: player.identity,
+        source_identity: player.identity,
+        kind: STATUS_THORNS.to_string(),
+        stacks: 1,
+        magnitude: THORNS_REFLECT_FRACTION,
+        applied_at: ctx.timestamp,
+        expires_at: Timestamp::from_micros_since_unix_epoch(
+            ctx.timestamp.to_micros_since_unix_epoch() + THORNS_DURATION_MICROS
+        ),
+    }).map_err(|e| format!("Failed to start thorns: {}", e))?;
+    Ok(true)
+}
+
+// Damage the target's thorns send back after taking `damage_taken`, 0 without thorns
+pub fn reflected_damage(ctx: &ReducerContext, target: Identity, damage_taken: i32) -> i32 {
+    match active_effect(ctx, STATUS_THORNS, target) {
+        Some(thorns) => thorns_share(damage_taken, thorns.magnitude),
+        None => 0,
+    }
+}
+
+// `fraction` of a hit, rounded; healing and blocked hits reflect nothing
+fn thorns_share(damage_taken: i32, fraction: f32) -> i32 {
+    if damage_taken <= 0 {
+        return 0;
+    }
+    (damage_taken as f32 * fraction).round() as i32
+}
+
+// Heal the attacker's nearby allies for a share of `damage_dealt` while their aura is up
+pub fn distribute_vampiric_heal(ctx: &ReducerContext, attacker: Identity, damage_dealt: i32) {
+    if damage_dealt <= 0 {
+        return;
+    }
+    let Some(aura) = active_effect(ctx, STATUS_VAMPIRIC_AURA, attacker) else {
+        return;
+    };
+    let Some(attacker) = ctx.db.player().identity().find(attacker) else {
+        return;
+    };
+    let team_count = config_logic::get_config(ctx).team_count;
+    let allies: Vec<Identity> = ctx.db.player().iter()
+        .filter(|p| p.identity != attacker.identity && p.health > 0)
+        .filter(|p| team_logic::is_ally(team_count, attacker.team, p.team))
+        .filter(|p| calculate_distance(&p.position, &attacker.position) <= VAMPIRIC_AURA_RADIUS)
+        .map(|p| p.identity)
+        .collect();
+    if allies.is_empty() {
+        return;
+    }
+
+    let share = vampiric_heal_share(damage_dealt, aura.magnitude, allies.len());
+    if share <= 0 {
+        return;
+    }
+    for ally in allies {
+        combat_logic::apply_heal(ctx, ally, share);
+    }
+}
+
+// Each ally's even split of `fraction` of the damage dealt, rounded down
+fn vampiric_heal_share(damage_dealt: i32, fraction: f32, ally_count: usize) -> i32 {
+    let pool = (damage_dealt as f32 * fraction).round() as i32;
+    pool / ally_count.max(1) as i32
+}
+
+// An effect of `kind` on `target` that hasn't expired yet (game_tick may not have pruned it)
+fn active_effect(ctx: &ReducerContext, kind: &str, target: Identity) -> Option<StatusEffect> {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    ctx.db.status_effect().iter()
+        .find(|e| e.kind == kind && e.target_identity == target && now_micros < e.expires_at.to_micros_since_unix_epoch())
+}
+
+// Remove the caller's debuffs for mana. Returns how many effects were removed.
+pub fn cleanse(ctx: &ReducerContext) -> Result<usize, String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can cleanse.".to_string());
+    };
+    if player.health <= 0 {
+        return Err("Dead players cannot cleanse.".to_string());
+    }
+    if ctx.timestamp.to_micros_since_unix_epoch() < player.cleanse_cooldown_until.to_micros_since_unix_epoch() {
+        return Err("Cleanse is on cooldown.".to_string());
+    }
+    if player.mana < CLEANSE_MANA_COST {
+        return Err("Not enough mana to cleanse.".to_string());
+    }
+
+    let removed = clear_negative_effects(ctx, player.identity);
+
+    player.mana -= CLEANSE_MANA_COST;
+    player.cleanse_cooldown_until = Timestamp::from_micros_since_unix_epoch(
+        ctx.timestamp.to_micros_since_unix_epoch() + CLEANSE_COOLDOWN_MICROS
+    );
+    player.speed_multiplier = speed_multiplier(ctx, player.identity);
+    ctx.db.player().identity().update(player);
+    Ok(removed)
+}
+
+// Remove every debuff on `target` (cleanse, death). Returns how many were removed.
+pub fn clear_negative_effects(ctx: &ReducerContext, target: Identity) -> usize {
+    let debuffs: Vec<u64> = ctx.db.status_effect().iter()
+        .filter(|e| e.target_identity == target && is_negative(&e.kind))
+        .map(|e| e.id)
+        .collect();
+    for id in &debuffs {
+        ctx.db.status_effect().id().delete(*id);
+    }
+    debuffs.len()
+}
+
+// Movement speed multiplier implied by the player's current effects
+fn speed_multiplier(ctx: &ReducerContext, target: Identity) -> f32 {
+    active_effect(ctx, STATUS_HASTE, target).map(|haste| haste.magnitude).unwrap_or(1.0)
+}
+
+// Back to normal speed once a haste runs out
+fn end_haste(ctx: &ReducerContext, target: Identity) {
+    if let Some(mut player) = ctx.db.player().identity().find(target) {
+        player.speed_multiplier = 1.0;
+        ctx.db.player().identity().update(player);
+    }
+}
+
+// Expire finished effects and tick periodic ones (called from game_tick)
+pub fn update_status_effects(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+
+    for effect in ctx.db.status_effect().iter() {
+        let target_active = ctx.db.player().identity().find(effect.target_identity).is_some();
+        if !target_active || now_micros >= effect.expires_at.to_micros_since_unix_epoch() {
+            ctx.db.status_effect().id().delete(effect.id);
+            if effect.kind == STATUS_HASTE {
+                end_haste(ctx, effect.target_identity);
+            }
+            continue;
+        }
+
+        if effect.kind == STATUS_BURN {
+            let outcome = combat_logic::apply_damage(ctx, effect.target_identity, burn_tick_damage(&effect), Some(effect.source_identity), None);
+            if outcome.killed {
+                // A dead target stops burning
+                ctx.db.status_effect().id().delete(effect.id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at_micros, identity};
+
+    fn burn(stacks: u32) -> StatusEffect {
+        StatusEffect {
+            id: 1,
+            target_identity: identity(1),
+            source_identity: identity(2),
+            kind: STATUS_BURN.to_string(),
+            stacks,
+            magnitude: BURN_DAMAGE_PER_STACK as f32,
+            applied_at: at_micros(0),
+            expires_at: at_micros(BURN_DURATION_MICROS),
+        }
+    }
+
+    #[test]
+    fn burn_stacks_up_to_the_cap() {
+        assert_eq!(add_burn_stack(1), 2);
+        assert_eq!(add_burn_stack(BURN_MAX_STACKS - 1), BURN_MAX_STACKS);
+        assert_eq!(add_burn_stack(BURN_MAX_STACKS), BURN_MAX_STACKS);
+    }
+
+    #[test]
+    fn burn_ticks_for_magnitude_per_stack() {
+        assert_eq!(burn_tick_damage(&burn(1)), BURN_DAMAGE_PER_STACK);
+        assert_eq!(burn_tick_damage(&burn(3)), 3 * BURN_DAMAGE_PER_STACK);
+    }
+
+    #[test]
+    fn empowered_hits_are_scaled_and_rounded() {
+        assert_eq!(empowered_damage(20, EMPOWER_DAMAGE_MULTIPLIER), 25);
+        assert_eq!(empowered_damage(10, EMPOWER_DAMAGE_MULTIPLIER), 13);
+        assert_eq!(empowered_damage(0, EMPOWER_DAMAGE_MULTIPLIER), 0);
+    }
+
+    #[test]
+    fn vampiric_heals_split_evenly_between_allies() {
+        assert_eq!(vampiric_heal_share(40, 0.5, 1), 20);
+        assert_eq!(vampiric_heal_share(40, 0.5, 3), 6);
+        assert_eq!(vampiric_heal_share(1, 0.25, 2), 0);
+    }
+
+    #[test]
+    fn cleanse_only_counts_debuffs_as_negative() {
+        assert!(is_negative(STATUS_BURN));
+        assert!(!is_negative(STATUS_EMPOWER));
+        assert!(!is_negative(STATUS_VAMPIRIC_AURA));
+        assert!(!is_negative(STATUS_HASTE));
+    }
+
+    #[test]
+    fn thorns_reflect_a_rounded_share() {
+        assert_eq!(thorns_share(25, 0.3), 8);
+        assert_eq!(thorns_share(100, 0.5), 50);
+    }
+
+    #[test]
+    fn thorns_ignore_empty_hits() {
+        assert_eq!(thorns_share(0, 0.5), 0);
+        assert_eq!(thorns_share(-10, 0.5), 0);
+    }
+}
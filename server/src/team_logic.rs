@@ -0,0 +1,75 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - team_logic.rs
+ *
+ * This file contains team assignment and ally checks.
+ *
+ * Key components:
+ *
+ * 1. Assignment:
+ *    - assign_team: Picks the least-populated of GameConfig.team_count teams
+ *    - rebalance_teams: Redistributes active players after the team count changes
+ *
+ * 2. Relationships:
+ *    - is_ally: Whether two team ids are on the same side. With team_count <= 1
+ *      (free-for-all) nobody is an ally, which disables friendly-fire protection
+ *
+ * Related files:
+ *    - config_logic.rs: team_count lives in GameConfig
+ *    - projectile_logic.rs: Uses is_ally to put teammates on COLLISION_LAYER_ALLY
+ */
+
+use spacetimedb::{ReducerContext, Table};
+// Import table traits
+use crate::player;
+
+pub fn is_ally(team_count: u32, team_a: u32, team_b: u32) -> bool {
+    team_count > 1 && team_a == team_b
+}
+
+// Least-populated team among active players, lowest id on ties (always 0 in FFA)
+pub fn assign_team(ctx: &ReducerContext, team_count: u32) -> u32 {
+    if team_count <= 1 {
+        return 0;
+    }
+    let mut counts = vec![0usize; team_count as usize];
+    for player in ctx.db.player().iter() {
+        if let Some(count) = counts.get_mut(player.team as usize) {
+            *count += 1;
+        }
+    }
+    counts.iter()
+        .enumerate()
+        .min_by_key(|(team, count)| (**count, *team))
+        .map(|(team, _)| team as u32)
+        .unwrap_or(0)
+}
+
+// Deal active players round-robin into the configured teams
+pub fn rebalance_teams(ctx: &ReducerContext, team_count: u32) {
+    let team_count = team_count.max(1);
+    for (index, mut player) in ctx.db.player().iter().enumerate() {
+        let team = index as u32 % team_count;
+        if player.team != team {
+            player.team = team;
+            ctx.db.player().identity().update(player);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_for_all_has_no_allies() {
+        assert!(!is_ally(1, 0, 0));
+        assert!(!is_ally(0, 0, 0));
+    }
+
+    #[test]
+    fn team_modes_ally_players_on_the_same_team() {
+        assert!(is_ally(2, 1, 1));
+        assert!(!is_ally(2, 0, 1));
+        assert!(is_ally(4, 3, 3));
+    }
+}
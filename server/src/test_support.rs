@@ -0,0 +1,144 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - test_support.rs
+ *
+ * This file contains builders for the unit tests in the *_logic modules. It is only
+ * compiled for `cargo test`.
+ *
+ * Key components:
+ *    - identity: Deterministic identities for test players
+ *    - player: A PlayerData row standing at the origin with full health and mana,
+ *      every timestamp at the epoch
+ *    - projectile: A plain homing sphere from one player at another, flying +z
+ *
+ * When modifying:
+ *    - New PlayerData and ProjectileData fields need a neutral default here
+ *    - Tests override the fields they care about with struct update syntax
+ */
+
+use spacetimedb::{Identity, Timestamp};
+use crate::common::{Vector3, InputState, GROUND_HEIGHT, COLLISION_MASK_DEFAULT};
+use crate::spell_logic::ON_EXPIRE_FIZZLE;
+use crate::{PlayerData, ProjectileData};
+
+pub fn identity(n: u8) -> Identity {
+    let mut bytes = [0u8; 32];
+    bytes[31] = n;
+    Identity::from_byte_array(bytes)
+}
+
+pub fn at_micros(micros: i64) -> Timestamp {
+    Timestamp::from_micros_since_unix_epoch(micros)
+}
+
+pub fn vec3(x: f32, y: f32, z: f32) -> Vector3 {
+    Vector3 { x, y, z }
+}
+
+pub fn idle_input() -> InputState {
+    InputState {
+        forward: false, backward: false, left: false, right: false,
+        sprint: false, jump: false, attack: false, cast_spell: false,
+        dash: false,
+        block: false,
+        sequence: 0,
+    }
+}
+
+pub fn player(n: u8) -> PlayerData {
+    let epoch = at_micros(0);
+    let origin = vec3(0.0, GROUND_HEIGHT, 0.0);
+    PlayerData {
+        identity: identity(n),
+        username: format!("player{}", n),
+        character_class: "wizard".to_string(),
+        position: origin.clone(),
+        smoothed_position: origin.clone(),
+        last_spawn_position: origin,
+        rotation: vec3(0.0, 0.0, 0.0),
+        health: 100,
+        max_health: 100,
+        mana: 100,
+        max_mana: 100,
+        armor: 0,
+        knockback_resistance: 0.0,
+        collision_radius: 0.5,
+        current_animation: "idle".to_string(),
+        is_moving: false,
+        is_running: false,
+        current_speed: 0.0,
+        speed_multiplier: 1.0,
+        is_attacking: false,
+        attack_until: epoch,
+        invulnerable_until: epoch,
+        dash_cooldown_until: epoch,
+        cleanse_cooldown_until: epoch,
+        portal_cooldown_until: epoch,
+        block_until: epoch,
+        respawn_at: epoch,
+        is_casting: false,
+        channeling_spell: None,
+        channel_until: epoch,
+        channel_target: None,
+        last_input_seq: 0,
+        last_input_at: epoch,
+        input: idle_input(),
+        color: "cyan".to_string(),
+        vertical_velocity: 0.0,
+        is_grounded: true,
+        jump_consumed: false,
+        gravity_scale: 1.0,
+        jump_scale: 1.0,
+        kills: 0,
+        deaths: 0,
+        level: 1,
+        xp: 0,
+        score: 0,
+        shots_fired: 0,
+        shots_hit: 0,
+        team: 0,
+        pvp_enabled: true,
+        in_combat: false,
+        last_combat_at: epoch,
+        last_damaged_at: epoch,
+        bound_position: None,
+        title: String::new(),
+        is_bot: false,
+        equipped_spells: Vec::new(),
+        reported_latency_micros: 0,
+        recent_deaths: Vec::new(),
+    }
+}
+
+pub fn projectile(caster: u8, target: u8) -> ProjectileData {
+    let epoch = at_micros(0);
+    ProjectileData {
+        id: 1,
+        caster_identity: identity(caster),
+        position: vec3(0.0, GROUND_HEIGHT, 0.0),
+        target_identity: identity(target),
+        speed: 10.0,
+        created_at: epoch,
+        last_moved_at: epoch,
+        expires_at: at_micros(1_000_000),
+        projectile_type: "homing_sphere".to_string(),
+        collision_mask: COLLISION_MASK_DEFAULT,
+        direction: vec3(0.0, 0.0, 1.0),
+        piercing: false,
+        max_pierce: 1,
+        hit_identities: Vec::new(),
+        hit_counted: false,
+        damage: 10,
+        status_effect: String::new(),
+        caster_team: 0,
+        caster_color: "cyan".to_string(),
+        fragment_count: 0,
+        arcing: false,
+        vertical_velocity: 0.0,
+        aoe_radius: 0.0,
+        homing_strength: 1.0,
+        gravity: 0.0,
+        rewind_micros: 0,
+        lifetime_fraction: 1.0,
+        on_expire: ON_EXPIRE_FIZZLE.to_string(),
+    }
+}
@@ -0,0 +1,116 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - tick_budget_logic.rs
+ *
+ * This file contains the soft per-tick work budget that keeps game_tick responsive
+ * under heavy load.
+ *
+ * Key components:
+ *
+ * 1. Load:
+ *    - tick_load: Rows the tick's simulation systems walk this tick (players, projectiles,
+ *      status effects and placed objects)
+ *
+ * 2. Shedding:
+ *    - should_shed_low_priority: With GameConfig.tick_entity_budget > 0 and the load above
+ *      it, tells game_tick to skip its low-priority systems (voice proximity, look targets,
+ *      autosave, high scores, pruning) this tick. Shedding is logged and counted in the
+ *      TickLoad row; after MAX_CONSECUTIVE_SHED_TICKS shed ticks in a row the deferred work
+ *      runs anyway, so a sustained overload only slows it down
+ *
+ * When modifying:
+ *    - Movement, combat, effects and anything else players feel immediately must never be
+ *      shed; only add systems to the low-priority group if skipping a few ticks is harmless
+ *
+ * Related files:
+ *    - lib.rs: game_tick (the system order and priority groups), TickLoad table and the
+ *      set_tick_entity_budget reducer
+ */
+
+use spacetimedb::{ReducerContext, Table};
+use crate::common::MAX_CONSECUTIVE_SHED_TICKS;
+use crate::{config_logic, TickLoad};
+// Import table traits
+use crate::{ground_effect, loot_drop, mine, player, projectile, status_effect, tick_load, totem};
+
+// TickLoad is a single-row table keyed by this id
+pub const TICK_LOAD_ID: u32 = 0;
+
+pub fn tick_load(ctx: &ReducerContext) -> u64 {
+    ctx.db.player().count()
+        + ctx.db.projectile().count()
+        + ctx.db.status_effect().count()
+        + ctx.db.ground_effect().count()
+        + ctx.db.totem().count()
+        + ctx.db.mine().count()
+        + ctx.db.loot_drop().count()
+}
+
+// A budget of 0 turns shedding off
+fn is_over_budget(load: u64, budget: u64) -> bool {
+    budget > 0 && load > budget
+}
+
+// Over budget sheds, unless the work has already been deferred MAX_CONSECUTIVE_SHED_TICKS in a row
+fn sheds(load: u64, budget: u64, consecutive_shed_ticks: u32) -> bool {
+    is_over_budget(load, budget) && consecutive_shed_ticks < MAX_CONSECUTIVE_SHED_TICKS
+}
+
+// Whether this tick should skip its low-priority systems. Records the decision in TickLoad.
+pub fn should_shed_low_priority(ctx: &ReducerContext, load: u64) -> bool {
+    let budget = config_logic::get_config(ctx).tick_entity_budget;
+    let mut state = ctx.db.tick_load().id().find(TICK_LOAD_ID).unwrap_or(TickLoad {
+        id: TICK_LOAD_ID,
+        last_load: 0,
+        consecutive_shed_ticks: 0,
+        total_shed_ticks: 0,
+    });
+
+    let over_budget = is_over_budget(load, budget);
+    let shed = sheds(load, budget, state.consecutive_shed_ticks);
+    if shed {
+        state.consecutive_shed_ticks += 1;
+        state.total_shed_ticks = state.total_shed_ticks.saturating_add(1);
+        spacetimedb::log::warn!("Tick load {} is over the budget of {}, deferring low-priority work", load, budget);
+    } else {
+        if over_budget {
+            spacetimedb::log::warn!(
+                "Tick load {} is over the budget of {}, but low-priority work was deferred {} ticks in a row, running it",
+                load,
+                budget,
+                state.consecutive_shed_ticks
+            );
+        }
+        state.consecutive_shed_ticks = 0;
+    }
+    state.last_load = load;
+
+    if ctx.db.tick_load().id().find(TICK_LOAD_ID).is_some() {
+        ctx.db.tick_load().id().update(state);
+    } else {
+        ctx.db.tick_load().insert(state);
+    }
+    shed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_budget_never_sheds() {
+        assert!(!sheds(u64::MAX, 0, 0));
+    }
+
+    #[test]
+    fn sheds_only_above_the_budget() {
+        assert!(!sheds(100, 100, 0));
+        assert!(sheds(101, 100, 0));
+    }
+
+    #[test]
+    fn deferred_work_runs_after_the_shed_limit() {
+        assert!(sheds(101, 100, MAX_CONSECUTIVE_SHED_TICKS - 1));
+        assert!(!sheds(101, 100, MAX_CONSECUTIVE_SHED_TICKS));
+        assert!(is_over_budget(101, 100));
+    }
+}
@@ -0,0 +1,118 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - totem_logic.rs
+ *
+ * This file contains healing totems: placed support objects that heal the owner
+ * and their allies nearby every game tick until they expire.
+ *
+ * Key components:
+ *
+ * 1. Placement:
+ *    - place_totem: Validates range and stores a Totem row carrying the owner's team
+ *
+ * 2. Game Tick:
+ *    - update_totems: Removes expired totems and heals everyone in range that
+ *      is the owner or an ally of the owner (heals_player), through
+ *      combat_logic::apply_heal so health never exceeds max_health
+ *
+ * Related files:
+ *    - combat_logic.rs: Clamped heal helper
+ *    - team_logic.rs: Ally checks (free-for-all totems only heal their owner)
+ *    - lib.rs: Totem table and place_totem reducer
+ */
+
+use spacetimedb::{ReducerContext, Table, Timestamp};
+use crate::combat_logic;
+use crate::common::{Vector3, TOTEM_CAST_RANGE, TOTEM_DURATION_MICROS, TOTEM_HEAL_PER_TICK, TOTEM_RADIUS};
+use crate::{calculate_distance, config_logic, team_logic, PlayerData, Totem};
+// Import table traits
+use crate::{player, totem};
+
+pub fn place_totem(ctx: &ReducerContext, position: Vector3) -> Result<(), String> {
+    let Some(owner) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Only active players can place a totem.".to_string());
+    };
+    if owner.health <= 0 {
+        return Err("Dead players cannot place a totem.".to_string());
+    }
+    if calculate_distance(&owner.position, &position) > TOTEM_CAST_RANGE {
+        return Err("Totem position is out of range.".to_string());
+    }
+
+    let expires_at = Timestamp::from_micros_since_unix_epoch(
+        ctx.timestamp.to_micros_since_unix_epoch() + TOTEM_DURATION_MICROS
+    );
+    ctx.db.totem().try_insert(Totem {
+        id: 0, // auto_inc will set this
+        owner_identity: owner.identity,
+        owner_team: owner.team,
+        position,
+        radius: TOTEM_RADIUS,
+        heal_per_tick: TOTEM_HEAL_PER_TICK,
+        expires_at,
+    }).map_err(|e| format!("Failed to place totem: {}", e))?;
+
+    spacetimedb::log::info!("Player {} placed a healing totem.", owner.identity);
+    Ok(())
+}
+
+// Expire old totems and heal the owner and allies in range of the rest
+pub fn update_totems(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let team_count = config_logic::get_config(ctx).team_count;
+
+    for totem in ctx.db.totem().iter() {
+        if now_micros >= totem.expires_at.to_micros_since_unix_epoch() {
+            ctx.db.totem().id().delete(totem.id);
+            continue;
+        }
+
+        let targets: Vec<_> = ctx.db.player().iter()
+            .filter(|p| heals_player(&totem, p, team_count))
+            .map(|p| p.identity)
+            .collect();
+        for target in targets {
+            combat_logic::apply_heal(ctx, target, totem.heal_per_tick);
+        }
+    }
+}
+
+// Whether a totem heals this player: the owner or an ally standing within its radius
+fn heals_player(totem: &Totem, player: &PlayerData, team_count: u32) -> bool {
+    let friendly = player.identity == totem.owner_identity
+        || team_logic::is_ally(team_count, totem.owner_team, player.team);
+    friendly && calculate_distance(&player.position, &totem.position) <= totem.radius
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{at_micros, player, vec3};
+
+    fn totem(owner: &PlayerData) -> Totem {
+        Totem {
+            id: 1,
+            owner_identity: owner.identity,
+            owner_team: owner.team,
+            position: owner.position.clone(),
+            radius: TOTEM_RADIUS,
+            heal_per_tick: TOTEM_HEAL_PER_TICK,
+            expires_at: at_micros(TOTEM_DURATION_MICROS),
+        }
+    }
+
+    #[test]
+    fn totems_heal_the_owner_and_nearby_allies_only() {
+        let owner = PlayerData { team: 1, ..player(1) };
+        let totem = totem(&owner);
+        let ally = PlayerData { team: 1, ..player(2) };
+        let enemy = PlayerData { team: 2, ..player(3) };
+        let far_ally = PlayerData { team: 1, position: vec3(TOTEM_RADIUS + 1.0, owner.position.y, 0.0), ..player(4) };
+
+        assert!(heals_player(&totem, &owner, 2));
+        assert!(heals_player(&totem, &ally, 2));
+        assert!(!heals_player(&totem, &enemy, 2));
+        assert!(!heals_player(&totem, &far_ally, 2));
+        // Free-for-all totems only heal their owner
+        assert!(!heals_player(&totem, &ally, 0));
+    }
+}
@@ -0,0 +1,88 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - voice_logic.rs
+ *
+ * This file maintains the VoiceProximity table used by spatial voice chat clients.
+ *
+ * Key components:
+ *
+ * 1. Proximity Update:
+ *    - update_voice_proximity: Recomputes which pairs of players are within
+ *      VOICE_CHAT_RADIUS of each other (called from game_tick)
+ *    - Rows are diffed against the previous tick: pairs still in range get their
+ *      distance refreshed, new pairs are inserted and out-of-range pairs deleted,
+ *      so clients only see real changes
+ *
+ * Notes:
+ *    - Each pair is stored once with player_a < player_b, so clients should check both columns
+ *    - Clients derive per-peer gain from `distance`
+ *
+ * Related files:
+ *    - common.rs: VOICE_CHAT_RADIUS
+ *    - lib.rs: VoiceProximity table definition
+ */
+
+use spacetimedb::{ReducerContext, Identity, Table};
+use std::collections::HashMap;
+use crate::common::VOICE_CHAT_RADIUS;
+use crate::{calculate_distance, PlayerData, VoiceProximity};
+// Import table traits
+use crate::{player, voice_proximity};
+
+pub fn update_voice_proximity(ctx: &ReducerContext) {
+    let players: Vec<PlayerData> = ctx.db.player().iter().collect();
+
+    // Pairs that should be audible this tick, keyed with the smaller identity first
+    let mut audible: HashMap<(Identity, Identity), f32> = HashMap::new();
+    for (i, a) in players.iter().enumerate() {
+        for b in players.iter().skip(i + 1) {
+            let distance = calculate_distance(&a.position, &b.position);
+            if distance <= VOICE_CHAT_RADIUS {
+                audible.insert(ordered_pair(a.identity, b.identity), distance);
+            }
+        }
+    }
+
+    // Refresh or remove existing rows
+    for mut row in ctx.db.voice_proximity().iter() {
+        match audible.remove(&(row.player_a, row.player_b)) {
+            Some(distance) => {
+                if (row.distance - distance).abs() > 0.01 {
+                    row.distance = distance;
+                    ctx.db.voice_proximity().id().update(row);
+                }
+            }
+            None => {
+                ctx.db.voice_proximity().id().delete(row.id);
+            }
+        }
+    }
+
+    // Whatever is left is a newly audible pair
+    for ((player_a, player_b), distance) in audible {
+        if let Err(e) = ctx.db.voice_proximity().try_insert(VoiceProximity {
+            id: 0, // auto_inc will set this
+            player_a,
+            player_b,
+            distance,
+        }) {
+            spacetimedb::log::error!("Failed to insert voice proximity pair: {}", e);
+        }
+    }
+}
+
+fn ordered_pair(a: Identity, b: Identity) -> (Identity, Identity) {
+    if a < b { (a, b) } else { (b, a) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::identity;
+
+    #[test]
+    fn pairs_are_stored_in_the_same_order_either_way() {
+        let (a, b) = (identity(1), identity(2));
+        assert_eq!(ordered_pair(a, b), (a, b));
+        assert_eq!(ordered_pair(b, a), (a, b));
+    }
+}
@@ -0,0 +1,148 @@
+/**
+ * Vibe Coding Starter Pack: 3D Multiplayer - votekick_logic.rs
+ *
+ * This file contains player-run vote kicks, for moderation while no admin is online.
+ *
+ * Key components:
+ *
+ * 1. Voting:
+ *    - start_votekick: An active player opens a VoteKick against another active player
+ *      (one open vote per target), counting as its first yes vote. It stays open for
+ *      VOTEKICK_DURATION_MICROS. Refused while fewer than MIN_VOTEKICK_VOTERS could vote
+ *    - cast_votekick_vote: Records an active player's yes or no. Each voter votes once
+ *      per kick and the target can't vote on their own kick
+ *
+ * 2. Game Tick:
+ *    - update_votekicks: Kicks the target through logout_player once yes votes are a
+ *      strict majority of the eligible voters (active, non-AFK human players other than the
+ *      target, see player_logic::is_active_participant), closing the vote. Votes that
+ *      expire first, or whose target already left, are dropped
+ *    - vote_passes: The majority rule. It also needs at least MIN_VOTEKICK_VOTERS eligible
+ *      voters, otherwise the starter's own yes would be a majority in a two-player game
+ *
+ * When modifying:
+ *    - A kick is a normal logout: the target keeps their progress and may rejoin.
+ *      Use ban_logic for anything permanent
+ *    - Only votes from players still active count toward the majority
+ *
+ * Related files:
+ *    - lib.rs: VoteKick table, logout_player and the start_votekick/cast_votekick_vote reducers
+ *    - ban_logic.rs: Admin bans
+ */
+
+use spacetimedb::{ReducerContext, Identity, Table, Timestamp};
+use crate::common::{VOTEKICK_DURATION_MICROS, MIN_VOTEKICK_VOTERS};
+use crate::{config_logic, logout_player, player_logic, GameConfig, VoteKick};
+// Import table traits
+use crate::{player, vote_kick};
+
+pub fn start_votekick(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    if ctx.db.player().identity().find(ctx.sender).is_none() {
+        return Err("Only active players can start a vote kick.".to_string());
+    }
+    if target == ctx.sender {
+        return Err("You cannot vote to kick yourself.".to_string());
+    }
+    if ctx.db.player().identity().find(target).is_none() {
+        return Err("That player is not active.".to_string());
+    }
+    if ctx.db.vote_kick().target().find(target).is_some() {
+        return Err("A vote to kick that player is already open.".to_string());
+    }
+    let config = config_logic::get_config(ctx);
+    if eligible_voters(ctx, target, &config).len() < MIN_VOTEKICK_VOTERS {
+        return Err(format!("A vote kick needs at least {} eligible voters.", MIN_VOTEKICK_VOTERS));
+    }
+
+    let expires_at = Timestamp::from_micros_since_unix_epoch(
+        ctx.timestamp.to_micros_since_unix_epoch() + VOTEKICK_DURATION_MICROS
+    );
+    ctx.db.vote_kick().try_insert(VoteKick {
+        id: 0, // auto_inc will set this
+        target,
+        started_by: ctx.sender,
+        yes_votes: vec![ctx.sender],
+        no_votes: Vec::new(),
+        started_at: ctx.timestamp,
+        expires_at,
+    }).map_err(|e| format!("Failed to start vote kick: {}", e))?;
+    spacetimedb::log::info!("Player {} started a vote to kick {}", ctx.sender, target);
+    Ok(())
+}
+
+pub fn cast_votekick_vote(ctx: &ReducerContext, vote_id: u64, yes: bool) -> Result<(), String> {
+    if ctx.db.player().identity().find(ctx.sender).is_none() {
+        return Err("Only active players can vote.".to_string());
+    }
+    let Some(mut vote) = ctx.db.vote_kick().id().find(vote_id) else {
+        return Err("Vote kick not found.".to_string());
+    };
+    if vote.target == ctx.sender {
+        return Err("You cannot vote on your own kick.".to_string());
+    }
+    if vote.yes_votes.contains(&ctx.sender) || vote.no_votes.contains(&ctx.sender) {
+        return Err("You have already voted.".to_string());
+    }
+
+    if yes {
+        vote.yes_votes.push(ctx.sender);
+    } else {
+        vote.no_votes.push(ctx.sender);
+    }
+    ctx.db.vote_kick().id().update(vote);
+    Ok(())
+}
+
+// Resolve passed votes and drop expired ones (called from game_tick)
+pub fn update_votekicks(ctx: &ReducerContext) {
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let config = config_logic::get_config(ctx);
+
+    for vote in ctx.db.vote_kick().iter() {
+        let Some(target) = ctx.db.player().identity().find(vote.target) else {
+            ctx.db.vote_kick().id().delete(vote.id);
+            continue;
+        };
+
+        let eligible = eligible_voters(ctx, vote.target, &config);
+        let yes = vote.yes_votes.iter().filter(|voter| eligible.contains(voter)).count();
+        if vote_passes(yes, eligible.len()) {
+            ctx.db.vote_kick().id().delete(vote.id);
+            spacetimedb::log::info!("Vote passed ({} of {}), kicking {}", yes, eligible.len(), vote.target);
+            logout_player(ctx, target);
+            continue;
+        }
+
+        if now_micros >= vote.expires_at.to_micros_since_unix_epoch() {
+            ctx.db.vote_kick().id().delete(vote.id);
+            spacetimedb::log::info!("Vote to kick {} expired ({} of {} voted yes)", vote.target, yes, eligible.len());
+        }
+    }
+}
+
+// Active, non-AFK human players who may vote on a kick of `target`
+fn eligible_voters(ctx: &ReducerContext, target: Identity, config: &GameConfig) -> Vec<Identity> {
+    ctx.db.player().iter()
+        .filter(|p| p.identity != target && !p.is_bot)
+        .filter(|p| player_logic::is_active_participant(p, config, ctx.timestamp))
+        .map(|p| p.identity)
+        .collect()
+}
+
+// A strict majority of yes votes among at least MIN_VOTEKICK_VOTERS eligible voters
+pub fn vote_passes(yes: usize, eligible: usize) -> bool {
+    eligible >= MIN_VOTEKICK_VOTERS && yes * 2 > eligible
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vote_kicks_need_a_strict_majority_of_enough_voters() {
+        assert!(!vote_passes(MIN_VOTEKICK_VOTERS - 1, MIN_VOTEKICK_VOTERS - 1));
+        assert!(vote_passes(3, 4));
+        assert!(!vote_passes(2, 4));
+        assert!(vote_passes(3, 5));
+    }
+}